@@ -0,0 +1,56 @@
+#!/usr/bin/env bash
+# Regenerates the `weights.rs` file of every recipe pallet that a runtime benchmarks. Until this is
+# run, those files hold estimates. Build the node with benchmarks first, and run this from the
+# repository's root on hardware like your validators':
+#
+#   cargo build --release -p kitchen-node --features runtime-benchmarks
+#   ./.maintain/benchmark-weights.sh
+
+set -euo pipefail
+
+NODE=./target/release/kitchen-node
+
+# The pallet, the chain whose runtime benchmarks it, and the pallet's weights file. A pallet that
+# weighs its calls by hand has `-` instead, and its results are printed for comparison.
+while read -r pallet chain output; do
+	if [ "$output" = - ]; then
+		written=()
+	else
+		written=(--template=./.maintain/frame-weight-template.hbs --output="$output")
+	fi
+	"$NODE" benchmark \
+		--chain="$chain" \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--pallet="$pallet" \
+		--extrinsic='*' \
+		--steps=50 \
+		--repeat=20 \
+		${written[@]+"${written[@]}"}
+done <<PALLETS
+basic_token dev ./pallets/basic-token/src/weights.rs
+charity dev ./pallets/charity/src/weights.rs
+check_membership_loose dev ./pallets/check-membership/src/loose/weights.rs
+check_membership_tight dev ./pallets/check-membership/src/tight/weights.rs
+compounding_interest dev ./pallets/compounding-interest/src/weights.rs
+constant_config dev ./pallets/constant-config/src/weights.rs
+default_instance dev ./pallets/default-instance/src/weights.rs
+double_map dev ./pallets/double-map/src/weights.rs
+fixed_point dev ./pallets/fixed-point/src/weights.rs
+generic_event dev ./pallets/generic-event/src/weights.rs
+hello_substrate dev ./pallets/hello-substrate/src/weights.rs
+last_caller dev ./pallets/last-caller/src/weights.rs
+lockable_currency dev ./pallets/lockable-currency/src/weights.rs
+map_set dev ./pallets/map-set/src/weights.rs
+randomness dev ./pallets/randomness/src/weights.rs
+reservable_currency dev ./pallets/reservable-currency/src/weights.rs
+ringbuffer_queue dev ./pallets/ringbuffer-queue/src/weights.rs
+simple_crowdfund dev ./pallets/simple-crowdfund/src/weights.rs
+simple_event dev ./pallets/simple-event/src/weights.rs
+simple_map dev ./pallets/simple-map/src/weights.rs
+storage_cache dev ./pallets/storage-cache/src/weights.rs
+struct_storage dev ./pallets/struct-storage/src/weights.rs
+sum_storage api-dev ./pallets/sum-storage/src/weights.rs
+vec_set dev ./pallets/vec-set/src/weights.rs
+weights weight-fee-dev -
+PALLETS
@@ -0,0 +1,132 @@
+//! A reference external miner for nodes that expose the sha3pow RPC.
+//!
+//! The miner repeatedly asks the node for work, tries a batch of nonces, and submits the first
+//! one that meets the difficulty. Between batches it checks back with the node so that it never
+//! spends long mining stale work.
+
+use jsonrpc_core_client::transports::http;
+use rand::{rngs::SmallRng, RngCore, SeedableRng};
+use sha3pow::{hash_meets_difficulty, Blake2b256, Compute, Keccak256, MemoryHard, PowHasher, Sha3};
+use sha3pow_rpc::{gen_client::Client, Work};
+use sp_core::U256;
+use std::{str::FromStr, time::Duration};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "sha3pow-miner")]
+struct Opt {
+	/// HTTP RPC endpoint of the node to mine for
+	#[structopt(long, default_value = "http://localhost:9933")]
+	url: String,
+
+	/// Number of nonces to try before checking whether the node's work changed
+	#[structopt(long, default_value = "100000")]
+	batch_size: u64,
+
+	/// Hash function the node mines with: `sha3`, `keccak` or `blake2`
+	#[structopt(long, default_value = "sha3")]
+	hasher: HasherKind,
+
+	/// Memory cost in KiB of the chain's memory-hard hashing, or 0 if the chain doesn't use it
+	#[structopt(long, default_value = "0")]
+	memory_cost: u32,
+}
+
+/// The hash functions the miner can mine with.
+#[derive(Debug, Clone, Copy)]
+enum HasherKind {
+	Sha3,
+	Keccak,
+	Blake2,
+}
+
+impl FromStr for HasherKind {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		match input {
+			"sha3" => Ok(HasherKind::Sha3),
+			"keccak" => Ok(HasherKind::Keccak),
+			"blake2" => Ok(HasherKind::Blake2),
+			other => Err(format!("Unknown hasher: {}", other)),
+		}
+	}
+}
+
+/// How long to wait before asking an idle node for work again.
+const IDLE_DELAY: Duration = Duration::from_secs(1);
+
+fn main() -> Result<(), String> {
+	let opt = Opt::from_args();
+	match opt.hasher {
+		HasherKind::Sha3 => mine_with(opt, Sha3),
+		HasherKind::Keccak => mine_with(opt, Keccak256),
+		HasherKind::Blake2 => mine_with(opt, Blake2b256),
+	}
+}
+
+/// Mine with the given hasher, wrapped in `MemoryHard` if the chain asks for it.
+fn mine_with<H: PowHasher>(opt: Opt, hasher: H) -> Result<(), String> {
+	let mut runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+	if opt.memory_cost > 0 {
+		let hasher = MemoryHard::new(hasher, opt.memory_cost);
+		runtime.block_on(run(opt, hasher))
+	} else {
+		runtime.block_on(run(opt, hasher))
+	}
+}
+
+async fn run<H: PowHasher>(opt: Opt, hasher: H) -> Result<(), String> {
+	let client = http::connect::<Client>(&opt.url)
+		.await
+		.map_err(|e| format!("Could not connect to {}: {:?}", opt.url, e))?;
+	let mut rng = SmallRng::from_entropy();
+	println!("Mining for {}", opt.url);
+
+	loop {
+		let work = match client.get_work().await {
+			Ok(Some(work)) => work,
+			Ok(None) => {
+				tokio::time::delay_for(IDLE_DELAY).await;
+				continue;
+			}
+			Err(e) => {
+				eprintln!("Failed to fetch work: {:?}", e);
+				tokio::time::delay_for(IDLE_DELAY).await;
+				continue;
+			}
+		};
+
+		// Start from a random point so that several miners don't all try the same nonces.
+		let mut seed = [0u8; 32];
+		rng.fill_bytes(&mut seed);
+		let start = U256::from_big_endian(&seed);
+
+		if let Some(nonce) = search(&hasher, &work, start, opt.batch_size) {
+			match client.submit_work(nonce, Some(work.job_id)).await {
+				Ok(true) => println!("Mined a block with pre-hash {:?}", work.pre_hash),
+				Ok(false) => eprintln!("The node did not import the block we sealed"),
+				Err(e) => eprintln!("Failed to submit work: {:?}", e),
+			}
+		}
+	}
+}
+
+/// Try `count` nonces starting at `start`, and return the first one that solves `work`.
+fn search<H: PowHasher>(hasher: &H, work: &Work, start: U256, count: u64) -> Option<U256> {
+	let mut nonce = start;
+	for _ in 0..count {
+		let seal = Compute {
+			difficulty: work.difficulty,
+			pre_hash: work.pre_hash,
+			nonce,
+		}
+		.compute_with(hasher);
+
+		if hash_meets_difficulty(&seal.work, seal.difficulty) {
+			return Some(nonce);
+		}
+		nonce = nonce.overflowing_add(U256::one()).0;
+	}
+	None
+}
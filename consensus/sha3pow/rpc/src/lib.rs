@@ -0,0 +1,217 @@
+//! RPC interface that lets external miners mine on a node using the sha3pow PoW algorithms.
+//!
+//! A miner asks for work with `sha3pow_getWork`, searches for a nonce that meets the difficulty,
+//! and hands it back with `sha3pow_submitWork`. The node checks the nonce and imports the sealed
+//! block through the `MiningWorker` that `sc_consensus_pow` uses for in-process mining. Submitting
+//! work makes the node author a block, so `sha3pow_submitWork` is an unsafe call.
+//!
+//! `sha3pow_totalWork` reports the total difficulty of the chain ending in a block, which is what
+//! PoW fork choice compares.
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use parity_scale_codec::Encode;
+use parking_lot::Mutex;
+use sc_client_api::backend::AuxStore;
+use sc_consensus_pow::{MiningMetadata, MiningWorker, PowAlgorithm};
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sha3pow::{hash_meets_difficulty, total_difficulty, Compute, PowHasher, Sha3};
+use sp_api::{ProvideRuntimeApi, TransactionFor};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::blake2_256, H256, U256};
+use sp_runtime::traits::Block as BlockT;
+use std::{marker::PhantomData, sync::Arc};
+
+/// A package of work for an external miner.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+	/// Identifies the work package. Two packages share an id only if they ask for the same seal.
+	pub job_id: H256,
+	/// Hash of the block being mined, before the seal is attached
+	pub pre_hash: H256,
+	/// Difficulty the seal must meet
+	pub difficulty: U256,
+}
+
+impl Work {
+	fn from_metadata(metadata: &MiningMetadata<H256, U256>) -> Self {
+		Self {
+			job_id: job_id(&metadata.pre_hash, &metadata.difficulty),
+			pre_hash: metadata.pre_hash,
+			difficulty: metadata.difficulty,
+		}
+	}
+}
+
+/// The job id of the work to seal `pre_hash` at `difficulty`.
+fn job_id(pre_hash: &H256, difficulty: &U256) -> H256 {
+	H256::from(blake2_256(&(pre_hash, difficulty).encode()))
+}
+
+#[rpc]
+pub trait Sha3PowApi {
+	/// Get the work currently being mined, if any.
+	#[rpc(name = "sha3pow_getWork")]
+	fn get_work(&self) -> Result<Option<Work>>;
+
+	/// Submit a nonce that solves the current work. If a job id is given, the nonce is only
+	/// accepted if that job is still current. Returns whether the sealed block was imported.
+	/// This is an unsafe call, so it is refused on RPC interfaces that deny those.
+	#[rpc(name = "sha3pow_submitWork")]
+	fn submit_work(&self, nonce: U256, job_id: Option<H256>) -> Result<bool>;
+}
+
+// Only the server side is generated, because a second `gen_client` would clash with the miner's.
+#[rpc(server)]
+pub trait TotalWorkApi<BlockHash> {
+	/// Get the total difficulty of the chain ending in the given block, or in the best block if
+	/// none is given.
+	#[rpc(name = "sha3pow_totalWork")]
+	fn total_work(&self, at: Option<BlockHash>) -> Result<U256>;
+}
+
+/// Errors these RPC apis can return.
+pub enum Error {
+	/// The node has no work to hand out
+	NoWork,
+	/// The submitted work is not the node's current work
+	StaleWork,
+	/// The nonce does not meet the difficulty
+	InsufficientWork,
+	/// The work recorded for a block could not be read
+	ChainLookup,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::NoWork => 1,
+			Error::StaleWork => 2,
+			Error::InsufficientWork => 3,
+			Error::ChainLookup => 4,
+		}
+	}
+}
+
+fn rpc_error(error: Error, message: &str) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(error.into()),
+		message: message.into(),
+		data: None,
+	}
+}
+
+/// A struct that implements the `Sha3PowApi`. The hasher must be the one the node mines with.
+pub struct Sha3Pow<B: BlockT, A: PowAlgorithm<B>, C: ProvideRuntimeApi<B>, H = Sha3> {
+	worker: Arc<Mutex<MiningWorker<B, A, C>>>,
+	hasher: H,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<B: BlockT, A: PowAlgorithm<B>, C: ProvideRuntimeApi<B>> Sha3Pow<B, A, C> {
+	/// Create a new `Sha3Pow` instance that hands out the work of the given mining worker.
+	pub fn new(worker: Arc<Mutex<MiningWorker<B, A, C>>>, deny_unsafe: DenyUnsafe) -> Self {
+		Self::with_hasher(worker, Sha3, deny_unsafe)
+	}
+}
+
+impl<B: BlockT, A: PowAlgorithm<B>, C: ProvideRuntimeApi<B>, H> Sha3Pow<B, A, C, H> {
+	/// Create a new `Sha3Pow` instance for a node that mines with the given hasher.
+	pub fn with_hasher(
+		worker: Arc<Mutex<MiningWorker<B, A, C>>>,
+		hasher: H,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self {
+			worker,
+			hasher,
+			deny_unsafe,
+		}
+	}
+}
+
+impl<B, A, C, H> Sha3PowApi for Sha3Pow<B, A, C, H>
+where
+	B: BlockT<Hash = H256>,
+	A: PowAlgorithm<B, Difficulty = U256> + Send + 'static,
+	C: ProvideRuntimeApi<B> + Send + Sync + 'static,
+	TransactionFor<C, B>: Send + 'static,
+	H: PowHasher,
+{
+	fn get_work(&self) -> Result<Option<Work>> {
+		Ok(self
+			.worker
+			.lock()
+			.metadata()
+			.as_ref()
+			.map(Work::from_metadata))
+	}
+
+	fn submit_work(&self, nonce: U256, job_id: Option<H256>) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let mut worker = self.worker.lock();
+		let work = worker
+			.metadata()
+			.as_ref()
+			.map(Work::from_metadata)
+			.ok_or_else(|| rpc_error(Error::NoWork, "The node is not mining"))?;
+
+		if job_id.map_or(false, |job_id| job_id != work.job_id) {
+			return Err(rpc_error(
+				Error::StaleWork,
+				"The job is no longer being mined",
+			));
+		}
+
+		// Check the answer ourselves before bothering the worker with it.
+		let seal = Compute {
+			difficulty: work.difficulty,
+			pre_hash: work.pre_hash,
+			nonce,
+		}
+		.compute_with(&self.hasher);
+		if !hash_meets_difficulty(&seal.work, seal.difficulty) {
+			return Err(rpc_error(
+				Error::InsufficientWork,
+				"The nonce does not meet the difficulty",
+			));
+		}
+
+		Ok(worker.submit(seal.encode()))
+	}
+}
+
+/// A struct that implements the `TotalWorkApi`.
+pub struct TotalWork<B, C> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<B, C> TotalWork<B, C> {
+	/// Create a new `TotalWork` instance that reads the work recorded by the given client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B, C> TotalWorkApi<B::Hash> for TotalWork<B, C>
+where
+	B: BlockT,
+	C: AuxStore + HeaderBackend<B> + Send + Sync + 'static,
+{
+	fn total_work(&self, at: Option<B::Hash>) -> Result<U256> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		total_difficulty::<B, _>(&*self.client, &at).map_err(|e| {
+			rpc_error(
+				Error::ChainLookup,
+				&format!("Unable to read the total work: {}", e),
+			)
+		})
+	}
+}
@@ -0,0 +1,53 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unnecessary_mut_passed)]
+//! The runtime API through which a proof of work chain tells its nodes how to hash.
+//!
+//! The hashing is part of the consensus rules, so it belongs to the chain rather than to the node.
+//! A runtime sets it at genesis and reports it here, and the node builds the matching `PowHasher`.
+
+use parity_scale_codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+/// The plain hash functions a chain can mine with.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum HashFunction {
+	Sha3,
+	Keccak256,
+	Blake2b256,
+}
+
+/// How a chain hashes its proof of work.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PowHashing {
+	/// The hash function blocks are sealed with
+	pub hash_function: HashFunction,
+	/// The memory in KiB each hash uses when the hash function is made memory-hard. Zero means
+	/// the hash function is used on its own.
+	pub memory_cost: u32,
+}
+
+impl Default for PowHashing {
+	/// Plain Sha3, which is what the recipes have always mined with.
+	fn default() -> Self {
+		Self {
+			hash_function: HashFunction::Sha3,
+			memory_cost: 0,
+		}
+	}
+}
+
+// Here we declare the runtime API. It is implemented in the `impl` block in
+// runtime amalgamator file (the `runtime/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+	/// Reports how the chain hashes its proof of work.
+	pub trait PowHashingApi {
+		/// The hashing every seal on the chain must be computed with.
+		fn pow_hashing() -> PowHashing;
+	}
+}
@@ -0,0 +1,165 @@
+//! Fork choice by total difficulty.
+//!
+//! `sc_consensus_pow::PowBlockImport` records, for every block it imports, the block's difficulty
+//! and the total difficulty of the chain ending in it. These are stored as a `PowAux` in the
+//! client's auxiliary database. The heaviest chain is the one whose tip has the greatest total
+//! difficulty, which is not necessarily the one with the most blocks.
+
+use sc_client_api::{backend::AuxStore, Backend};
+use sc_consensus_pow::{Error, PowAux};
+use sp_blockchain::{Backend as _, HeaderBackend};
+use sp_consensus::{Error as ConsensusError, SelectChain};
+use sp_core::U256;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, Zero},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The total difficulty of the chain ending in the block with the given hash. Blocks that were
+/// never imported through `PowBlockImport`, such as the genesis block, count as zero.
+pub fn total_difficulty<B: BlockT, C: AuxStore>(
+	client: &C,
+	hash: &B::Hash,
+) -> Result<U256, Error<B>> {
+	PowAux::<U256>::read::<_, B>(client, hash).map(|aux| aux.total_difficulty)
+}
+
+/// Of the given blocks, the one that ends the heaviest chain. Ties go to whichever block comes
+/// first, so callers should list their preferred blocks first.
+pub fn heaviest<B: BlockT, C: AuxStore>(
+	client: &C,
+	candidates: impl IntoIterator<Item = B::Hash>,
+) -> Result<Option<B::Hash>, Error<B>> {
+	let mut best: Option<(B::Hash, U256)> = None;
+	for hash in candidates {
+		let work = total_difficulty::<B, _>(client, &hash)?;
+		if best
+			.as_ref()
+			.map_or(true, |(_, best_work)| work > *best_work)
+		{
+			best = Some((hash, work));
+		}
+	}
+	Ok(best.map(|(hash, _)| hash))
+}
+
+/// A `SelectChain` that builds on the leaf with the greatest total difficulty.
+///
+/// This is the PoW counterpart of `sc_consensus::LongestChain`, which counts blocks rather than
+/// work.
+pub struct HeaviestChain<B, BE> {
+	backend: Arc<BE>,
+	_phantom: PhantomData<B>,
+}
+
+impl<B, BE> HeaviestChain<B, BE> {
+	pub fn new(backend: Arc<BE>) -> Self {
+		Self {
+			backend,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<B: BlockT, BE: Backend<B>> HeaviestChain<B, BE> {
+	fn header(&self, hash: B::Hash) -> Result<B::Header, ConsensusError> {
+		self.backend
+			.blockchain()
+			.header(BlockId::Hash(hash))
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?
+			.ok_or_else(|| {
+				ConsensusError::ChainLookup(format!("Missing header for block {}", hash))
+			})
+	}
+
+	/// Whether the block with the given hash is `ancestor` or one of its descendants
+	fn descends_from(
+		&self,
+		hash: B::Hash,
+		ancestor: B::Hash,
+		ancestor_number: NumberFor<B>,
+	) -> Result<bool, ConsensusError> {
+		let mut header = self.header(hash)?;
+		while *header.number() > ancestor_number {
+			header = self.header(*header.parent_hash())?;
+		}
+		Ok(header.hash() == ancestor)
+	}
+}
+
+// Manually implement clone, because the backend isn't Clone. Only the `Arc` is.
+impl<B, BE> Clone for HeaviestChain<B, BE> {
+	fn clone(&self) -> Self {
+		Self::new(self.backend.clone())
+	}
+}
+
+impl<B: BlockT, BE: Backend<B>> SelectChain<B> for HeaviestChain<B, BE> {
+	fn leaves(&self) -> Result<Vec<B::Hash>, ConsensusError> {
+		self.backend
+			.blockchain()
+			.leaves()
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))
+	}
+
+	fn best_chain(&self) -> Result<B::Header, ConsensusError> {
+		let info = self.backend.blockchain().info();
+		let current_best = info.best_hash;
+
+		// Like `PowBlockImport`, only switch away from the current best block for strictly more
+		// work. Other ties go to the longest chain, because leaves are sorted by descending number.
+		// Blocks that don't build on the last finalized block can't be imported, so forks that
+		// left the finalized chain are out, however heavy. Every fork builds on genesis, so there's
+		// nothing to check until something else is finalized.
+		let mut candidates = vec![current_best];
+		for leaf in self.leaves()? {
+			if leaf != current_best
+				&& (info.finalized_number.is_zero()
+					|| self.descends_from(leaf, info.finalized_hash, info.finalized_number)?)
+			{
+				candidates.push(leaf);
+			}
+		}
+
+		let best_hash = heaviest::<B, _>(&*self.backend, candidates)
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?
+			.unwrap_or(current_best);
+
+		self.header(best_hash)
+	}
+
+	/// The block that finality gadgets like GRANDPA should vote for: the tip of the heaviest chain
+	/// that contains `target_hash`, or its ancestor at `maybe_max_number`. Without this, votes
+	/// would never go past `target_hash`.
+	fn finality_target(
+		&self,
+		target_hash: B::Hash,
+		maybe_max_number: Option<NumberFor<B>>,
+	) -> Result<Option<B::Hash>, ConsensusError> {
+		let target_number = *self.header(target_hash)?.number();
+
+		// Heaviest first. The sort is stable, so ties still go to the longest chain.
+		let mut leaves = self
+			.leaves()?
+			.into_iter()
+			.map(|leaf| Ok((total_difficulty::<B, _>(&*self.backend, &leaf)?, leaf)))
+			.collect::<Result<Vec<_>, Error<B>>>()
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?;
+		leaves.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+		for (_, leaf) in leaves {
+			let mut header = self.header(leaf)?;
+			if let Some(max_number) = maybe_max_number {
+				while *header.number() > max_number {
+					header = self.header(*header.parent_hash())?;
+				}
+			}
+			if self.descends_from(header.hash(), target_hash, target_number)? {
+				return Ok(Some(header.hash()));
+			}
+		}
+
+		Ok(None)
+	}
+}
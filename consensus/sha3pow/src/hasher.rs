@@ -0,0 +1,156 @@
+//! The hash functions the proof of work algorithms can be built on.
+//!
+//! Every algorithm in this crate seals blocks the same way: it hashes the encoded `Compute` and
+//! checks the result against the difficulty. Only the hash function differs, and it is supplied by
+//! a `PowHasher`.
+
+use sha3::{Digest, Keccak256 as Keccak256Digest, Sha3_256};
+use sha3pow_runtime_api::{HashFunction, PowHashing};
+use sp_core::{hashing::blake2_256, H256};
+
+/// A hash function that can secure a proof of work chain.
+///
+/// Hashers are values rather than bare types so that they can carry parameters, such as the
+/// memory cost of `MemoryHard`, which every node on a chain must agree on.
+pub trait PowHasher: Clone + Send + Sync + 'static {
+	/// Hash the given data down to 32 bytes.
+	fn hash(&self, data: &[u8]) -> H256;
+}
+
+/// The Sha3-256 hash function. This is what the recipes have always mined with.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Sha3;
+
+impl PowHasher for Sha3 {
+	fn hash(&self, data: &[u8]) -> H256 {
+		H256::from_slice(Sha3_256::digest(data).as_slice())
+	}
+}
+
+/// The Keccak-256 hash function, as used by Ethereum. It differs from Sha3-256 only in padding.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Keccak256;
+
+impl PowHasher for Keccak256 {
+	fn hash(&self, data: &[u8]) -> H256 {
+		H256::from_slice(Keccak256Digest::digest(data).as_slice())
+	}
+}
+
+/// The Blake2b hash function with a 256 bit output, as used throughout Substrate.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Blake2b256;
+
+impl PowHasher for Blake2b256 {
+	fn hash(&self, data: &[u8]) -> H256 {
+		H256::from(blake2_256(data))
+	}
+}
+
+/// A memory-hard hash built on top of another hasher.
+///
+/// Each hash fills a buffer of `memory_cost` KiB by repeatedly hashing the input, then walks the
+/// buffer in an order that depends on its contents, mixing every visited entry back in. Computing a
+/// hash without keeping the whole buffer around means recomputing large parts of it, which blunts
+/// the advantage of specialised hardware. The price is that every hash, including the one done to
+/// verify a block, takes about `memory_cost * 64` calls of the inner hasher.
+///
+/// The memory cost is part of the consensus rules, so every node on a chain must use the same one.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryHard<H> {
+	inner: H,
+	memory_cost: u32,
+}
+
+/// The size of one entry in the `MemoryHard` buffer, in bytes.
+const ENTRY_SIZE: usize = 32;
+
+impl<H: PowHasher> MemoryHard<H> {
+	/// Create a memory-hard hasher that uses `memory_cost` KiB of memory per hash.
+	/// A cost of zero is treated as one.
+	pub fn new(inner: H, memory_cost: u32) -> Self {
+		Self {
+			inner,
+			memory_cost: memory_cost.max(1),
+		}
+	}
+
+	/// The memory used by each hash, in KiB.
+	pub fn memory_cost(&self) -> u32 {
+		self.memory_cost
+	}
+
+	fn entries(&self) -> usize {
+		self.memory_cost as usize * 1024 / ENTRY_SIZE
+	}
+}
+
+impl<H: PowHasher> PowHasher for MemoryHard<H> {
+	fn hash(&self, data: &[u8]) -> H256 {
+		let entries = self.entries();
+
+		// Fill the buffer with a hash chain seeded by the input.
+		let mut current = self.inner.hash(data);
+		let mut memory = Vec::with_capacity(entries);
+		for _ in 0..entries {
+			memory.push(current);
+			current = self.inner.hash(current.as_bytes());
+		}
+
+		// Visit the buffer in a data-dependent order, so it can't be computed lazily.
+		let mut mix = [0u8; 2 * ENTRY_SIZE];
+		for _ in 0..entries {
+			let mut index = [0u8; 8];
+			index.copy_from_slice(&current[..8]);
+			let index = (u64::from_le_bytes(index) % entries as u64) as usize;
+
+			mix[..ENTRY_SIZE].copy_from_slice(current.as_bytes());
+			mix[ENTRY_SIZE..].copy_from_slice(memory[index].as_bytes());
+			current = self.inner.hash(&mix);
+			memory[index] = current;
+		}
+
+		current
+	}
+}
+
+impl PowHasher for HashFunction {
+	fn hash(&self, data: &[u8]) -> H256 {
+		match self {
+			HashFunction::Sha3 => Sha3.hash(data),
+			HashFunction::Keccak256 => Keccak256.hash(data),
+			HashFunction::Blake2b256 => Blake2b256.hash(data),
+		}
+	}
+}
+
+/// The hasher a chain's `PowHashing` describes.
+///
+/// Unlike the other hashers, which are picked when the node is compiled, this one is picked when
+/// the node starts, so the same node can mine any chain that reports its hashing through the
+/// `PowHashingApi`.
+#[derive(Clone, Copy, Debug)]
+pub enum ChainHasher {
+	/// A hash function used on its own
+	Plain(HashFunction),
+	/// A hash function made memory-hard
+	MemoryHard(MemoryHard<HashFunction>),
+}
+
+impl From<PowHashing> for ChainHasher {
+	fn from(hashing: PowHashing) -> Self {
+		match hashing.memory_cost {
+			0 => ChainHasher::Plain(hashing.hash_function),
+			cost => ChainHasher::MemoryHard(MemoryHard::new(hashing.hash_function, cost)),
+		}
+	}
+}
+
+impl PowHasher for ChainHasher {
+	fn hash(&self, data: &[u8]) -> H256 {
+		match self {
+			ChainHasher::Plain(hasher) => hasher.hash(data),
+			ChainHasher::MemoryHard(hasher) => hasher.hash(data),
+		}
+	}
+}
@@ -1,13 +1,30 @@
+//! Proof of work algorithms for Substrate, generic over the hash function they mine with.
+//!
+//! The Sha3 flavours are available as `MinimalSha3Algorithm` and `Sha3Algorithm`. Other hash
+//! functions can be plugged in through the `PowHasher` trait. A node that should follow whatever
+//! hashing the chain asks for reads it with `genesis_hasher` and mines with the `ChainHasher`.
+
 use parity_scale_codec::{Decode, Encode};
 use sc_consensus_pow::{Error, PowAlgorithm};
-use sha3::{Digest, Sha3_256};
 use sp_api::ProvideRuntimeApi;
 use sp_consensus_pow::{DifficultyApi, Seal as RawSeal};
 use sp_core::{H256, U256};
 use sp_runtime::generic::BlockId;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, Zero};
 use std::sync::Arc;
 
+mod fork_choice;
+mod hasher;
+mod mining;
+
+pub use fork_choice::{heaviest, total_difficulty, HeaviestChain};
+pub use hasher::{Blake2b256, ChainHasher, Keccak256, MemoryHard, PowHasher, Sha3};
+pub use mining::{start_miner, MiningMetrics};
+pub use sha3pow_runtime_api::{HashFunction, PowHashing, PowHashingApi};
+
+#[cfg(test)]
+mod tests;
+
 /// Determine whether the given hash satisfies the given difficulty.
 /// The test is done by multiplying the two together. If the product
 /// overflows the bounds of U256, then the product (and thus the hash)
@@ -19,6 +36,26 @@ pub fn hash_meets_difficulty(hash: &H256, difficulty: U256) -> bool {
 	!overflowed
 }
 
+/// Read the hashing the chain mines with from its genesis state, through the `PowHashingApi`.
+///
+/// The hashing is fixed at genesis, so it only needs to be read once, when the node starts.
+pub fn genesis_hasher<B: BlockT, C>(client: &C) -> Result<ChainHasher, String>
+where
+	C: ProvideRuntimeApi<B>,
+	C::Api: PowHashingApi<B>,
+{
+	client
+		.runtime_api()
+		.pow_hashing(&BlockId::Number(Zero::zero()))
+		.map(Into::into)
+		.map_err(|err| {
+			format!(
+				"Fetching the PoW hashing from the runtime failed: {:?}",
+				err
+			)
+		})
+}
+
 /// A Seal struct that will be encoded to a Vec<u8> as used as the
 /// `RawSeal` type.
 #[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
@@ -38,8 +75,14 @@ pub struct Compute {
 }
 
 impl Compute {
+	/// Compute the seal with Sha3 hashing.
 	pub fn compute(self) -> Seal {
-		let work = H256::from_slice(Sha3_256::digest(&self.encode()[..]).as_slice());
+		self.compute_with(&Sha3)
+	}
+
+	/// Compute the seal with the given hasher.
+	pub fn compute_with<H: PowHasher>(self, hasher: &H) -> Seal {
+		let work = hasher.hash(&self.encode()[..]);
 
 		Seal {
 			nonce: self.nonce,
@@ -49,13 +92,53 @@ impl Compute {
 	}
 }
 
-/// A minimal PoW algorithm that uses Sha3 hashing.
+/// Check that a raw seal proves the work needed to seal `pre_hash` at `difficulty`, using the
+/// given hasher.
+pub fn verify_seal<H: PowHasher>(
+	hasher: &H,
+	pre_hash: &H256,
+	seal: &RawSeal,
+	difficulty: U256,
+) -> bool {
+	// Try to construct a seal object by decoding the raw seal given
+	let seal = match Seal::decode(&mut &seal[..]) {
+		Ok(seal) => seal,
+		Err(_) => return false,
+	};
+
+	// See whether the hash meets the difficulty requirement. If not, fail fast.
+	if !hash_meets_difficulty(&seal.work, difficulty) {
+		return false;
+	}
+
+	// Make sure the provided work actually comes from the correct pre_hash
+	let compute = Compute {
+		difficulty,
+		pre_hash: *pre_hash,
+		nonce: seal.nonce,
+	};
+
+	compute.compute_with(hasher) == seal
+}
+
+/// A minimal PoW algorithm that works with any `PowHasher`.
 /// Difficulty is fixed at 1_000_000
-#[derive(Clone)]
-pub struct MinimalSha3Algorithm;
+#[derive(Clone, Default)]
+pub struct MinimalPowAlgorithm<H> {
+	hasher: H,
+}
 
-// Here we implement the general PowAlgorithm trait for our concrete Sha3Algorithm
-impl<B: BlockT<Hash = H256>> PowAlgorithm<B> for MinimalSha3Algorithm {
+impl<H> MinimalPowAlgorithm<H> {
+	pub fn new(hasher: H) -> Self {
+		Self { hasher }
+	}
+}
+
+/// A minimal PoW algorithm that uses Sha3 hashing.
+pub type MinimalSha3Algorithm = MinimalPowAlgorithm<Sha3>;
+
+// Here we implement the general PowAlgorithm trait for our concrete MinimalPowAlgorithm
+impl<B: BlockT<Hash = H256>, H: PowHasher> PowAlgorithm<B> for MinimalPowAlgorithm<H> {
 	type Difficulty = U256;
 
 	fn difficulty(&self, _parent: B::Hash) -> Result<Self::Difficulty, Error<B>> {
@@ -71,54 +154,42 @@ impl<B: BlockT<Hash = H256>> PowAlgorithm<B> for MinimalSha3Algorithm {
 		seal: &RawSeal,
 		difficulty: Self::Difficulty,
 	) -> Result<bool, Error<B>> {
-		// Try to construct a seal object by decoding the raw seal given
-		let seal = match Seal::decode(&mut &seal[..]) {
-			Ok(seal) => seal,
-			Err(_) => return Ok(false),
-		};
-
-		// See whether the hash meets the difficulty requirement. If not, fail fast.
-		if !hash_meets_difficulty(&seal.work, difficulty) {
-			return Ok(false);
-		}
-
-		// Make sure the provided work actually comes from the correct pre_hash
-		let compute = Compute {
-			difficulty,
-			pre_hash: *pre_hash,
-			nonce: seal.nonce,
-		};
-
-		if compute.compute() != seal {
-			return Ok(false);
-		}
-
-		Ok(true)
+		Ok(verify_seal(&self.hasher, pre_hash, seal, difficulty))
 	}
 }
 
-/// A complete PoW Algorithm that uses Sha3 hashing.
+/// A complete PoW Algorithm that works with any `PowHasher`.
 /// Needs a reference to the client so it can grab the difficulty from the runtime.
-pub struct Sha3Algorithm<C> {
+pub struct RuntimePowAlgorithm<C, H> {
 	client: Arc<C>,
+	hasher: H,
 }
 
-impl<C> Sha3Algorithm<C> {
+impl<C, H: Default> RuntimePowAlgorithm<C, H> {
 	pub fn new(client: Arc<C>) -> Self {
-		Self { client }
+		Self::with_hasher(client, H::default())
 	}
 }
 
+impl<C, H> RuntimePowAlgorithm<C, H> {
+	pub fn with_hasher(client: Arc<C>, hasher: H) -> Self {
+		Self { client, hasher }
+	}
+}
+
+/// A complete PoW Algorithm that uses Sha3 hashing.
+pub type Sha3Algorithm<C> = RuntimePowAlgorithm<C, Sha3>;
+
 // Manually implement clone. Deriving doesn't work because
-// it'll derive impl<C: Clone> Clone for Sha3Algorithm<C>. But C in practice isn't Clone.
-impl<C> Clone for Sha3Algorithm<C> {
+// it'll derive impl<C: Clone> Clone for RuntimePowAlgorithm<C, H>. But C in practice isn't Clone.
+impl<C, H: Clone> Clone for RuntimePowAlgorithm<C, H> {
 	fn clone(&self) -> Self {
-		Self::new(self.client.clone())
+		Self::with_hasher(self.client.clone(), self.hasher.clone())
 	}
 }
 
-// Here we implement the general PowAlgorithm trait for our concrete Sha3Algorithm
-impl<B: BlockT<Hash = H256>, C> PowAlgorithm<B> for Sha3Algorithm<C>
+// Here we implement the general PowAlgorithm trait for our concrete RuntimePowAlgorithm
+impl<B: BlockT<Hash = H256>, C, H: PowHasher> PowAlgorithm<B> for RuntimePowAlgorithm<C, H>
 where
 	C: ProvideRuntimeApi<B>,
 	C::Api: DifficultyApi<B, U256>,
@@ -146,28 +217,6 @@ where
 		seal: &RawSeal,
 		difficulty: Self::Difficulty,
 	) -> Result<bool, Error<B>> {
-		// Try to construct a seal object by decoding the raw seal given
-		let seal = match Seal::decode(&mut &seal[..]) {
-			Ok(seal) => seal,
-			Err(_) => return Ok(false),
-		};
-
-		// See whether the hash meets the difficulty requirement. If not, fail fast.
-		if !hash_meets_difficulty(&seal.work, difficulty) {
-			return Ok(false);
-		}
-
-		// Make sure the provided work actually comes from the correct pre_hash
-		let compute = Compute {
-			difficulty,
-			pre_hash: *pre_hash,
-			nonce: seal.nonce,
-		};
-
-		if compute.compute() != seal {
-			return Ok(false);
-		}
-
-		Ok(true)
+		Ok(verify_seal(&self.hasher, pre_hash, seal, difficulty))
 	}
 }
@@ -0,0 +1,261 @@
+//! A multi-threaded miner for the sha3pow proof of work algorithms.
+//!
+//! The `MiningWorker` from `sc_consensus_pow` prepares blocks and imports them once they are
+//! sealed, but searching for the seal is up to the node. Here several threads split the nonce
+//! space between them, drop their work as soon as a new best block is imported, and report how
+//! hard they are working through Prometheus.
+
+use crate::{hash_meets_difficulty, Compute, PowHasher};
+use futures::{future, Future, StreamExt};
+use log::debug;
+use parity_scale_codec::Encode;
+use parking_lot::{Mutex, RwLock};
+use rand::{rngs::SmallRng, RngCore, SeedableRng};
+use sc_client_api::BlockchainEvents;
+use sc_consensus_pow::{MiningWorker, PowAlgorithm};
+use sp_api::{ProvideRuntimeApi, TransactionFor};
+use sp_blockchain::HeaderBackend;
+use sp_core::{H256, U256};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::{
+	sync::Arc,
+	thread,
+	time::{Duration, Instant},
+};
+use substrate_prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+
+/// How many nonces a thread tries before checking whether its work went stale.
+const BATCH_SIZE: u64 = 10_000;
+
+/// How long an idle thread waits before looking for new work again.
+const IDLE_SLEEP: Duration = Duration::from_millis(100);
+
+/// How often the hashrate is recomputed.
+const HASHRATE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Prometheus metrics describing the local miner.
+#[derive(Clone)]
+pub struct MiningMetrics {
+	hashrate: Gauge<U64>,
+	attempts: Counter<U64>,
+	seals_found: Counter<U64>,
+}
+
+impl MiningMetrics {
+	/// Create the metrics and register them with the node's Prometheus registry.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			hashrate: register(
+				Gauge::new(
+					"pow_mining_hashrate",
+					"Hashes per second computed by the local miner",
+				)?,
+				registry,
+			)?,
+			attempts: register(
+				Counter::new(
+					"pow_mining_attempts_total",
+					"Number of nonces tried by the local miner",
+				)?,
+				registry,
+			)?,
+			seals_found: register(
+				Counter::new(
+					"pow_mining_seals_found_total",
+					"Number of seals found by the local miner and imported",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// State shared between the mining threads and the task that follows the chain.
+struct Shared {
+	/// The best block known to the node. Work that builds on any other block is stale.
+	best_hash: RwLock<H256>,
+	/// Bumped whenever the best block changes, so threads can notice stale work cheaply.
+	version: AtomicU64,
+	/// Total number of nonces tried. Used to compute the hashrate.
+	attempts: AtomicU64,
+	/// Set when the node shuts down.
+	stop: AtomicBool,
+}
+
+impl Shared {
+	fn stopped(&self) -> bool {
+		self.stop.load(Ordering::Relaxed)
+	}
+}
+
+/// Tells the mining threads to stop when dropped.
+struct StopOnDrop(Arc<Shared>);
+
+impl Drop for StopOnDrop {
+	fn drop(&mut self) {
+		self.0.stop.store(true, Ordering::Relaxed);
+	}
+}
+
+/// Start `threads` mining threads that search for seals for the work offered by `worker`, hashing
+/// with `hasher`. With zero threads nothing is mined locally, and the work is left to external
+/// miners.
+///
+/// The returned future follows the best block so the threads can abandon stale work. It should be
+/// spawned on the node's task manager. When it is dropped at shutdown, the threads stop as well.
+pub fn start_miner<B, A, C, H>(
+	worker: Arc<Mutex<MiningWorker<B, A, C>>>,
+	client: Arc<C>,
+	hasher: H,
+	threads: usize,
+	metrics: Option<MiningMetrics>,
+) -> impl Future<Output = ()>
+where
+	B: BlockT<Hash = H256>,
+	A: PowAlgorithm<B, Difficulty = U256> + Send + 'static,
+	C: ProvideRuntimeApi<B> + BlockchainEvents<B> + HeaderBackend<B> + Send + Sync + 'static,
+	TransactionFor<C, B>: Send + 'static,
+	H: PowHasher,
+{
+	let shared = Arc::new(Shared {
+		best_hash: RwLock::new(client.info().best_hash),
+		version: AtomicU64::new(0),
+		attempts: AtomicU64::new(0),
+		stop: AtomicBool::new(false),
+	});
+
+	for index in 0..threads {
+		let worker = worker.clone();
+		let shared = shared.clone();
+		let hasher = hasher.clone();
+		let metrics = metrics.clone();
+		thread::Builder::new()
+			.name(format!("pow-miner-{}", index))
+			.spawn(move || mine(index, threads, worker, hasher, shared, metrics))
+			.expect("Spawning a mining thread should succeed");
+	}
+
+	{
+		let shared = shared.clone();
+		thread::Builder::new()
+			.name("pow-hashrate".into())
+			.spawn(move || report_hashrate(shared, metrics))
+			.expect("Spawning the hashrate thread should succeed");
+	}
+
+	let stop_on_drop = StopOnDrop(shared.clone());
+	client
+		.import_notification_stream()
+		.for_each(move |notification| {
+			// Keep the guard alive for as long as we follow the chain.
+			let _ = &stop_on_drop;
+			if notification.is_new_best {
+				*shared.best_hash.write() = notification.hash;
+				shared.version.fetch_add(1, Ordering::SeqCst);
+			}
+			future::ready(())
+		})
+}
+
+/// The body of a single mining thread.
+///
+/// For any given piece of work, thread `index` only tries nonces that are congruent to `index`
+/// modulo `threads`, so no two threads ever try the same nonce.
+fn mine<B, A, C, H>(
+	index: usize,
+	threads: usize,
+	worker: Arc<Mutex<MiningWorker<B, A, C>>>,
+	hasher: H,
+	shared: Arc<Shared>,
+	metrics: Option<MiningMetrics>,
+) where
+	B: BlockT<Hash = H256>,
+	A: PowAlgorithm<B, Difficulty = U256>,
+	C: ProvideRuntimeApi<B>,
+	TransactionFor<C, B>: Send + 'static,
+	H: PowHasher,
+{
+	let mut rng = SmallRng::from_entropy();
+	let step = U256::from(threads);
+
+	while !shared.stopped() {
+		let version = shared.version.load(Ordering::SeqCst);
+		let metadata = match worker.lock().metadata() {
+			Some(metadata) if metadata.best_hash == *shared.best_hash.read() => metadata,
+			// Either there is no work yet, or the worker hasn't caught up with the best block.
+			_ => {
+				thread::sleep(IDLE_SLEEP);
+				continue;
+			}
+		};
+
+		// Start from a random point so that different miners don't all try the same nonces.
+		let mut seed = [0u8; 32];
+		rng.fill_bytes(&mut seed);
+		let base = U256::from_big_endian(&seed);
+		let mut nonce = (base - base % step).overflowing_add(U256::from(index)).0;
+
+		'work: while shared.version.load(Ordering::SeqCst) == version && !shared.stopped() {
+			let mut tried = 0;
+			let mut found = None;
+			while tried < BATCH_SIZE {
+				let seal = Compute {
+					difficulty: metadata.difficulty,
+					pre_hash: metadata.pre_hash,
+					nonce,
+				}
+				.compute_with(&hasher);
+				tried += 1;
+
+				if hash_meets_difficulty(&seal.work, seal.difficulty) {
+					found = Some(seal);
+					break;
+				}
+				nonce = nonce.overflowing_add(step).0;
+			}
+
+			shared.attempts.fetch_add(tried, Ordering::Relaxed);
+			if let Some(metrics) = &metrics {
+				metrics.attempts.inc_by(tried);
+			}
+
+			let mut worker = worker.lock();
+			// The worker rebuilds its proposal from time to time, for example to include new
+			// transactions. Once it has, our work is stale.
+			if worker.metadata().map(|m| m.pre_hash) != Some(metadata.pre_hash) {
+				break 'work;
+			}
+
+			if let Some(seal) = found {
+				if worker.submit(seal.encode()) {
+					if let Some(metrics) = &metrics {
+						metrics.seals_found.inc();
+					}
+				}
+				break 'work;
+			}
+		}
+	}
+}
+
+/// Periodically compute the miner's hashrate and report it.
+fn report_hashrate(shared: Arc<Shared>, metrics: Option<MiningMetrics>) {
+	let mut last_time = Instant::now();
+	let mut last_attempts = 0;
+
+	while !shared.stopped() {
+		thread::sleep(HASHRATE_INTERVAL);
+
+		let attempts = shared.attempts.load(Ordering::Relaxed);
+		let elapsed = last_time.elapsed().as_secs_f64();
+		let hashrate = (attempts - last_attempts) as f64 / elapsed;
+		last_time = Instant::now();
+		last_attempts = attempts;
+
+		debug!(target: "pow", "Mining at {:.0} hashes per second", hashrate);
+		if let Some(metrics) = &metrics {
+			metrics.hashrate.set(hashrate as u64);
+		}
+	}
+}
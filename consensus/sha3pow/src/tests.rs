@@ -0,0 +1,374 @@
+use crate::*;
+use sp_core::{H256, U256};
+use std::str::FromStr;
+
+fn hash_of(hasher: &impl PowHasher, data: &[u8]) -> H256 {
+	hasher.hash(data)
+}
+
+/// Search nonces from zero until one meets the difficulty, and return its raw seal.
+fn mine(hasher: &impl PowHasher, pre_hash: H256, difficulty: U256) -> RawSeal {
+	let mut nonce = U256::zero();
+	loop {
+		let seal = Compute {
+			difficulty,
+			pre_hash,
+			nonce,
+		}
+		.compute_with(hasher);
+		if hash_meets_difficulty(&seal.work, difficulty) {
+			return seal.encode();
+		}
+		nonce += U256::one();
+	}
+}
+
+#[test]
+fn hashers_match_known_vectors() {
+	assert_eq!(
+		hash_of(&Sha3, b""),
+		H256::from_str("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a").unwrap()
+	);
+	assert_eq!(
+		hash_of(&Keccak256, b""),
+		H256::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap()
+	);
+	assert_eq!(
+		hash_of(&Blake2b256, b""),
+		H256::from_str("0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8").unwrap()
+	);
+}
+
+#[test]
+fn compute_defaults_to_sha3() {
+	let compute = Compute {
+		difficulty: U256::from(10),
+		pre_hash: H256::repeat_byte(1),
+		nonce: U256::from(42),
+	};
+	assert_eq!(compute.clone().compute(), compute.compute_with(&Sha3));
+}
+
+#[test]
+fn seals_verify_with_their_own_hasher_only() {
+	let pre_hash = H256::repeat_byte(7);
+	let difficulty = U256::from(64);
+
+	let sha3_seal = mine(&Sha3, pre_hash, difficulty);
+	assert!(verify_seal(&Sha3, &pre_hash, &sha3_seal, difficulty));
+	assert!(!verify_seal(&Keccak256, &pre_hash, &sha3_seal, difficulty));
+
+	let keccak_seal = mine(&Keccak256, pre_hash, difficulty);
+	assert!(verify_seal(&Keccak256, &pre_hash, &keccak_seal, difficulty));
+	assert!(!verify_seal(
+		&Blake2b256,
+		&pre_hash,
+		&keccak_seal,
+		difficulty
+	));
+
+	let blake2_seal = mine(&Blake2b256, pre_hash, difficulty);
+	assert!(verify_seal(
+		&Blake2b256,
+		&pre_hash,
+		&blake2_seal,
+		difficulty
+	));
+	assert!(!verify_seal(&Sha3, &pre_hash, &blake2_seal, difficulty));
+}
+
+#[test]
+fn seals_are_bound_to_pre_hash() {
+	let difficulty = U256::from(64);
+	let seal = mine(&Blake2b256, H256::repeat_byte(1), difficulty);
+
+	assert!(!verify_seal(
+		&Blake2b256,
+		&H256::repeat_byte(2),
+		&seal,
+		difficulty
+	));
+}
+
+#[test]
+fn garbage_seals_are_rejected() {
+	assert!(!verify_seal(
+		&Sha3,
+		&H256::repeat_byte(1),
+		&vec![1, 2, 3],
+		U256::one()
+	));
+}
+
+#[test]
+fn memory_hard_depends_on_memory_cost() {
+	let small = MemoryHard::new(Blake2b256, 1);
+	let large = MemoryHard::new(Blake2b256, 2);
+
+	assert_eq!(hash_of(&small, b"recipes"), hash_of(&small, b"recipes"));
+	assert_ne!(hash_of(&small, b"recipes"), hash_of(&large, b"recipes"));
+	assert_ne!(
+		hash_of(&small, b"recipes"),
+		hash_of(&Blake2b256, b"recipes")
+	);
+	assert_eq!(MemoryHard::new(Sha3, 0).memory_cost(), 1);
+}
+
+#[test]
+fn memory_hard_seals_verify_at_their_memory_cost() {
+	let pre_hash = H256::repeat_byte(3);
+	let difficulty = U256::from(8);
+	let hasher = MemoryHard::new(Sha3, 4);
+
+	let seal = mine(&hasher, pre_hash, difficulty);
+	assert!(verify_seal(&hasher, &pre_hash, &seal, difficulty));
+	assert!(!verify_seal(
+		&MemoryHard::new(Sha3, 8),
+		&pre_hash,
+		&seal,
+		difficulty
+	));
+}
+
+#[test]
+fn chain_hasher_follows_the_chains_hashing() {
+	let plain = ChainHasher::from(PowHashing {
+		hash_function: HashFunction::Keccak256,
+		memory_cost: 0,
+	});
+	assert_eq!(hash_of(&plain, b"recipes"), hash_of(&Keccak256, b"recipes"));
+
+	let memory_hard = ChainHasher::from(PowHashing {
+		hash_function: HashFunction::Blake2b256,
+		memory_cost: 2,
+	});
+	assert_eq!(
+		hash_of(&memory_hard, b"recipes"),
+		hash_of(&MemoryHard::new(Blake2b256, 2), b"recipes")
+	);
+
+	let default = ChainHasher::from(PowHashing::default());
+	assert_eq!(hash_of(&default, b"recipes"), hash_of(&Sha3, b"recipes"));
+}
+
+mod fork_choice {
+	use crate::*;
+	use sc_client_api::{
+		backend::{AuxStore, NewBlockState},
+		in_mem::Backend,
+	};
+	use sc_consensus_pow::{aux_key, PowAux};
+	use sp_consensus::SelectChain;
+	use sp_core::{H256, U256};
+	use sp_runtime::{
+		generic::BlockId,
+		testing::{Block as RawBlock, ExtrinsicWrapper, Header},
+		traits::Header as _,
+	};
+	use std::sync::Arc;
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+	fn genesis(backend: &Backend<Block>) -> Header {
+		let header = Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		backend
+			.blockchain()
+			.insert(
+				header.hash(),
+				header.clone(),
+				None,
+				None,
+				NewBlockState::Final,
+			)
+			.unwrap();
+		header
+	}
+
+	/// Import `length` blocks at `difficulty` on top of `parent`, recording their work the way
+	/// `PowBlockImport` does. `salt` keeps blocks on different forks apart.
+	fn extend(
+		backend: &Backend<Block>,
+		parent: &Header,
+		length: u64,
+		difficulty: u64,
+		salt: u8,
+	) -> Header {
+		let mut parent = parent.clone();
+		for _ in 0..length {
+			let header = Header::new(
+				parent.number + 1,
+				H256::repeat_byte(salt),
+				Default::default(),
+				parent.hash(),
+				Default::default(),
+			);
+			let parent_work = total_difficulty::<Block, _>(backend, &parent.hash()).unwrap();
+			let aux = PowAux {
+				difficulty: U256::from(difficulty),
+				total_difficulty: parent_work + U256::from(difficulty),
+			};
+			backend
+				.insert_aux(&[(&aux_key(&header.hash())[..], &aux.encode()[..])], &[])
+				.unwrap();
+			backend
+				.blockchain()
+				.insert(
+					header.hash(),
+					header.clone(),
+					None,
+					None,
+					NewBlockState::Best,
+				)
+				.unwrap();
+			parent = header;
+		}
+		parent
+	}
+
+	#[test]
+	fn unknown_blocks_have_no_work() {
+		let backend = Backend::<Block>::new();
+		let genesis = genesis(&backend);
+
+		assert_eq!(
+			total_difficulty::<Block, _>(&backend, &genesis.hash()).unwrap(),
+			U256::zero()
+		);
+	}
+
+	#[test]
+	fn total_difficulty_accumulates() {
+		let backend = Backend::<Block>::new();
+		let genesis = genesis(&backend);
+		let tip = extend(&backend, &genesis, 3, 100, 1);
+
+		assert_eq!(
+			total_difficulty::<Block, _>(&backend, &tip.hash()).unwrap(),
+			U256::from(300)
+		);
+	}
+
+	#[test]
+	fn heavier_fork_beats_longer_fork() {
+		let backend = Backend::<Block>::new();
+		let genesis = genesis(&backend);
+		let long = extend(&backend, &genesis, 3, 100, 1);
+		let heavy = extend(&backend, &genesis, 2, 200, 2);
+
+		assert_eq!(
+			heaviest::<Block, _>(&backend, vec![long.hash(), heavy.hash()]).unwrap(),
+			Some(heavy.hash())
+		);
+	}
+
+	#[test]
+	fn ties_go_to_the_first_candidate() {
+		let backend = Backend::<Block>::new();
+		let genesis = genesis(&backend);
+		let a = extend(&backend, &genesis, 2, 100, 1);
+		let b = extend(&backend, &genesis, 1, 200, 2);
+
+		assert_eq!(
+			heaviest::<Block, _>(&backend, vec![a.hash(), b.hash()]).unwrap(),
+			Some(a.hash())
+		);
+		assert_eq!(
+			heaviest::<Block, _>(&backend, vec![b.hash(), a.hash()]).unwrap(),
+			Some(b.hash())
+		);
+	}
+
+	#[test]
+	fn heaviest_chain_follows_work_not_length() {
+		let backend = Arc::new(Backend::<Block>::new());
+		let genesis = genesis(&backend);
+		let heavy = extend(&backend, &genesis, 2, 300, 2);
+		// Imported last, so the backend believes the long fork is best
+		let long = extend(&backend, &genesis, 5, 100, 1);
+		let select_chain = HeaviestChain::<Block, _>::new(backend.clone());
+
+		let mut leaves = select_chain.leaves().unwrap();
+		leaves.sort();
+		let mut expected = vec![heavy.hash(), long.hash()];
+		expected.sort();
+		assert_eq!(leaves, expected);
+		assert_eq!(select_chain.best_chain().unwrap(), heavy);
+
+		// Once the long fork catches up and overtakes, it wins again.
+		let long = extend(&backend, &long, 2, 100, 1);
+		assert_eq!(select_chain.best_chain().unwrap(), long);
+	}
+
+	#[test]
+	fn heaviest_chain_keeps_current_best_on_a_tie() {
+		let backend = Arc::new(Backend::<Block>::new());
+		let genesis = genesis(&backend);
+		let long = extend(&backend, &genesis, 2, 100, 1);
+		// Imported last, so this is the backend's best block
+		let short = extend(&backend, &genesis, 1, 200, 2);
+		let select_chain = HeaviestChain::<Block, _>::new(backend.clone());
+
+		// Equal work is no reason to reorganise, even onto a longer chain.
+		assert_eq!(select_chain.best_chain().unwrap(), short);
+		assert_ne!(select_chain.best_chain().unwrap(), long);
+	}
+
+	#[test]
+	fn heaviest_chain_votes_for_the_heaviest_descendant() {
+		let backend = Arc::new(Backend::<Block>::new());
+		let genesis = genesis(&backend);
+		let fork_point = extend(&backend, &genesis, 1, 100, 1);
+		let heavy_first = extend(&backend, &fork_point, 1, 300, 2);
+		let heavy = extend(&backend, &heavy_first, 1, 300, 2);
+		let long_first = extend(&backend, &fork_point, 1, 100, 3);
+		let long = extend(&backend, &long_first, 4, 100, 3);
+		let select_chain = HeaviestChain::<Block, _>::new(backend.clone());
+
+		assert_eq!(
+			select_chain.finality_target(genesis.hash(), None).unwrap(),
+			Some(heavy.hash())
+		);
+		// Votes stop at the maximum number
+		assert_eq!(
+			select_chain
+				.finality_target(genesis.hash(), Some(2))
+				.unwrap(),
+			Some(heavy_first.hash())
+		);
+		// A target on the lighter fork gets that fork's tip
+		assert_eq!(
+			select_chain
+				.finality_target(long_first.hash(), None)
+				.unwrap(),
+			Some(long.hash())
+		);
+	}
+
+	#[test]
+	fn heaviest_chain_stays_on_the_finalized_chain() {
+		let backend = Arc::new(Backend::<Block>::new());
+		let genesis = genesis(&backend);
+		let _heavy = extend(&backend, &genesis, 1, 300, 1);
+		let light = extend(&backend, &genesis, 1, 100, 2);
+		backend
+			.blockchain()
+			.finalize_header(BlockId::Hash(light.hash()), None)
+			.unwrap();
+		let select_chain = HeaviestChain::<Block, _>::new(backend.clone());
+
+		// The heavy fork doesn't contain the finalized block, so it can't be built on
+		assert_eq!(select_chain.best_chain().unwrap(), light);
+		let light = extend(&backend, &light, 1, 100, 2);
+		assert_eq!(select_chain.best_chain().unwrap(), light);
+		assert_eq!(
+			select_chain.finality_target(light.hash(), None).unwrap(),
+			Some(light.hash())
+		);
+	}
+}
@@ -1,4 +1,7 @@
+use runtime::AccountId;
 use sc_cli::RunCmd;
+use sp_core::crypto::Ss58Codec;
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +11,51 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Where the mining difficulty comes from. `fixed` mines at the constant difficulty of
+	/// `MinimalPowAlgorithm`. `runtime` mines with `RuntimePowAlgorithm`, which asks the runtime's
+	/// `DifficultyApi` so the difficulty tracks the network's hashrate.
+	#[structopt(long = "difficulty-mode", default_value = "fixed")]
+	pub difficulty_mode: DifficultyMode,
+
+	/// Number of threads to mine with. The threads split the nonce space between them. Use 0 to
+	/// leave mining to external miners connected through the `sha3pow_getWork` RPC.
+	#[structopt(long = "mining-threads", default_value = "1")]
+	pub mining_threads: usize,
+
+	/// Account, in SS58 format, that receives the reward for the blocks this node mines. Without
+	/// it, blocks are still mined but nobody is rewarded.
+	#[structopt(long = "author", parse(try_from_str = parse_account))]
+	pub author: Option<AccountId>,
+}
+
+fn parse_account(address: &str) -> Result<AccountId, String> {
+	AccountId::from_ss58check(address)
+		.map_err(|e| format!("Invalid author address `{}`: {:?}", address, e))
+}
+
+/// The available sources of mining difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifficultyMode {
+	/// A constant difficulty hardcoded in the node
+	Fixed,
+	/// A difficulty retargeted by the runtime
+	Runtime,
+}
+
+impl FromStr for DifficultyMode {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"fixed" => Ok(DifficultyMode::Fixed),
+			"runtime" => Ok(DifficultyMode::Runtime),
+			other => Err(format!(
+				"Unknown difficulty mode `{}`. Expected `fixed` or `runtime`",
+				other
+			)),
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
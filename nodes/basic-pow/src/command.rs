@@ -61,7 +61,7 @@ pub fn run() -> sc_cli::Result<()> {
 					task_manager,
 					import_queue,
 					..
-				} = service::new_partial(&config)?;
+				} = service::new_partial(&config, cli.difficulty_mode)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		}
@@ -72,7 +72,7 @@ pub fn run() -> sc_cli::Result<()> {
 					client,
 					task_manager,
 					..
-				} = service::new_partial(&config)?;
+				} = service::new_partial(&config, cli.difficulty_mode)?;
 				Ok((cmd.run(client, config.database), task_manager))
 			})
 		}
@@ -83,7 +83,7 @@ pub fn run() -> sc_cli::Result<()> {
 					client,
 					task_manager,
 					..
-				} = service::new_partial(&config)?;
+				} = service::new_partial(&config, cli.difficulty_mode)?;
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		}
@@ -95,7 +95,7 @@ pub fn run() -> sc_cli::Result<()> {
 					task_manager,
 					import_queue,
 					..
-				} = service::new_partial(&config)?;
+				} = service::new_partial(&config, cli.difficulty_mode)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		}
@@ -111,16 +111,19 @@ pub fn run() -> sc_cli::Result<()> {
 					task_manager,
 					backend,
 					..
-				} = service::new_partial(&config)?;
+				} = service::new_partial(&config, cli.difficulty_mode)?;
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		}
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let difficulty_mode = cli.difficulty_mode;
+			let mining_threads = cli.mining_threads;
+			let author = cli.author.clone();
 			runner.run_node_until_exit(|config| async move {
 				match config.role {
-					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
+					Role::Light => service::new_light(config, difficulty_mode),
+					_ => service::new_full(config, difficulty_mode, mining_threads, author),
 				}
 				.map_err(sc_cli::Error::Service)
 			})
@@ -0,0 +1,44 @@
+//! The RPC extensions of the basic-pow node.
+
+use crate::service::{FullClient, MiningWorker};
+use parking_lot::Mutex;
+pub use sc_rpc_api::DenyUnsafe;
+use sha3pow::ChainHasher;
+use sha3pow_rpc::{Sha3Pow, Sha3PowApi, TotalWork, TotalWorkApi};
+use std::sync::Arc;
+
+/// Full client dependencies.
+pub struct FullDeps {
+	/// The client instance to use
+	pub client: Arc<FullClient>,
+	/// The mining worker, if this node mines
+	pub mining_worker: Option<Arc<Mutex<MiningWorker>>>,
+	/// The hasher the chain mines with
+	pub hasher: ChainHasher,
+	/// Whether to deny unsafe calls
+	pub deny_unsafe: DenyUnsafe,
+}
+
+/// Instantiate all full RPC extensions.
+pub fn create_full(deps: FullDeps) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> {
+	let mut io = jsonrpc_core::IoHandler::default();
+	let FullDeps {
+		client,
+		mining_worker,
+		hasher,
+		deny_unsafe,
+	} = deps;
+
+	io.extend_with(TotalWorkApi::to_delegate(TotalWork::new(client)));
+
+	// Let external miners fetch work from and submit seals to our mining worker.
+	if let Some(worker) = mining_worker {
+		io.extend_with(Sha3PowApi::to_delegate(Sha3Pow::with_hasher(
+			worker,
+			hasher,
+			deny_unsafe,
+		)));
+	}
+
+	io
+}
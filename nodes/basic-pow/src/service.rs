@@ -1,16 +1,19 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 #![allow(clippy::needless_borrow)]
-use runtime::{self, opaque::Block, RuntimeApi};
+use crate::cli::DifficultyMode;
+use runtime::{self, opaque::Block, AccountId, RuntimeApi};
 use sc_client_api::{ExecutorProvider, RemoteBackend};
+use sc_consensus_pow::{Error as PowError, PowAlgorithm};
 use sc_executor::native_executor_instance;
 pub use sc_executor::NativeExecutor;
 use sc_service::{error::Error as ServiceError, Configuration, PartialComponents, TaskManager};
 use sha3pow::*;
-use sp_api::TransactionFor;
+use sp_api::{ProvideRuntimeApi, TransactionFor};
 use sp_consensus::import_queue::BasicQueue;
-use sp_core::{Encode, U256};
+use sp_consensus_pow::{DifficultyApi, Seal as RawSeal};
+use sp_core::{Encode, H256, U256};
 use sp_inherents::InherentDataProviders;
-use std::thread;
+use sp_runtime::generic::BlockId;
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -20,9 +23,75 @@ native_executor_instance!(
 	runtime::native_version,
 );
 
-type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
+pub type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
-type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type FullSelectChain = HeaviestChain<Block, FullBackend>;
+pub type MiningWorker = sc_consensus_pow::MiningWorker<Block, Algorithm<FullClient>, FullClient>;
+
+/// The PoW algorithm chosen with the `--difficulty-mode` flag. Either way, seals are hashed the
+/// way the chain's genesis asks for.
+pub enum Algorithm<C> {
+	/// Difficulty is fixed in the node
+	Fixed(MinimalPowAlgorithm<ChainHasher>),
+	/// Difficulty is fetched from the runtime through the `DifficultyApi`
+	Runtime(RuntimePowAlgorithm<C, ChainHasher>),
+}
+
+impl<C> Algorithm<C> {
+	pub fn new(mode: DifficultyMode, client: Arc<C>, hasher: ChainHasher) -> Self {
+		match mode {
+			DifficultyMode::Fixed => Algorithm::Fixed(MinimalPowAlgorithm::new(hasher)),
+			DifficultyMode::Runtime => {
+				Algorithm::Runtime(RuntimePowAlgorithm::with_hasher(client, hasher))
+			}
+		}
+	}
+}
+
+// Manually implement clone for the same reason as `Sha3Algorithm` does.
+impl<C> Clone for Algorithm<C> {
+	fn clone(&self) -> Self {
+		match self {
+			Algorithm::Fixed(algorithm) => Algorithm::Fixed(algorithm.clone()),
+			Algorithm::Runtime(algorithm) => Algorithm::Runtime(algorithm.clone()),
+		}
+	}
+}
+
+// Both variants verify seals the same way, they only differ in where the difficulty comes from.
+// So we simply delegate to whichever algorithm was chosen.
+impl<C> PowAlgorithm<Block> for Algorithm<C>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: DifficultyApi<Block, U256>,
+{
+	type Difficulty = U256;
+
+	fn difficulty(&self, parent: H256) -> Result<Self::Difficulty, PowError<Block>> {
+		match self {
+			Algorithm::Fixed(algorithm) => PowAlgorithm::<Block>::difficulty(algorithm, parent),
+			Algorithm::Runtime(algorithm) => PowAlgorithm::<Block>::difficulty(algorithm, parent),
+		}
+	}
+
+	fn verify(
+		&self,
+		parent: &BlockId<Block>,
+		pre_hash: &H256,
+		pre_digest: Option<&[u8]>,
+		seal: &RawSeal,
+		difficulty: Self::Difficulty,
+	) -> Result<bool, PowError<Block>> {
+		match self {
+			Algorithm::Fixed(algorithm) => PowAlgorithm::<Block>::verify(
+				algorithm, parent, pre_hash, pre_digest, seal, difficulty,
+			),
+			Algorithm::Runtime(algorithm) => PowAlgorithm::<Block>::verify(
+				algorithm, parent, pre_hash, pre_digest, seal, difficulty,
+			),
+		}
+	}
+}
 
 pub fn build_inherent_data_providers() -> Result<InherentDataProviders, ServiceError> {
 	let providers = InherentDataProviders::new();
@@ -40,6 +109,7 @@ pub fn build_inherent_data_providers() -> Result<InherentDataProviders, ServiceE
 #[allow(clippy::type_complexity)]
 pub fn new_partial(
 	config: &Configuration,
+	difficulty_mode: DifficultyMode,
 ) -> Result<
 	PartialComponents<
 		FullClient,
@@ -52,9 +122,10 @@ pub fn new_partial(
 			Arc<FullClient>,
 			FullClient,
 			FullSelectChain,
-			MinimalSha3Algorithm,
+			Algorithm<FullClient>,
 			impl sp_consensus::CanAuthorWith<Block>,
 		>,
+		ChainHasher,
 	>,
 	ServiceError,
 > {
@@ -64,7 +135,7 @@ pub fn new_partial(
 		sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
 	let client = Arc::new(client);
 
-	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+	let select_chain = HeaviestChain::new(backend.clone());
 
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
@@ -76,10 +147,14 @@ pub fn new_partial(
 
 	let can_author_with = sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
 
+	// Mine and verify with whatever hashing the chain was created with.
+	let hasher = genesis_hasher::<Block, _>(&*client).map_err(ServiceError::Other)?;
+	let algorithm = Algorithm::new(difficulty_mode, client.clone(), hasher);
+
 	let pow_block_import = sc_consensus_pow::PowBlockImport::new(
 		client.clone(),
 		client.clone(),
-		sha3pow::MinimalSha3Algorithm,
+		algorithm.clone(),
 		0, // check inherents starting at block 0
 		select_chain.clone(),
 		inherent_data_providers.clone(),
@@ -89,7 +164,7 @@ pub fn new_partial(
 	let import_queue = sc_consensus_pow::import_queue(
 		Box::new(pow_block_import.clone()),
 		None,
-		sha3pow::MinimalSha3Algorithm,
+		algorithm,
 		inherent_data_providers.clone(),
 		&task_manager.spawn_handle(),
 		config.prometheus_registry(),
@@ -104,12 +179,17 @@ pub fn new_partial(
 		transaction_pool,
 		select_chain,
 		inherent_data_providers,
-		other: pow_block_import,
+		other: (pow_block_import, hasher),
 	})
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	config: Configuration,
+	difficulty_mode: DifficultyMode,
+	mining_threads: usize,
+	author: Option<AccountId>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -119,8 +199,8 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		select_chain,
 		transaction_pool,
 		inherent_data_providers,
-		other: pow_block_import,
-	} = new_partial(&config)?;
+		other: (pow_block_import, hasher),
+	} = new_partial(&config, difficulty_mode)?;
 
 	let (network, network_status_sinks, system_rpc_tx, network_starter) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
@@ -146,26 +226,12 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let is_authority = config.role.is_authority();
 	let prometheus_registry = config.prometheus_registry().cloned();
 
-	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
-		network: network.clone(),
-		client: client.clone(),
-		keystore: keystore_container.sync_keystore(),
-		task_manager: &mut task_manager,
-		transaction_pool: transaction_pool.clone(),
-		rpc_extensions_builder: Box::new(|_, _| ()),
-		on_demand: None,
-		remote_blockchain: None,
-		backend,
-		network_status_sinks,
-		system_rpc_tx,
-		config,
-	})?;
-
-	if is_authority {
+	// The mining worker is built before the RPC extensions, so external miners can reach it.
+	let mining_worker = if is_authority {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
-			transaction_pool,
+			transaction_pool.clone(),
 			prometheus_registry.as_ref(),
 		);
 
@@ -176,14 +242,15 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		//   https://substrate.dev/rustdocs/v3.0.0/sc_consensus_pow/fn.start_mining_worker.html
 		// Also refer to kulupu config:
 		//   https://github.com/kulupu/kulupu/blob/master/src/service.rs
-		let (_worker, worker_task) = sc_consensus_pow::start_mining_worker(
+		let (worker, worker_task) = sc_consensus_pow::start_mining_worker(
 			Box::new(pow_block_import),
-			client,
+			client.clone(),
 			select_chain,
-			MinimalSha3Algorithm,
+			Algorithm::new(difficulty_mode, client.clone(), hasher),
 			proposer,
-			network,
-			None,
+			network.clone(),
+			// The block rewards pallet pays whoever is named in this pre-runtime digest
+			author.map(|author| author.encode()),
 			inherent_data_providers,
 			// time to wait for a new block before starting to mine a new one
 			Duration::from_secs(10),
@@ -197,39 +264,63 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 			.spawn_blocking("pow", worker_task);
 
 		// Start Mining
-		let mut nonce: U256 = U256::from(0);
-		thread::spawn(move || loop {
-			let worker = _worker.clone();
-			let metadata = worker.lock().metadata();
-			if let Some(metadata) = metadata {
-				let compute = Compute {
-					difficulty: metadata.difficulty,
-					pre_hash: metadata.pre_hash,
-					nonce,
-				};
-				let seal = compute.compute();
-				if hash_meets_difficulty(&seal.work, seal.difficulty) {
-					nonce = U256::from(0);
-					let mut worker = worker.lock();
-					worker.submit(seal.encode());
-				} else {
-					nonce = nonce.saturating_add(U256::from(1));
-					if nonce == U256::MAX {
-						nonce = U256::from(0);
-					}
-				}
-			} else {
-				thread::sleep(Duration::new(1, 0));
-			}
-		});
-	}
+		let metrics = prometheus_registry
+			.as_ref()
+			.map(MiningMetrics::register)
+			.transpose()?;
+
+		task_manager.spawn_handle().spawn(
+			"pow-miner",
+			start_miner(
+				worker.clone(),
+				client.clone(),
+				hasher,
+				mining_threads,
+				metrics,
+			),
+		);
+
+		Some(worker)
+	} else {
+		None
+	};
+
+	let rpc_client = client.clone();
+	let rpc_extensions_builder = Box::new(move |deny_unsafe, _| {
+		let deps = crate::rpc::FullDeps {
+			client: rpc_client.clone(),
+			mining_worker: mining_worker.clone(),
+			hasher,
+			deny_unsafe,
+		};
+
+		crate::rpc::create_full(deps)
+	});
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		network,
+		client,
+		keystore: keystore_container.sync_keystore(),
+		task_manager: &mut task_manager,
+		transaction_pool,
+		rpc_extensions_builder,
+		on_demand: None,
+		remote_blockchain: None,
+		backend,
+		network_status_sinks,
+		system_rpc_tx,
+		config,
+	})?;
 
 	network_starter.start_network();
 	Ok(task_manager)
 }
 
 /// Builds a new service for a light client.
-pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_light(
+	config: Configuration,
+	difficulty_mode: DifficultyMode,
+) -> Result<TaskManager, ServiceError> {
 	let (client, backend, keystore_container, mut task_manager, on_demand) =
 		sc_service::new_light_parts::<Block, RuntimeApi, Executor>(&config)?;
 
@@ -241,15 +332,19 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 		on_demand.clone(),
 	));
 
-	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+	let select_chain = HeaviestChain::new(backend.clone());
 	let inherent_data_providers = build_inherent_data_providers()?;
 	// FixMe #375
 	let _can_author_with = sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
 
+	// Light clients keep the genesis state, so they can read the hashing without the network.
+	let hasher = genesis_hasher::<Block, _>(&*client).map_err(ServiceError::Other)?;
+	let algorithm = Algorithm::new(difficulty_mode, client.clone(), hasher);
+
 	let pow_block_import = sc_consensus_pow::PowBlockImport::new(
 		client.clone(),
 		client.clone(),
-		sha3pow::MinimalSha3Algorithm,
+		algorithm.clone(),
 		0, // check inherents starting at block 0
 		select_chain,
 		inherent_data_providers.clone(),
@@ -260,7 +355,7 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let import_queue = sc_consensus_pow::import_queue(
 		Box::new(pow_block_import),
 		None,
-		sha3pow::MinimalSha3Algorithm,
+		algorithm,
 		inherent_data_providers,
 		&task_manager.spawn_handle(),
 		config.prometheus_registry(),
@@ -8,6 +8,11 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Number of threads to mine with. The threads split the nonce space between them. Use 0 to
+	/// leave mining to external miners connected through the `sha3pow_getWork` RPC.
+	#[structopt(long = "mining-threads", default_value = "1")]
+	pub mining_threads: usize,
 }
 
 #[derive(Debug, StructOpt)]
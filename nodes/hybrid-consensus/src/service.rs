@@ -7,12 +7,11 @@ pub use sc_executor::NativeExecutor;
 use sc_finality_grandpa::GrandpaBlockImport;
 use sc_service::{error::Error as ServiceError, Configuration, PartialComponents, TaskManager};
 use sha3pow::*;
+use sha3pow_rpc::{Sha3Pow, Sha3PowApi, TotalWork, TotalWorkApi};
 use sp_api::TransactionFor;
 use sp_consensus::import_queue::BasicQueue;
-use sp_core::{Encode, U256};
 use sp_inherents::InherentDataProviders;
 use std::sync::Arc;
-use std::thread;
 use std::time::Duration;
 
 // Our native executor instance.
@@ -24,7 +23,7 @@ native_executor_instance!(
 
 type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
-type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type FullSelectChain = HeaviestChain<Block, FullBackend>;
 
 pub fn build_inherent_data_providers() -> Result<InherentDataProviders, ServiceError> {
 	let providers = InherentDataProviders::new();
@@ -69,7 +68,7 @@ pub fn new_partial(
 		sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
 	let client = Arc::new(client);
 
-	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+	let select_chain = HeaviestChain::new(backend.clone());
 
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
@@ -90,7 +89,7 @@ pub fn new_partial(
 	let pow_block_import = sc_consensus_pow::PowBlockImport::new(
 		grandpa_block_import,
 		client.clone(),
-		sha3pow::MinimalSha3Algorithm,
+		sha3pow::MinimalSha3Algorithm::default(),
 		0, // check inherents starting at block 0
 		select_chain.clone(),
 		inherent_data_providers.clone(),
@@ -100,7 +99,7 @@ pub fn new_partial(
 	let import_queue = sc_consensus_pow::import_queue(
 		Box::new(pow_block_import.clone()),
 		None,
-		sha3pow::MinimalSha3Algorithm,
+		sha3pow::MinimalSha3Algorithm::default(),
 		inherent_data_providers.clone(),
 		&task_manager.spawn_handle(),
 		config.prometheus_registry(),
@@ -120,7 +119,7 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(config: Configuration, mining_threads: usize) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -158,27 +157,12 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let enable_grandpa = !config.disable_grandpa;
 
-	let (_rpc_handlers, telemetry_connection_notifier) =
-		sc_service::spawn_tasks(sc_service::SpawnTasksParams {
-			network: network.clone(),
-			client: client.clone(),
-			keystore: keystore_container.sync_keystore(),
-			task_manager: &mut task_manager,
-			transaction_pool: transaction_pool.clone(),
-			rpc_extensions_builder: Box::new(|_, _| ()),
-			on_demand: None,
-			remote_blockchain: None,
-			backend,
-			network_status_sinks,
-			system_rpc_tx,
-			config,
-		})?;
-
-	if is_authority {
+	// The mining worker is built before the RPC extensions, so external miners can reach it.
+	let mining_worker = if is_authority {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
-			transaction_pool,
+			transaction_pool.clone(),
 			prometheus_registry.as_ref(),
 		);
 
@@ -189,11 +173,11 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		//   https://substrate.dev/rustdocs/v3.0.0/sc_consensus_pow/fn.start_mining_worker.html
 		// Also refer to kulupu config:
 		//   https://github.com/kulupu/kulupu/blob/master/src/service.rs
-		let (_worker, worker_task) = sc_consensus_pow::start_mining_worker(
+		let (worker, worker_task) = sc_consensus_pow::start_mining_worker(
 			Box::new(pow_block_import),
-			client,
+			client.clone(),
 			select_chain,
-			MinimalSha3Algorithm,
+			MinimalSha3Algorithm::default(),
 			proposer,
 			network.clone(),
 			None,
@@ -210,32 +194,57 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 			.spawn_blocking("pow", worker_task);
 
 		// Start Mining
-		let mut nonce: U256 = U256::from(0);
-		thread::spawn(move || loop {
-			let worker = _worker.clone();
-			let metadata = worker.lock().metadata();
-			if let Some(metadata) = metadata {
-				let compute = Compute {
-					difficulty: metadata.difficulty,
-					pre_hash: metadata.pre_hash,
-					nonce,
-				};
-				let seal = compute.compute();
-				if hash_meets_difficulty(&seal.work, seal.difficulty) {
-					nonce = U256::from(0);
-					let mut worker = worker.lock();
-					worker.submit(seal.encode());
-				} else {
-					nonce = nonce.saturating_add(U256::from(1));
-					if nonce == U256::MAX {
-						nonce = U256::from(0);
-					}
-				}
-			} else {
-				thread::sleep(Duration::new(1, 0));
-			}
-		});
-	}
+		let metrics = prometheus_registry
+			.as_ref()
+			.map(MiningMetrics::register)
+			.transpose()?;
+
+		task_manager.spawn_handle().spawn(
+			"pow-miner",
+			start_miner(
+				worker.clone(),
+				client.clone(),
+				Sha3,
+				mining_threads,
+				metrics,
+			),
+		);
+
+		Some(worker)
+	} else {
+		None
+	};
+
+	let rpc_client = client.clone();
+	let rpc_extensions_builder = Box::new(move |deny_unsafe, _| {
+		let mut io = jsonrpc_core::IoHandler::<sc_rpc::Metadata>::default();
+		io.extend_with(TotalWorkApi::to_delegate(TotalWork::new(
+			rpc_client.clone(),
+		)));
+		if let Some(worker) = &mining_worker {
+			io.extend_with(Sha3PowApi::to_delegate(Sha3Pow::new(
+				worker.clone(),
+				deny_unsafe,
+			)));
+		}
+		io
+	});
+
+	let (_rpc_handlers, telemetry_connection_notifier) =
+		sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+			network: network.clone(),
+			client,
+			keystore: keystore_container.sync_keystore(),
+			task_manager: &mut task_manager,
+			transaction_pool,
+			rpc_extensions_builder,
+			on_demand: None,
+			remote_blockchain: None,
+			backend,
+			network_status_sinks,
+			system_rpc_tx,
+			config,
+		})?;
 
 	let grandpa_config = sc_finality_grandpa::Config {
 		gossip_duration: Duration::from_millis(333),
@@ -290,7 +299,7 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 		on_demand.clone(),
 	));
 
-	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+	let select_chain = HeaviestChain::new(backend.clone());
 
 	let (grandpa_block_import, _) = sc_finality_grandpa::block_import(
 		client.clone(),
@@ -304,7 +313,7 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let pow_block_import = sc_consensus_pow::PowBlockImport::new(
 		grandpa_block_import,
 		client.clone(),
-		MinimalSha3Algorithm,
+		MinimalSha3Algorithm::default(),
 		0, // check inherents starting at block 0
 		select_chain,
 		inherent_data_providers.clone(),
@@ -314,7 +323,7 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let import_queue = sc_consensus_pow::import_queue(
 		Box::new(pow_block_import),
 		None,
-		MinimalSha3Algorithm,
+		MinimalSha3Algorithm::default(),
 		inherent_data_providers,
 		&task_manager.spawn_handle(),
 		config.prometheus_registry(),
@@ -1,42 +1,187 @@
-use runtime::{
-	genesis::{account_id_from_seed, dev_genesis, testnet_genesis},
-	GenesisConfig, WASM_BINARY,
-};
+use crate::cli::Runtime;
+use sc_chain_spec::ChainSpecExtension;
+use sc_service::{ChainType, GenericChainSpec, RuntimeGenesis};
+use serde::{Deserialize, Serialize};
 use sp_core::sr25519;
+use super_runtime::{genesis::account_id_from_seed, AccountId};
 
 // Note this is the URL for the telemetry server
 //const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
-/// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+/// Node specific values stored in the chain spec, next to the genesis.
+#[derive(Default, Clone, Serialize, Deserialize, ChainSpecExtension)]
+#[serde(rename_all = "camelCase")]
+pub struct Extensions {
+	/// The runtime the chain runs. Every part of the node that depends on the runtime reads it
+	/// from here, so they can't disagree about it.
+	pub runtime: Option<Runtime>,
+}
+
+impl Extensions {
+	/// Extensions recording that the chain runs `runtime`
+	pub fn new(runtime: Runtime) -> Self {
+		Self {
+			runtime: Some(runtime),
+		}
+	}
+}
+
+/// Specialized `ChainSpec` for each runtime the node can run. These are specializations of the
+/// general Substrate ChainSpec type.
+pub type SuperChainSpec = GenericChainSpec<super_runtime::GenesisConfig, Extensions>;
+pub type WeightFeeChainSpec = GenericChainSpec<weight_fee_runtime::GenesisConfig, Extensions>;
+pub type OcwChainSpec = GenericChainSpec<ocw_runtime::GenesisConfig, Extensions>;
+pub type ApiChainSpec = GenericChainSpec<api_runtime::GenesisConfig, Extensions>;
+pub type MinimalGrandpaChainSpec =
+	GenericChainSpec<minimal_grandpa_runtime::GenesisConfig, Extensions>;
+
+/// The runtime to run `chain_spec` with. Specs that don't record their runtime, like those written
+/// before it was recorded, are identified by their id.
+pub fn runtime_of(chain_spec: &dyn sc_service::ChainSpec) -> Runtime {
+	sc_chain_spec::get_extension::<Option<Runtime>>(chain_spec.extensions())
+		.copied()
+		.flatten()
+		.unwrap_or_else(|| Runtime::from_chain_spec_id(chain_spec.id()))
+}
+
+/// The chain spec id of `runtime`'s flavour of the `name` chain, e.g. `weight_fee_dev`.
+pub fn chain_spec_id(runtime: Runtime, name: &str) -> String {
+	match runtime.id_prefix() {
+		Some(prefix) => format!("{}_{}", prefix, name),
+		None => name.into(),
+	}
+}
+
+/// Build a Development ChainSpec for the given runtime
+pub fn dev_config(runtime: Runtime) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+	Ok(match runtime {
+		Runtime::Super => Box::new(dev_spec(
+			runtime,
+			super_runtime::WASM_BINARY,
+			super_runtime::genesis::dev_genesis,
+		)?),
+		Runtime::WeightFee => Box::new(dev_spec(
+			runtime,
+			weight_fee_runtime::WASM_BINARY,
+			weight_fee_runtime::genesis::dev_genesis,
+		)?),
+		Runtime::Ocw => Box::new(dev_spec(
+			runtime,
+			ocw_runtime::WASM_BINARY,
+			ocw_runtime::genesis::dev_genesis,
+		)?),
+		Runtime::Api => Box::new(dev_spec(
+			runtime,
+			api_runtime::WASM_BINARY,
+			api_runtime::genesis::dev_genesis,
+		)?),
+		Runtime::MinimalGrandpa => Box::new(dev_spec(
+			runtime,
+			minimal_grandpa_runtime::WASM_BINARY,
+			minimal_grandpa_runtime::genesis::dev_genesis,
+		)?),
+	})
+}
 
-/// Build a Development ChainSpec
-pub fn dev_config() -> Result<ChainSpec, String> {
-	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+/// Build a Local ChainSpec for the given runtime
+pub fn local_testnet_config(runtime: Runtime) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+	Ok(match runtime {
+		Runtime::Super => Box::new(local_testnet_spec(
+			runtime,
+			super_runtime::WASM_BINARY,
+			super_runtime::genesis::testnet_genesis,
+		)?),
+		Runtime::WeightFee => Box::new(local_testnet_spec(
+			runtime,
+			weight_fee_runtime::WASM_BINARY,
+			weight_fee_runtime::genesis::testnet_genesis,
+		)?),
+		Runtime::Ocw => Box::new(local_testnet_spec(
+			runtime,
+			ocw_runtime::WASM_BINARY,
+			ocw_runtime::genesis::testnet_genesis,
+		)?),
+		Runtime::Api => Box::new(local_testnet_spec(
+			runtime,
+			api_runtime::WASM_BINARY,
+			api_runtime::genesis::testnet_genesis,
+		)?),
+		// Alice is the only GRANDPA authority, like in the dev chain
+		Runtime::MinimalGrandpa => Box::new(local_testnet_spec(
+			runtime,
+			minimal_grandpa_runtime::WASM_BINARY,
+			|wasm_binary, root_key, endowed_accounts| {
+				minimal_grandpa_runtime::genesis::testnet_genesis(
+					wasm_binary,
+					vec![minimal_grandpa_runtime::genesis::authority_keys_from_seed(
+						"Alice",
+					)],
+					root_key,
+					endowed_accounts,
+				)
+			},
+		)?),
+	})
+}
 
-	Ok(ChainSpec::from_genesis(
+/// Load a ChainSpec from a JSON file, decoding its genesis as the given runtime's. The runtime is
+/// recorded in the loaded spec, so it wins over whatever the file says.
+pub fn from_json_file(
+	runtime: Runtime,
+	path: std::path::PathBuf,
+) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+	Ok(match runtime {
+		Runtime::Super => recording(runtime, SuperChainSpec::from_json_file(path)?),
+		Runtime::WeightFee => recording(runtime, WeightFeeChainSpec::from_json_file(path)?),
+		Runtime::Ocw => recording(runtime, OcwChainSpec::from_json_file(path)?),
+		Runtime::Api => recording(runtime, ApiChainSpec::from_json_file(path)?),
+		Runtime::MinimalGrandpa => {
+			recording(runtime, MinimalGrandpaChainSpec::from_json_file(path)?)
+		}
+	})
+}
+
+fn recording<G: RuntimeGenesis + 'static>(
+	runtime: Runtime,
+	mut chain_spec: GenericChainSpec<G, Extensions>,
+) -> Box<dyn sc_service::ChainSpec> {
+	chain_spec.extensions_mut().runtime = Some(runtime);
+	Box::new(chain_spec)
+}
+
+fn dev_spec<G: RuntimeGenesis + 'static>(
+	runtime: Runtime,
+	wasm_binary: Option<&'static [u8]>,
+	genesis: fn(&[u8]) -> G,
+) -> Result<GenericChainSpec<G, Extensions>, String> {
+	let wasm_binary = wasm_binary.ok_or_else(|| "Development wasm not available".to_string())?;
+
+	Ok(GenericChainSpec::from_genesis(
 		"Development",
-		"dev",
-		sc_service::ChainType::Development,
-		move || dev_genesis(wasm_binary),
+		&chain_spec_id(runtime, "dev"),
+		ChainType::Development,
+		move || genesis(wasm_binary),
 		vec![],
 		None,
 		None,
 		None,
-		None,
+		Extensions::new(runtime),
 	))
 }
 
-/// Build a Local Chainspec
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
-	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+fn local_testnet_spec<G: RuntimeGenesis + 'static>(
+	runtime: Runtime,
+	wasm_binary: Option<&'static [u8]>,
+	genesis: fn(&[u8], AccountId, Vec<AccountId>) -> G,
+) -> Result<GenericChainSpec<G, Extensions>, String> {
+	let wasm_binary = wasm_binary.ok_or_else(|| "Development wasm not available".to_string())?;
 
-	Ok(ChainSpec::from_genesis(
+	Ok(GenericChainSpec::from_genesis(
 		"Local Testnet",
-		"local_testnet",
-		sc_service::ChainType::Local,
+		&chain_spec_id(runtime, "local_testnet"),
+		ChainType::Local,
 		move || {
-			testnet_genesis(
+			genesis(
 				wasm_binary,
 				account_id_from_seed::<sr25519::Pair>("Alice"),
 				vec![
@@ -59,6 +204,6 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		None,
 		None,
 		None,
-		None,
+		Extensions::new(runtime),
 	))
 }
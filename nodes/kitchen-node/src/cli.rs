@@ -1,4 +1,6 @@
 use sc_cli::RunCmd;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +10,117 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// When to author blocks. `instant` seals a block for every transaction. `manual` only seals
+	/// when asked to through the `engine_createBlock` RPC. `interval` seals a block every
+	/// `--sealing-interval` milliseconds, even if it is empty.
+	#[structopt(long = "sealing", default_value = "instant")]
+	pub sealing: Sealing,
+
+	/// Milliseconds between blocks when sealing at an interval.
+	#[structopt(long = "sealing-interval", default_value = "6000")]
+	pub sealing_interval: u64,
+
+	/// Which runtime to run: `super`, `weight-fee`, `ocw`, `api` or `minimal-grandpa`. Without it,
+	/// the runtime is the one recorded in the chain spec, or else chosen from the chain spec id,
+	/// falling back to the super runtime.
+	#[structopt(long = "runtime")]
+	pub runtime: Option<Runtime>,
+}
+
+/// The runtimes this node can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Runtime {
+	/// The super runtime, with most of the recipes' pallets
+	Super,
+	/// The runtime with custom weights and fees
+	WeightFee,
+	/// The runtime with an off-chain worker
+	Ocw,
+	/// The runtime with a custom runtime API
+	Api,
+	/// The runtime that keeps a set of GRANDPA authorities. The node seals blocks itself and
+	/// doesn't run GRANDPA voting, so the authorities are only kept in the runtime's storage.
+	MinimalGrandpa,
+}
+
+impl Runtime {
+	/// The prefix of the ids of this runtime's chain specs. The super runtime's specs keep their
+	/// plain `dev` and `local_testnet` ids.
+	pub fn id_prefix(&self) -> Option<&'static str> {
+		match self {
+			Runtime::Super => None,
+			Runtime::WeightFee => Some("weight_fee"),
+			Runtime::Ocw => Some("ocw"),
+			Runtime::Api => Some("api"),
+			Runtime::MinimalGrandpa => Some("minimal_grandpa"),
+		}
+	}
+
+	/// Identifies the runtime a chain spec is meant for from its id.
+	pub fn from_chain_spec_id(id: &str) -> Self {
+		[
+			Runtime::WeightFee,
+			Runtime::Ocw,
+			Runtime::Api,
+			Runtime::MinimalGrandpa,
+		]
+		.iter()
+		.copied()
+		.find(|runtime| {
+			runtime
+				.id_prefix()
+				.map_or(false, |prefix| id.starts_with(prefix))
+		})
+		.unwrap_or(Runtime::Super)
+	}
+}
+
+impl FromStr for Runtime {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"super" => Ok(Runtime::Super),
+			"weight-fee" => Ok(Runtime::WeightFee),
+			"ocw" => Ok(Runtime::Ocw),
+			"api" => Ok(Runtime::Api),
+			"minimal-grandpa" => Ok(Runtime::MinimalGrandpa),
+			other => Err(format!(
+				"Unknown runtime `{}`. Expected `super`, `weight-fee`, `ocw`, `api` or \
+				`minimal-grandpa`",
+				other
+			)),
+		}
+	}
+}
+
+/// The available ways of sealing blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sealing {
+	/// Author a block as soon as a transaction arrives
+	Instant,
+	/// Author a block whenever the `engine_createBlock` RPC is called
+	Manual,
+	/// Author a block at a fixed interval
+	Interval,
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			"interval" => Ok(Sealing::Interval),
+			other => Err(format!(
+				"Unknown sealing mode `{}`. Expected `instant`, `manual` or `interval`",
+				other
+			)),
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -15,6 +128,9 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Generate a chain specification from endowed accounts, a sudo key and genesis values.
+	GenerateSpec(crate::genesis_generator::GenerateSpecCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -24,6 +140,12 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export every pallet's storage at a given block as decoded JSON.
+	ExportSnapshot(crate::snapshot::ExportSnapshotCmd),
+
+	/// Generate a raw chain specification whose genesis is the storage of a snapshot.
+	ImportSnapshot(crate::snapshot::ImportSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -32,4 +154,7 @@ pub enum Subcommand {
 
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
+
+	/// Benchmark the runtime's pallets.
+	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
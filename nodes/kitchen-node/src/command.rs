@@ -1,8 +1,10 @@
 use crate::chain_spec;
-use crate::cli::{Cli, Subcommand};
+use crate::cli::{Cli, Runtime, Subcommand};
 use crate::service;
 use sc_cli::{ChainSpec, Role, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
+use std::time::Duration;
+use super_runtime::opaque::Block;
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -30,20 +32,90 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
-		Ok(match id {
-			"dev" => Box::new(chain_spec::dev_config()?),
-			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
-			path => Box::new(chain_spec::ChainSpec::from_json_file(
-				std::path::PathBuf::from(path),
-			)?),
-		})
+		// The built in chains may name their runtime, as in `weight-fee-dev`.
+		let (named_runtime, chain) = match id.rfind('-') {
+			Some(i) => match id[..i].parse::<Runtime>() {
+				Ok(runtime) => (Some(runtime), &id[i + 1..]),
+				Err(_) => (None, id),
+			},
+			None => (None, id),
+		};
+
+		let runtime = match (named_runtime, self.runtime) {
+			(Some(named), Some(flag)) if named != flag => {
+				return Err(format!(
+					"Chain `{}` is for the {:?} runtime, but `--runtime` asked for {:?}",
+					id, named, flag
+				))
+			}
+			(named, flag) => named.or(flag),
+		};
+
+		match chain {
+			"dev" => chain_spec::dev_config(runtime.unwrap_or(Runtime::Super)),
+			"" | "local" => chain_spec::local_testnet_config(runtime.unwrap_or(Runtime::Super)),
+			_ => {
+				let path = std::path::PathBuf::from(id);
+				// The genesis is only decoded once the chain is built, so any runtime's spec type
+				// can read which runtime the file is really for.
+				let runtime = match self.runtime {
+					Some(runtime) => runtime,
+					None => chain_spec::runtime_of(&chain_spec::SuperChainSpec::from_json_file(
+						path.clone(),
+					)?),
+				};
+				chain_spec::from_json_file(runtime, path)
+			}
+		}
 	}
 
-	fn native_runtime_version(_: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
-		&runtime::VERSION
+	// `load_spec` records the runtime in the chain spec, `--runtime` included, so this and the
+	// subcommands all run the same one.
+	fn native_runtime_version(chain_spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
+		match chain_spec::runtime_of(&**chain_spec) {
+			Runtime::Super => &super_runtime::VERSION,
+			Runtime::WeightFee => &weight_fee_runtime::VERSION,
+			Runtime::Ocw => &ocw_runtime::VERSION,
+			Runtime::Api => &api_runtime::VERSION,
+			Runtime::MinimalGrandpa => &minimal_grandpa_runtime::VERSION,
+		}
 	}
 }
 
+/// Evaluates `$code` with `$api` and `$executor` naming the runtime API and native executor of
+/// `$runtime`.
+macro_rules! with_runtime {
+	($runtime:expr, $api:ident, $executor:ident, $code:expr) => {
+		match $runtime {
+			Runtime::Super => {
+				use service::SuperExecutor as $executor;
+				use super_runtime::RuntimeApi as $api;
+				$code
+			}
+			Runtime::WeightFee => {
+				use service::WeightFeeExecutor as $executor;
+				use weight_fee_runtime::RuntimeApi as $api;
+				$code
+			}
+			Runtime::Ocw => {
+				use ocw_runtime::RuntimeApi as $api;
+				use service::OcwExecutor as $executor;
+				$code
+			}
+			Runtime::Api => {
+				use api_runtime::RuntimeApi as $api;
+				use service::ApiExecutor as $executor;
+				$code
+			}
+			Runtime::MinimalGrandpa => {
+				use minimal_grandpa_runtime::RuntimeApi as $api;
+				use service::MinimalGrandpaExecutor as $executor;
+				$code
+			}
+		}
+	};
+}
+
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
 	let cli = Cli::from_args();
@@ -53,76 +125,142 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		}
+		Some(Subcommand::GenerateSpec(cmd)) => cmd
+			.run(cli.runtime.unwrap_or(Runtime::Super))
+			.map_err(Into::into),
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
-				let PartialComponents {
-					client,
-					task_manager,
-					import_queue,
-					..
-				} = service::new_partial(&config)?;
-				Ok((cmd.run(client, import_queue), task_manager))
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						import_queue,
+						..
+					} = service::new_partial::<RuntimeApi, Executor>(&config)?;
+					Ok((cmd.run(client, import_queue), task_manager))
+				})
 			})
 		}
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
-				let PartialComponents {
-					client,
-					task_manager,
-					..
-				} = service::new_partial(&config)?;
-				Ok((cmd.run(client, config.database), task_manager))
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						..
+					} = service::new_partial::<RuntimeApi, Executor>(&config)?;
+					Ok((cmd.run(client, config.database), task_manager))
+				})
 			})
 		}
 		Some(Subcommand::ExportState(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
-				let PartialComponents {
-					client,
-					task_manager,
-					..
-				} = service::new_partial(&config)?;
-				Ok((cmd.run(client, config.chain_spec), task_manager))
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						..
+					} = service::new_partial::<RuntimeApi, Executor>(&config)?;
+					Ok((cmd.run(client, config.chain_spec), task_manager))
+				})
 			})
 		}
+		Some(Subcommand::ExportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.sync_run(|config| {
+					let PartialComponents { client, .. } =
+						service::new_partial::<RuntimeApi, Executor>(&config)?;
+					cmd.run(&*client, runtime).map_err(Into::into)
+				})
+			})
+		}
+		Some(Subcommand::ImportSnapshot(cmd)) => cmd
+			.run(cli.runtime.unwrap_or(Runtime::Super))
+			.map_err(Into::into),
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
-				let PartialComponents {
-					client,
-					task_manager,
-					import_queue,
-					..
-				} = service::new_partial(&config)?;
-				Ok((cmd.run(client, import_queue), task_manager))
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						import_queue,
+						..
+					} = service::new_partial::<RuntimeApi, Executor>(&config)?;
+					Ok((cmd.run(client, import_queue), task_manager))
+				})
 			})
 		}
+		Some(Subcommand::Benchmark(cmd)) => {
+			if cfg!(feature = "runtime-benchmarks") {
+				let runner = cli.create_runner(cmd)?;
+				// Benchmarks run the runtime directly, so only the executor is needed.
+				match chain_spec::runtime_of(&*runner.config().chain_spec) {
+					Runtime::Super => {
+						runner.sync_run(|config| cmd.run::<Block, service::SuperExecutor>(config))
+					}
+					Runtime::WeightFee => runner
+						.sync_run(|config| cmd.run::<Block, service::WeightFeeExecutor>(config)),
+					Runtime::Ocw => Err("The ocw-runtime has no benchmarks.".into()),
+					Runtime::Api => {
+						runner.sync_run(|config| cmd.run::<Block, service::ApiExecutor>(config))
+					}
+					Runtime::MinimalGrandpa => {
+						Err("The minimal-grandpa-runtime has no benchmarks.".into())
+					}
+				}
+			} else {
+				Err("Benchmarking wasn't enabled when building the node. \
+				You can enable it with `--features runtime-benchmarks`."
+					.into())
+			}
+		}
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.database))
 		}
 		Some(Subcommand::Revert(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
-				let PartialComponents {
-					client,
-					task_manager,
-					backend,
-					..
-				} = service::new_partial(&config)?;
-				Ok((cmd.run(client, backend), task_manager))
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						backend,
+						..
+					} = service::new_partial::<RuntimeApi, Executor>(&config)?;
+					Ok((cmd.run(client, backend), task_manager))
+				})
 			})
 		}
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
-			runner.run_node_until_exit(|config| async move {
-				match config.role {
-					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
-				}
-				.map_err(sc_cli::Error::Service)
+			let runtime = chain_spec::runtime_of(&*runner.config().chain_spec);
+			let sealing = cli.sealing;
+			let sealing_interval = Duration::from_millis(cli.sealing_interval);
+			with_runtime!(runtime, RuntimeApi, Executor, {
+				runner.run_node_until_exit(|config| async move {
+					match config.role {
+						Role::Light => service::new_light::<RuntimeApi, Executor>(config),
+						_ => service::new_full::<RuntimeApi, Executor>(
+							config,
+							runtime,
+							sealing,
+							sealing_interval,
+						),
+					}
+					.map_err(sc_cli::Error::Service)
+				})
 			})
 		}
 	}
@@ -0,0 +1,232 @@
+//! The `generate-spec` subcommand. It writes a chain spec for a bespoke test network from a list of
+//! endowed accounts, a sudo key and per-pallet genesis values, so no recompiling is needed.
+
+use crate::chain_spec;
+use crate::cli::Runtime;
+use sc_service::{ChainType, GenericChainSpec, RuntimeGenesis};
+use serde::Deserialize;
+use serde_json::Value;
+use sp_core::crypto::Ss58Codec;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+use super_runtime::{AccountId, Balance};
+
+/// The `generate-spec` command
+#[derive(Debug, StructOpt)]
+pub struct GenerateSpecCmd {
+	/// JSON file listing the endowed accounts, as `[{ "account": "<SS58>", "balance": 1000 }]`.
+	/// Accounts without a balance get the same endowment as the built in testnets.
+	#[structopt(long = "endowed-accounts", parse(from_os_str))]
+	pub endowed_accounts: PathBuf,
+
+	/// Account, in SS58 format, that holds the sudo key
+	#[structopt(long = "sudo", parse(try_from_str = parse_account))]
+	pub sudo: AccountId,
+
+	/// JSON file with genesis values for individual pallets, such as
+	/// `{ "charity": { "pot": 1000 } }`. They are laid over the genesis built from the other
+	/// options, so only the values that differ need to be given.
+	#[structopt(long = "genesis", parse(from_os_str))]
+	pub genesis: Option<PathBuf>,
+
+	/// Human readable name of the chain
+	#[structopt(long = "name", default_value = "Custom Testnet")]
+	pub name: String,
+
+	/// Id of the chain. Ids of runtimes other than the super runtime are prefixed with the
+	/// runtime's name. The spec also records the runtime, so the node runs it with the right one.
+	#[structopt(long = "id", default_value = "custom_testnet")]
+	pub id: String,
+
+	/// Write the genesis as raw storage, ready to be shared with other nodes
+	#[structopt(long = "raw")]
+	pub raw: bool,
+
+	/// File to write the chain spec to, instead of stdout
+	#[structopt(long = "output", short = "o", parse(from_os_str))]
+	pub output: Option<PathBuf>,
+}
+
+/// The balance of endowed accounts that don't give one, the same as in the built in testnets
+const TESTNET_ENDOWMENT: Balance = 1 << 60;
+
+/// One entry of the endowed accounts file
+#[derive(Debug, Deserialize)]
+struct EndowedAccount {
+	account: String,
+	balance: Option<Balance>,
+}
+
+fn parse_account(address: &str) -> Result<AccountId, String> {
+	AccountId::from_ss58check(address)
+		.map_err(|e| format!("Invalid account address `{}`: {:?}", address, e))
+}
+
+impl GenerateSpecCmd {
+	/// Generate a chain spec for `runtime` and write it out
+	pub fn run(&self, runtime: Runtime) -> Result<(), String> {
+		let balances = self.read_balances()?;
+		let sudo = self.sudo.clone();
+
+		// Each runtime's testnet genesis sets up the sudo key and its pallets. Only the balances
+		// are replaced, and every runtime keeps those in `pallet_balances`.
+		macro_rules! endowed {
+			($runtime:ident, $genesis:expr) => {{
+				let mut genesis = $genesis;
+				genesis.pallet_balances = Some($runtime::BalancesConfig { balances });
+				genesis
+			}};
+		}
+
+		let json = match runtime {
+			Runtime::Super => self.generate(
+				runtime,
+				endowed!(
+					super_runtime,
+					super_runtime::genesis::testnet_genesis(
+						wasm_binary(super_runtime::WASM_BINARY)?,
+						sudo,
+						vec![],
+					)
+				),
+			),
+			Runtime::WeightFee => self.generate(
+				runtime,
+				endowed!(
+					weight_fee_runtime,
+					weight_fee_runtime::genesis::testnet_genesis(
+						wasm_binary(weight_fee_runtime::WASM_BINARY)?,
+						sudo,
+						vec![],
+					)
+				),
+			),
+			Runtime::Ocw => self.generate(
+				runtime,
+				endowed!(
+					ocw_runtime,
+					ocw_runtime::genesis::testnet_genesis(
+						wasm_binary(ocw_runtime::WASM_BINARY)?,
+						sudo,
+						vec![],
+					)
+				),
+			),
+			Runtime::Api => self.generate(
+				runtime,
+				endowed!(
+					api_runtime,
+					api_runtime::genesis::testnet_genesis(
+						wasm_binary(api_runtime::WASM_BINARY)?,
+						sudo,
+						vec![],
+					)
+				),
+			),
+			// Alice is the only GRANDPA authority, like in the built in chains. Since this node
+			// doesn't run GRANDPA, other authorities can be set through `--genesis` if needed.
+			Runtime::MinimalGrandpa => self.generate(
+				runtime,
+				endowed!(
+					minimal_grandpa_runtime,
+					minimal_grandpa_runtime::genesis::testnet_genesis(
+						wasm_binary(minimal_grandpa_runtime::WASM_BINARY)?,
+						vec![minimal_grandpa_runtime::genesis::authority_keys_from_seed(
+							"Alice"
+						)],
+						sudo,
+						vec![],
+					)
+				),
+			),
+		}?;
+
+		match &self.output {
+			Some(path) => fs::write(path, json)
+				.map_err(|e| format!("Could not write `{}`: {}", path.display(), e)),
+			None => {
+				println!("{}", json);
+				Ok(())
+			}
+		}
+	}
+
+	fn read_balances(&self) -> Result<Vec<(AccountId, Balance)>, String> {
+		let accounts: Vec<EndowedAccount> = serde_json::from_str(&read(&self.endowed_accounts)?)
+			.map_err(|e| {
+				format!(
+					"Invalid endowed accounts file `{}`: {}",
+					self.endowed_accounts.display(),
+					e
+				)
+			})?;
+
+		accounts
+			.into_iter()
+			.map(|endowed| {
+				Ok((
+					parse_account(&endowed.account)?,
+					endowed.balance.unwrap_or(TESTNET_ENDOWMENT),
+				))
+			})
+			.collect()
+	}
+
+	/// Lay the per-pallet values over `genesis` and render the chain spec as JSON
+	fn generate<G: RuntimeGenesis + 'static>(
+		&self,
+		runtime: Runtime,
+		genesis: G,
+	) -> Result<String, String> {
+		let mut genesis = serde_json::to_value(&genesis)
+			.map_err(|e| format!("Could not encode the genesis: {}", e))?;
+		if let Some(path) = &self.genesis {
+			let overrides = serde_json::from_str(&read(path)?)
+				.map_err(|e| format!("Invalid genesis file `{}`: {}", path.display(), e))?;
+			merge(&mut genesis, overrides);
+		}
+
+		// Decoding the merged values checks they fit the runtime, so unknown pallets or fields
+		// are reported here rather than when a node first starts the chain.
+		serde_json::from_value::<G>(genesis.clone())
+			.map_err(|e| format!("Genesis values don't fit the {:?} runtime: {}", runtime, e))?;
+
+		let chain_spec = GenericChainSpec::from_genesis(
+			&self.name,
+			&chain_spec::chain_spec_id(runtime, &self.id),
+			ChainType::Local,
+			move || serde_json::from_value(genesis.clone()).expect("checked above; qed"),
+			vec![],
+			None,
+			None,
+			None,
+			chain_spec::Extensions::new(runtime),
+		);
+
+		sc_service::ChainSpec::as_json(&chain_spec, self.raw)
+	}
+}
+
+fn wasm_binary(wasm_binary: Option<&'static [u8]>) -> Result<&'static [u8], String> {
+	wasm_binary.ok_or_else(|| "Wasm binary not available".to_string())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+	fs::read_to_string(path).map_err(|e| format!("Could not read `{}`: {}", path.display(), e))
+}
+
+/// Recursively lay the values of `overrides` over `base`. Objects are merged key by key, anything
+/// else replaces what was there.
+fn merge(base: &mut Value, overrides: Value) {
+	match (base, overrides) {
+		(Value::Object(base), Value::Object(overrides)) => {
+			for (key, value) in overrides {
+				merge(base.entry(key).or_insert(Value::Null), value);
+			}
+		}
+		(base, overrides) => *base = overrides,
+	}
+}
@@ -8,6 +8,8 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod genesis_generator;
+mod snapshot;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
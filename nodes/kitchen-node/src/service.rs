@@ -1,43 +1,145 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 #![allow(clippy::needless_borrow)]
-use runtime::{self, opaque::Block, RuntimeApi};
+use crate::cli::{Runtime, Sealing};
+use futures::{channel::mpsc, prelude::*};
 use sc_client_api::RemoteBackend;
-use sc_consensus_manual_seal::InstantSealParams;
-use sc_executor::native_executor_instance;
+use sc_consensus_manual_seal::{
+	rpc::{ManualSeal, ManualSealApi},
+	EngineCommand, InstantSealParams, ManualSealParams,
+};
 pub use sc_executor::NativeExecutor;
+use sc_executor::{native_executor_instance, NativeExecutionDispatch};
 use sc_service::{error::Error as ServiceError, Configuration, PartialComponents, TaskManager};
-use sp_api::TransactionFor;
+use sp_api::{ConstructRuntimeApi, TransactionFor};
 use sp_consensus::import_queue::BasicQueue;
+use sp_core::H256;
 use sp_inherents::InherentDataProviders;
-use std::sync::Arc;
+use sp_runtime::traits::BlakeTwo256;
+use std::{pin::Pin, sync::Arc, time::Duration};
+use super_runtime::opaque::Block;
 
-// Our native executor instance.
+// One native executor instance for each runtime the node can run.
 native_executor_instance!(
-	pub Executor,
-	runtime::api::dispatch,
-	runtime::native_version,
+	pub SuperExecutor,
+	super_runtime::api::dispatch,
+	super_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
 );
 
-type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
+native_executor_instance!(
+	pub WeightFeeExecutor,
+	weight_fee_runtime::api::dispatch,
+	weight_fee_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
+);
+
+native_executor_instance!(
+	pub OcwExecutor,
+	ocw_runtime::api::dispatch,
+	ocw_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
+);
+
+native_executor_instance!(
+	pub ApiExecutor,
+	api_runtime::api::dispatch,
+	api_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
+);
+
+native_executor_instance!(
+	pub MinimalGrandpaExecutor,
+	minimal_grandpa_runtime::api::dispatch,
+	minimal_grandpa_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
+);
+
+/// The runtime APIs the node needs from whichever runtime it runs.
+pub trait RuntimeApiCollection:
+	sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
+	+ sp_api::ApiExt<Block>
+	+ sp_block_builder::BlockBuilder<Block>
+	+ sp_api::Metadata<Block>
+	+ sp_offchain::OffchainWorkerApi<Block>
+	+ sp_session::SessionKeys<Block>
+where
+	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
+{
+}
+
+impl<Api> RuntimeApiCollection for Api
+where
+	Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
+		+ sp_api::ApiExt<Block>
+		+ sp_block_builder::BlockBuilder<Block>
+		+ sp_api::Metadata<Block>
+		+ sp_offchain::OffchainWorkerApi<Block>
+		+ sp_session::SessionKeys<Block>,
+	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
+{
+}
+
+/// RPC extensions that only work with some of the runtimes, because they need runtime APIs the
+/// others don't have.
+pub trait RuntimeRpc<Executor>: Sized {
+	/// Adds this runtime's extra RPC methods to `io`. Most runtimes have none.
+	fn extend_rpc(
+		_client: Arc<FullClient<Self, Executor>>,
+		_io: &mut jsonrpc_core::IoHandler<sc_rpc::Metadata>,
+	) {
+	}
+}
+
+impl<Executor> RuntimeRpc<Executor> for super_runtime::RuntimeApi {}
+impl<Executor> RuntimeRpc<Executor> for ocw_runtime::RuntimeApi {}
+impl<Executor> RuntimeRpc<Executor> for api_runtime::RuntimeApi {}
+impl<Executor> RuntimeRpc<Executor> for minimal_grandpa_runtime::RuntimeApi {}
+
+impl<Executor> RuntimeRpc<Executor> for weight_fee_runtime::RuntimeApi
+where
+	Executor: NativeExecutionDispatch + 'static,
+{
+	fn extend_rpc(
+		client: Arc<FullClient<Self, Executor>>,
+		io: &mut jsonrpc_core::IoHandler<sc_rpc::Metadata>,
+	) {
+		use fee_breakdown_rpc::{FeeBreakdown, FeeBreakdownApi};
+
+		let fee_breakdown: FeeBreakdown<_, _, weight_fee_runtime::Balance> =
+			FeeBreakdown::new(client);
+		io.extend_with(FeeBreakdownApi::to_delegate(fee_breakdown));
+	}
+}
+
+type FullClient<RuntimeApi, Executor> = sc_service::TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type LightBackend = sc_service::TLightBackend<Block>;
+type LightClient<RuntimeApi, Executor> = sc_service::TLightClient<Block, RuntimeApi, Executor>;
 
 /// Returns most parts of a service. Not enough to run a full chain,
 /// But enough to perform chain operations like purge-chain
 #[allow(clippy::type_complexity)]
-pub fn new_partial(
+pub fn new_partial<RuntimeApi, Executor>(
 	config: &Configuration,
 ) -> Result<
 	PartialComponents<
-		FullClient,
+		FullClient<RuntimeApi, Executor>,
 		FullBackend,
 		FullSelectChain,
-		BasicQueue<Block, TransactionFor<FullClient, Block>>,
-		sc_transaction_pool::FullPool<Block, FullClient>,
+		BasicQueue<Block, TransactionFor<FullClient<RuntimeApi, Executor>, Block>>,
+		sc_transaction_pool::FullPool<Block, FullClient<RuntimeApi, Executor>>,
 		(),
 	>,
 	ServiceError,
-> {
+>
+where
+	RuntimeApi:
+		ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	Executor: NativeExecutionDispatch + 'static,
+{
 	let inherent_data_providers = InherentDataProviders::new();
 	inherent_data_providers
 		.register_provider(sp_timestamp::InherentDataProvider)
@@ -78,7 +180,23 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// `sealing_interval` is only used when sealing at an interval. `runtime` must be the runtime that
+/// `RuntimeApi` and `Executor` belong to.
+pub fn new_full<RuntimeApi, Executor>(
+	config: Configuration,
+	runtime: Runtime,
+	sealing: Sealing,
+	sealing_interval: Duration,
+) -> Result<TaskManager, ServiceError>
+where
+	RuntimeApi:
+		ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	RuntimeApi: RuntimeRpc<Executor>,
+	Executor: NativeExecutionDispatch + 'static,
+{
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -89,7 +207,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		transaction_pool,
 		inherent_data_providers,
 		..
-	} = new_partial(&config)?;
+	} = new_partial::<RuntimeApi, Executor>(&config)?;
 
 	let (network, network_status_sinks, system_rpc_tx, network_starter) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
@@ -107,11 +225,10 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		// Initialize seed for signing transaction using off-chain workers. This is a convenience
 		// so learners can see the transactions submitted simply running the node.
 		// Typically these keys should be inserted with RPC calls to `author_insertKey`.
-		#[cfg(feature = "ocw")]
-		{
+		if runtime == Runtime::Ocw {
 			sp_keystore::SyncCryptoStore::sr25519_generate_new(
 				&*keystore,
-				runtime::ocw_demo::KEY_TYPE,
+				ocw_runtime::ocw_demo::KEY_TYPE,
 				Some("//Alice"),
 			)
 			.expect("Creating key with account Alice should succeed.");
@@ -129,13 +246,35 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let is_authority = config.role.is_authority();
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// In manual mode, blocks are requested through RPC. The RPC handler passes each request down
+	// this channel to the authorship task.
+	let (command_sink, commands_stream) = match (is_authority, sealing) {
+		(true, Sealing::Manual) => {
+			let (sink, stream) = mpsc::channel(1024);
+			(Some(sink), Some(stream))
+		}
+		_ => (None, None),
+	};
+
+	let rpc_client = client.clone();
+	let rpc_extensions_builder = Box::new(move |_, _| {
+		let mut io = jsonrpc_core::IoHandler::<sc_rpc::Metadata>::default();
+		RuntimeApi::extend_rpc(rpc_client.clone(), &mut io);
+		if let Some(command_sink) = &command_sink {
+			io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(
+				command_sink.clone(),
+			)));
+		}
+		io
+	});
+
 	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		network,
 		client: client.clone(),
 		keystore,
 		task_manager: &mut task_manager,
 		transaction_pool: transaction_pool.clone(),
-		rpc_extensions_builder: Box::new(|_, _| ()),
+		rpc_extensions_builder,
 		on_demand: None,
 		remote_blockchain: None,
 		backend,
@@ -152,27 +291,78 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 			prometheus_registry.as_ref(),
 		);
 
-		let authorship_future = sc_consensus_manual_seal::run_instant_seal(InstantSealParams {
-			block_import: client.clone(),
-			env: proposer,
-			client,
-			pool: transaction_pool.pool().clone(),
-			select_chain,
-			consensus_data_provider: None,
-			inherent_data_providers,
-		});
-
-		task_manager
-			.spawn_essential_handle()
-			.spawn_blocking("instant-seal", authorship_future);
-	};
+		match sealing {
+			Sealing::Instant => {
+				let authorship_future =
+					sc_consensus_manual_seal::run_instant_seal(InstantSealParams {
+						block_import: client.clone(),
+						env: proposer,
+						client,
+						pool: transaction_pool.pool().clone(),
+						select_chain,
+						consensus_data_provider: None,
+						inherent_data_providers,
+					});
+
+				task_manager
+					.spawn_essential_handle()
+					.spawn_blocking("instant-seal", authorship_future);
+			}
+			Sealing::Manual | Sealing::Interval => {
+				let commands_stream: Pin<Box<dyn Stream<Item = EngineCommand<H256>> + Send>> =
+					match commands_stream {
+						Some(commands_stream) => Box::pin(commands_stream),
+						// Ask for a new block every interval, whether or not there are transactions.
+						None => Box::pin(interval(sealing_interval).map(|_| {
+							EngineCommand::SealNewBlock {
+								create_empty: true,
+								finalize: false,
+								parent_hash: None,
+								sender: None,
+							}
+						})),
+					};
+
+				let authorship_future =
+					sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+						block_import: client.clone(),
+						env: proposer,
+						client,
+						pool: transaction_pool.pool().clone(),
+						commands_stream,
+						select_chain,
+						consensus_data_provider: None,
+						inherent_data_providers,
+					});
+
+				task_manager
+					.spawn_essential_handle()
+					.spawn_blocking("manual-seal", authorship_future);
+			}
+		}
+	}
 
 	network_starter.start_network();
 	Ok(task_manager)
 }
 
+/// A stream that yields once every `duration`.
+fn interval(duration: Duration) -> impl Stream<Item = ()> {
+	stream::unfold((), move |_| async move {
+		futures_timer::Delay::new(duration).await;
+		Some(((), ()))
+	})
+}
+
 /// Builds a new service for a light client.
-pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_light<RuntimeApi, Executor>(config: Configuration) -> Result<TaskManager, ServiceError>
+where
+	RuntimeApi:
+		ConstructRuntimeApi<Block, LightClient<RuntimeApi, Executor>> + Send + Sync + 'static,
+	<RuntimeApi as ConstructRuntimeApi<Block, LightClient<RuntimeApi, Executor>>>::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<LightBackend, Block>>,
+	Executor: NativeExecutionDispatch + 'static,
+{
 	let (client, backend, keystore_container, mut task_manager, on_demand) =
 		sc_service::new_light_parts::<Block, RuntimeApi, Executor>(&config)?;
 
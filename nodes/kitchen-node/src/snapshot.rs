@@ -0,0 +1,1127 @@
+//! The `export-snapshot` and `import-snapshot` subcommands.
+//!
+//! A snapshot is a JSON file holding every storage item of every pallet at some block. The
+//! runtime's metadata tells us which keys belong to which item, and what types the keys and values
+//! have, so values of primitive types and of the selected runtime's own types are written out
+//! decoded. Anything else, including aliases that pallets define themselves, is kept as hex.
+//! Importing a snapshot turns it back into storage, and writes a chain spec whose genesis is that
+//! storage.
+
+use crate::chain_spec;
+use crate::cli::Runtime;
+use frame_metadata::{
+	DecodeDifferent, ModuleMetadata, RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType,
+	StorageHasher,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, PruningParams, SharedParams};
+use sc_client_api::{backend::Backend, StorageProvider};
+use sc_service::{ChainType, GenericChainSpec};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	bytes::{from_hex, to_hex},
+	crypto::Ss58Codec,
+	hashing::{blake2_128, blake2_256, twox_128, twox_256, twox_64},
+	storage::{well_known_keys, ChildInfo, Storage, StorageChild, StorageKey},
+	H256,
+};
+use sp_runtime::{generic::BlockId, AccountId32, BuildStorage};
+use std::{collections::BTreeMap, convert::TryFrom, fs, path::PathBuf};
+use structopt::StructOpt;
+// Every runtime of this node shares the same opaque block type
+use super_runtime::{opaque::Block, BlockNumber};
+
+/// The `export-snapshot` command
+#[derive(Debug, StructOpt)]
+pub struct ExportSnapshotCmd {
+	/// Block hash or number to take the snapshot at. Defaults to the best block.
+	#[structopt(value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	/// File to write the snapshot to, instead of stdout
+	#[structopt(long = "output", short = "o", parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl CliConfiguration for ExportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
+
+/// The `import-snapshot` command
+#[derive(Debug, StructOpt)]
+pub struct ImportSnapshotCmd {
+	/// Snapshot file written by `export-snapshot`
+	#[structopt(parse(from_os_str))]
+	pub snapshot: PathBuf,
+
+	/// Human readable name of the chain
+	#[structopt(long = "name", default_value = "Snapshot Testnet")]
+	pub name: String,
+
+	/// Id of the chain. Ids of runtimes other than the super runtime are prefixed with the
+	/// runtime's name. The spec also records the runtime, so the node runs it with the right one.
+	#[structopt(long = "id", default_value = "snapshot_testnet")]
+	pub id: String,
+
+	/// File to write the chain spec to, instead of stdout
+	#[structopt(long = "output", short = "o", parse(from_os_str))]
+	pub output: Option<PathBuf>,
+}
+
+/// The contents of a snapshot file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+	/// The block the snapshot was taken at
+	block: SnapshotBlock,
+	/// The storage items of each pallet, by storage prefix and item name
+	pallets: BTreeMap<String, BTreeMap<String, Item>>,
+	/// Top level storage that no pallet's metadata accounts for, such as the runtime code
+	other: BTreeMap<String, String>,
+	/// The contents of every child trie, by the child trie's storage key
+	child_tries: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotBlock {
+	number: BlockNumber,
+	hash: String,
+}
+
+/// One storage item, along with the types that its values were decoded as
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum Item {
+	#[serde(rename_all = "camelCase")]
+	Value {
+		#[serde(rename = "type")]
+		ty: String,
+		value: Value,
+	},
+	#[serde(rename_all = "camelCase")]
+	Map {
+		hasher: String,
+		key_type: String,
+		value_type: String,
+		entries: Vec<Entry>,
+	},
+	#[serde(rename_all = "camelCase")]
+	DoubleMap {
+		hasher1: String,
+		hasher2: String,
+		key1_type: String,
+		key2_type: String,
+		value_type: String,
+		entries: Vec<Entry>,
+	},
+}
+
+/// One entry of a map. Keys behind hashers that don't keep the key around can't be decoded, so
+/// such entries only have the full storage key in `raw_key`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	key: Option<Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	key2: Option<Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	raw_key: Option<String>,
+	value: Value,
+}
+
+impl ExportSnapshotCmd {
+	/// Take the snapshot of a chain running `runtime` and write it out
+	pub fn run<C, BE>(&self, client: &C, runtime: Runtime) -> Result<(), String>
+	where
+		BE: Backend<Block>,
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+		C::Api: Metadata<Block>,
+	{
+		let at = match &self.block {
+			Some(block) => block.parse::<Block>()?,
+			None => BlockId::Hash(client.info().best_hash),
+		};
+		let hash = client
+			.expect_block_hash_from_id(&at)
+			.map_err(|e| e.to_string())?;
+		let number = client
+			.expect_block_number_from_id(&at)
+			.map_err(|e| e.to_string())?;
+		let at = BlockId::Hash(hash);
+
+		let metadata = client
+			.runtime_api()
+			.metadata(&at)
+			.map_err(|e| format!("Could not fetch the metadata: {:?}", e))?;
+		let modules = decode_modules(&metadata)?;
+
+		let mut storage = Storage {
+			top: client
+				.storage_pairs(&at, &StorageKey(Vec::new()))
+				.map_err(|e| e.to_string())?
+				.into_iter()
+				.map(|(key, value)| (key.0, value.0))
+				.collect(),
+			children_default: Default::default(),
+		};
+
+		// The top trie only holds the roots of the child tries, their contents are fetched
+		// separately.
+		let child_roots = storage
+			.top
+			.keys()
+			.filter(|key| key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX));
+		for prefixed_key in child_roots {
+			let storage_key =
+				&prefixed_key[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..];
+			let child_info = ChildInfo::new_default(storage_key);
+			let mut data = BTreeMap::new();
+			for key in client
+				.child_storage_keys(&at, &child_info, &StorageKey(Vec::new()))
+				.map_err(|e| e.to_string())?
+			{
+				if let Some(value) = client
+					.child_storage(&at, &child_info, &key)
+					.map_err(|e| e.to_string())?
+				{
+					data.insert(key.0, value.0);
+				}
+			}
+			storage
+				.children_default
+				.insert(storage_key.to_vec(), StorageChild { data, child_info });
+		}
+
+		let block = SnapshotBlock {
+			number,
+			hash: format!("{:?}", hash),
+		};
+		let types = RuntimeTypes::of(runtime);
+		let snapshot = Snapshot::from_storage(block, modules, storage, &types)?;
+		let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+		write(&self.output, json)
+	}
+}
+
+impl ImportSnapshotCmd {
+	/// Turn the snapshot into the genesis of a chain spec for `runtime` and write it out
+	pub fn run(&self, runtime: Runtime) -> Result<(), String> {
+		let snapshot = fs::read_to_string(&self.snapshot)
+			.map_err(|e| format!("Could not read `{}`: {}", self.snapshot.display(), e))?;
+		let snapshot: Snapshot = serde_json::from_str(&snapshot)
+			.map_err(|e| format!("Invalid snapshot `{}`: {}", self.snapshot.display(), e))?;
+		let genesis = snapshot.into_genesis(&RuntimeTypes::of(runtime))?;
+
+		let chain_spec = GenericChainSpec::from_genesis(
+			&self.name,
+			&chain_spec::chain_spec_id(runtime, &self.id),
+			ChainType::Local,
+			move || genesis.clone(),
+			vec![],
+			None,
+			None,
+			None,
+			chain_spec::Extensions::new(runtime),
+		);
+
+		// Only the raw form makes sense, the snapshot's storage is all there is.
+		let json = sc_service::ChainSpec::as_json(&chain_spec, true)?;
+		write(&self.output, json)
+	}
+}
+
+impl Snapshot {
+	/// Sort `storage` into the items of the pallets described by `modules`
+	fn from_storage(
+		block: SnapshotBlock,
+		modules: Vec<ModuleMetadata>,
+		storage: Storage,
+		types: &RuntimeTypes,
+	) -> Result<Self, String> {
+		let mut remaining = storage.top;
+
+		let mut pallets = BTreeMap::new();
+		for storage in modules.into_iter().filter_map(|module| module.storage) {
+			let storage = decoded(storage)?;
+			let prefix = decoded(storage.prefix)?;
+			let mut items = BTreeMap::new();
+
+			for entry in decoded(storage.entries)? {
+				let name = decoded(entry.name)?;
+				let item_prefix = item_prefix(&prefix, &name);
+				let pairs = take_prefixed(&mut remaining, &item_prefix);
+				if pairs.is_empty() {
+					continue;
+				}
+
+				let item = match entry.ty {
+					StorageEntryType::Plain(ty) => {
+						let ty = decoded(ty)?;
+						Item::Value {
+							value: decode_value(&Ty::parse(&ty, types), &pairs[0].1),
+							ty,
+						}
+					}
+					StorageEntryType::Map {
+						hasher, key, value, ..
+					} => {
+						let (key_type, value_type) = (decoded(key)?, decoded(value)?);
+						let (key_ty, value_ty) =
+							(Ty::parse(&key_type, types), Ty::parse(&value_type, types));
+						let entries = pairs
+							.iter()
+							.map(|(storage_key, value)| {
+								let mut input = &storage_key[item_prefix.len()..];
+								let mut entry = Entry {
+									key: decode_key(&hasher, &key_ty, &mut input),
+									value: decode_value(&value_ty, value),
+									..Default::default()
+								};
+								if entry.key.is_none() || !input.is_empty() {
+									entry.key = None;
+									entry.raw_key = Some(to_hex(storage_key, false));
+								}
+								entry
+							})
+							.collect();
+						Item::Map {
+							hasher: hasher_name(&hasher).into(),
+							key_type,
+							value_type,
+							entries,
+						}
+					}
+					StorageEntryType::DoubleMap {
+						hasher,
+						key1,
+						key2,
+						value,
+						key2_hasher,
+					} => {
+						let (key1_type, key2_type) = (decoded(key1)?, decoded(key2)?);
+						let value_type = decoded(value)?;
+						let (key1_ty, key2_ty) =
+							(Ty::parse(&key1_type, types), Ty::parse(&key2_type, types));
+						let value_ty = Ty::parse(&value_type, types);
+						let entries = pairs
+							.iter()
+							.map(|(storage_key, value)| {
+								let mut input = &storage_key[item_prefix.len()..];
+								// An opaque first key would swallow the second one
+								let key = match key1_ty {
+									Ty::Opaque => None,
+									_ => decode_key(&hasher, &key1_ty, &mut input),
+								};
+								let key2 = key
+									.as_ref()
+									.and_then(|_| decode_key(&key2_hasher, &key2_ty, &mut input));
+								let value = decode_value(&value_ty, value);
+								match (key, key2) {
+									(Some(key), Some(key2)) if input.is_empty() => Entry {
+										key: Some(key),
+										key2: Some(key2),
+										value,
+										..Default::default()
+									},
+									_ => Entry {
+										raw_key: Some(to_hex(storage_key, false)),
+										value,
+										..Default::default()
+									},
+								}
+							})
+							.collect();
+						Item::DoubleMap {
+							hasher1: hasher_name(&hasher).into(),
+							hasher2: hasher_name(&key2_hasher).into(),
+							key1_type,
+							key2_type,
+							value_type,
+							entries,
+						}
+					}
+				};
+				items.insert(name, item);
+			}
+
+			if !items.is_empty() {
+				pallets.insert(prefix, items);
+			}
+		}
+
+		// The roots of the child tries are derived from their contents, so they aren't kept
+		take_prefixed(
+			&mut remaining,
+			well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX,
+		);
+		let child_tries = storage
+			.children_default
+			.into_iter()
+			.map(|(storage_key, child)| {
+				let contents = child
+					.data
+					.iter()
+					.map(|(key, value)| (to_hex(key, false), to_hex(value, false)))
+					.collect();
+				(to_hex(&storage_key, false), contents)
+			})
+			.collect();
+
+		Ok(Snapshot {
+			block,
+			pallets,
+			other: remaining
+				.iter()
+				.map(|(key, value)| (to_hex(key, false), to_hex(value, false)))
+				.collect(),
+			child_tries,
+		})
+	}
+
+	fn into_genesis(self, types: &RuntimeTypes) -> Result<SnapshotGenesis, String> {
+		let mut top = BTreeMap::new();
+		for (key, value) in self.other {
+			top.insert(hex(&key)?, hex(&value)?);
+		}
+
+		for (prefix, items) in self.pallets {
+			for (name, item) in items {
+				let item_prefix = item_prefix(&prefix, &name);
+				match item {
+					Item::Value { ty, value } => {
+						top.insert(item_prefix, encode_value(&Ty::parse(&ty, types), &value)?);
+					}
+					Item::Map {
+						hasher,
+						key_type,
+						value_type,
+						entries,
+					} => {
+						let (key_ty, value_ty) =
+							(Ty::parse(&key_type, types), Ty::parse(&value_type, types));
+						for entry in entries {
+							let key = match (&entry.raw_key, &entry.key) {
+								(Some(raw_key), _) => hex(raw_key)?,
+								(None, Some(key)) => {
+									let mut storage_key = item_prefix.clone();
+									storage_key
+										.extend(hash_key(&hasher, &encode_value(&key_ty, key)?)?);
+									storage_key
+								}
+								(None, None) => {
+									return Err(format!(
+										"An entry of {}::{} has no key",
+										prefix, name
+									))
+								}
+							};
+							top.insert(key, encode_value(&value_ty, &entry.value)?);
+						}
+					}
+					Item::DoubleMap {
+						hasher1,
+						hasher2,
+						key1_type,
+						key2_type,
+						value_type,
+						entries,
+					} => {
+						let (key1_ty, key2_ty) =
+							(Ty::parse(&key1_type, types), Ty::parse(&key2_type, types));
+						let value_ty = Ty::parse(&value_type, types);
+						for entry in entries {
+							let key = match (&entry.raw_key, &entry.key, &entry.key2) {
+								(Some(raw_key), _, _) => hex(raw_key)?,
+								(None, Some(key1), Some(key2)) => {
+									let mut storage_key = item_prefix.clone();
+									storage_key.extend(hash_key(
+										&hasher1,
+										&encode_value(&key1_ty, key1)?,
+									)?);
+									storage_key.extend(hash_key(
+										&hasher2,
+										&encode_value(&key2_ty, key2)?,
+									)?);
+									storage_key
+								}
+								_ => {
+									return Err(format!(
+										"An entry of {}::{} is missing a key",
+										prefix, name
+									))
+								}
+							};
+							top.insert(key, encode_value(&value_ty, &entry.value)?);
+						}
+					}
+				}
+			}
+		}
+
+		let mut children = Vec::new();
+		for (storage_key, contents) in self.child_tries {
+			let contents = contents
+				.iter()
+				.map(|(key, value)| Ok((hex(key)?, hex(value)?)))
+				.collect::<Result<_, String>>()?;
+			children.push((hex(&storage_key)?, contents));
+		}
+
+		Ok(SnapshotGenesis {
+			top: top.into_iter().collect(),
+			children,
+		})
+	}
+}
+
+/// A genesis that is nothing but the storage taken from a snapshot
+#[derive(Clone, Serialize, Deserialize)]
+struct SnapshotGenesis {
+	top: Vec<(Vec<u8>, Vec<u8>)>,
+	children: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+}
+
+impl BuildStorage for SnapshotGenesis {
+	fn assimilate_storage(&self, storage: &mut Storage) -> Result<(), String> {
+		storage.top.extend(self.top.iter().cloned());
+		for (storage_key, contents) in &self.children {
+			let child_info = ChildInfo::new_default(storage_key);
+			storage
+				.children_default
+				.entry(storage_key.clone())
+				.or_insert_with(|| StorageChild {
+					data: Default::default(),
+					child_info,
+				})
+				.data
+				.extend(contents.iter().cloned());
+		}
+		Ok(())
+	}
+}
+
+/// The types that snapshots know how to decode and encode.
+///
+/// Type names in the metadata are those written in the pallets' source, and the metadata doesn't
+/// say what an alias stands for. So only primitive types and the runtime's own types, such as
+/// `T::AccountId` and `T::Balance`, are recognized, and the latter are decoded as the selected
+/// runtime defines them. Aliases that pallets define for themselves, like `BalanceOf<T>` or
+/// `GroupIndex`, are opaque and kept as hex, as is anything else. An opaque part of a vec, option
+/// or tuple can't be told apart from what follows it, so such a type is opaque as a whole.
+///
+/// An opaque value takes up all that is left of its input. So an opaque map key is kept as hex
+/// when it is the last key of the storage key, but an opaque first key of a double map can't be
+/// split off, and those entries only have a `raw_key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Ty {
+	Bool,
+	U8,
+	U16,
+	U32,
+	U64,
+	/// Written as a decimal string, JSON numbers can't hold all of them
+	U128,
+	/// Written in SS58 format
+	AccountId,
+	H256,
+	/// `Vec<u8>`, written as hex
+	Bytes,
+	Vec(Box<Ty>),
+	Option(Box<Ty>),
+	Tuple(Vec<Ty>),
+	Opaque,
+}
+
+/// A type the runtime's own types can be defined as
+trait KnownType {
+	const TY: Ty;
+}
+
+impl KnownType for u32 {
+	const TY: Ty = Ty::U32;
+}
+
+impl KnownType for u64 {
+	const TY: Ty = Ty::U64;
+}
+
+impl KnownType for u128 {
+	const TY: Ty = Ty::U128;
+}
+
+impl KnownType for H256 {
+	const TY: Ty = Ty::H256;
+}
+
+impl KnownType for AccountId32 {
+	const TY: Ty = Ty::AccountId;
+}
+
+/// What a runtime's own types are, so that their names can be recognized
+#[derive(Debug, Clone)]
+struct RuntimeTypes {
+	block_number: Ty,
+	index: Ty,
+	balance: Ty,
+	account_id: Ty,
+	hash: Ty,
+}
+
+impl RuntimeTypes {
+	fn new<BlockNumber, Index, Balance, AccountId, Hash>() -> Self
+	where
+		BlockNumber: KnownType,
+		Index: KnownType,
+		Balance: KnownType,
+		AccountId: KnownType,
+		Hash: KnownType,
+	{
+		Self {
+			block_number: BlockNumber::TY,
+			index: Index::TY,
+			balance: Balance::TY,
+			account_id: AccountId::TY,
+			hash: Hash::TY,
+		}
+	}
+
+	fn of(runtime: Runtime) -> Self {
+		macro_rules! types_of {
+			($runtime:ident) => {
+				Self::new::<
+					$runtime::BlockNumber,
+					$runtime::Index,
+					$runtime::Balance,
+					$runtime::AccountId,
+					$runtime::Hash,
+				>()
+			};
+		}
+
+		match runtime {
+			Runtime::Super => types_of!(super_runtime),
+			Runtime::WeightFee => types_of!(weight_fee_runtime),
+			Runtime::Ocw => types_of!(ocw_runtime),
+			Runtime::Api => types_of!(api_runtime),
+			Runtime::MinimalGrandpa => types_of!(minimal_grandpa_runtime),
+		}
+	}
+}
+
+impl Ty {
+	fn parse(name: &str, types: &RuntimeTypes) -> Self {
+		let name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+		Self::parse_compact(&name, types)
+	}
+
+	fn parse_compact(name: &str, types: &RuntimeTypes) -> Self {
+		let parse = |inner: &str| Self::parse_compact(inner, types);
+		match name {
+			"bool" => Ty::Bool,
+			"u8" => Ty::U8,
+			"u16" => Ty::U16,
+			"u32" => Ty::U32,
+			"u64" => Ty::U64,
+			"u128" => Ty::U128,
+			"H256" => Ty::H256,
+			"T::BlockNumber" | "BlockNumber" => types.block_number.clone(),
+			"T::Index" | "Index" => types.index.clone(),
+			"T::Balance" | "Balance" => types.balance.clone(),
+			"T::AccountId" | "AccountId" => types.account_id.clone(),
+			"T::Hash" | "Hash" => types.hash.clone(),
+			"Vec<u8>" => Ty::Bytes,
+			_ => {
+				let ty = if let Some(inner) = strip(name, "Vec<", ">") {
+					Ty::Vec(Box::new(parse(inner)))
+				} else if let Some(inner) = strip(name, "Option<", ">") {
+					Ty::Option(Box::new(parse(inner)))
+				} else if let Some(inner) = strip(name, "(", ")") {
+					Ty::Tuple(split_top_level(inner).map(parse).collect())
+				} else {
+					Ty::Opaque
+				};
+				if ty.has_opaque_part() {
+					Ty::Opaque
+				} else {
+					ty
+				}
+			}
+		}
+	}
+
+	fn has_opaque_part(&self) -> bool {
+		match self {
+			Ty::Opaque => true,
+			Ty::Vec(inner) | Ty::Option(inner) => inner.has_opaque_part(),
+			Ty::Tuple(types) => types.iter().any(Ty::has_opaque_part),
+			_ => false,
+		}
+	}
+
+	/// Decode a value of this type off the front of `input`. Opaque values take all of it.
+	fn decode(&self, input: &mut &[u8]) -> Option<Value> {
+		Some(match self {
+			Ty::Bool => Value::from(bool::decode(input).ok()?),
+			Ty::U8 => Value::from(u8::decode(input).ok()?),
+			Ty::U16 => Value::from(u16::decode(input).ok()?),
+			Ty::U32 => Value::from(u32::decode(input).ok()?),
+			Ty::U64 => Value::from(u64::decode(input).ok()?),
+			Ty::U128 => Value::from(u128::decode(input).ok()?.to_string()),
+			Ty::AccountId => {
+				Value::from(AccountId32::from(<[u8; 32]>::decode(input).ok()?).to_ss58check())
+			}
+			Ty::H256 => Value::from(to_hex(&<[u8; 32]>::decode(input).ok()?, false)),
+			Ty::Bytes => Value::from(to_hex(&Vec::<u8>::decode(input).ok()?, false)),
+			Ty::Vec(inner) => {
+				let len = <Compact<u32>>::decode(input).ok()?.0;
+				Value::Array(
+					(0..len)
+						.map(|_| inner.decode(input))
+						.collect::<Option<_>>()?,
+				)
+			}
+			Ty::Option(inner) => match u8::decode(input).ok()? {
+				0 => Value::Null,
+				1 => inner.decode(input)?,
+				_ => return None,
+			},
+			Ty::Tuple(types) => Value::Array(
+				types
+					.iter()
+					.map(|ty| ty.decode(input))
+					.collect::<Option<_>>()?,
+			),
+			Ty::Opaque => {
+				let value = Value::from(to_hex(input, false));
+				*input = &[];
+				value
+			}
+		})
+	}
+
+	/// Encode a value written the way `decode` writes it
+	fn encode_to(&self, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+		let invalid = || format!("`{}` is not a valid {:?}", value, self);
+		let number = || value.as_u64().ok_or_else(invalid);
+		match self {
+			Ty::Bool => value.as_bool().ok_or_else(invalid)?.encode_to(out),
+			Ty::U8 => u8::try_from(number()?)
+				.map_err(|_| invalid())?
+				.encode_to(out),
+			Ty::U16 => u16::try_from(number()?)
+				.map_err(|_| invalid())?
+				.encode_to(out),
+			Ty::U32 => u32::try_from(number()?)
+				.map_err(|_| invalid())?
+				.encode_to(out),
+			Ty::U64 => number()?.encode_to(out),
+			Ty::U128 => match value {
+				Value::String(s) => s.parse::<u128>().map_err(|_| invalid())?,
+				_ => u128::from(number()?),
+			}
+			.encode_to(out),
+			Ty::AccountId => AccountId32::from_ss58check(value.as_str().ok_or_else(invalid)?)
+				.map_err(|_| invalid())?
+				.encode_to(out),
+			Ty::H256 => {
+				let bytes = hex(value.as_str().ok_or_else(invalid)?)?;
+				if bytes.len() != 32 {
+					return Err(invalid());
+				}
+				out.extend(bytes);
+			}
+			Ty::Bytes => hex(value.as_str().ok_or_else(invalid)?)?.encode_to(out),
+			Ty::Vec(inner) => {
+				let values = value.as_array().ok_or_else(invalid)?;
+				Compact(values.len() as u32).encode_to(out);
+				for value in values {
+					inner.encode_to(value, out)?;
+				}
+			}
+			Ty::Option(inner) => match value {
+				Value::Null => 0u8.encode_to(out),
+				value => {
+					1u8.encode_to(out);
+					inner.encode_to(value, out)?;
+				}
+			},
+			Ty::Tuple(types) => {
+				let values = value.as_array().ok_or_else(invalid)?;
+				if values.len() != types.len() {
+					return Err(invalid());
+				}
+				for (ty, value) in types.iter().zip(values) {
+					ty.encode_to(value, out)?;
+				}
+			}
+			Ty::Opaque => out.extend(hex(value.as_str().ok_or_else(invalid)?)?),
+		}
+		Ok(())
+	}
+}
+
+/// Decode a whole storage value. Values that don't decode as their type are written as
+/// `{ "raw": "0x..." }`, so they still come back unchanged on import.
+fn decode_value(ty: &Ty, bytes: &[u8]) -> Value {
+	let mut input = bytes;
+	match ty.decode(&mut input) {
+		Some(value) if input.is_empty() => value,
+		_ => serde_json::json!({ "raw": to_hex(bytes, false) }),
+	}
+}
+
+fn encode_value(ty: &Ty, value: &Value) -> Result<Vec<u8>, String> {
+	if let Some(raw) = value.get("raw").and_then(Value::as_str) {
+		return hex(raw);
+	}
+	let mut out = Vec::new();
+	ty.encode_to(value, &mut out)?;
+	Ok(out)
+}
+
+/// Decode a map key off the front of `input`, if the hasher keeps the key after its hash
+fn decode_key(hasher: &StorageHasher, ty: &Ty, input: &mut &[u8]) -> Option<Value> {
+	let hash_len = match hasher {
+		StorageHasher::Blake2_128Concat => 16,
+		StorageHasher::Twox64Concat => 8,
+		StorageHasher::Identity => 0,
+		_ => return None,
+	};
+	if input.len() < hash_len {
+		return None;
+	}
+	*input = &input[hash_len..];
+	ty.decode(input)
+}
+
+fn hasher_name(hasher: &StorageHasher) -> &'static str {
+	match hasher {
+		StorageHasher::Blake2_128 => "Blake2_128",
+		StorageHasher::Blake2_256 => "Blake2_256",
+		StorageHasher::Blake2_128Concat => "Blake2_128Concat",
+		StorageHasher::Twox128 => "Twox128",
+		StorageHasher::Twox256 => "Twox256",
+		StorageHasher::Twox64Concat => "Twox64Concat",
+		StorageHasher::Identity => "Identity",
+	}
+}
+
+/// Hash an encoded map key with the named hasher
+fn hash_key(hasher: &str, key: &[u8]) -> Result<Vec<u8>, String> {
+	Ok(match hasher {
+		"Blake2_128" => blake2_128(key).to_vec(),
+		"Blake2_256" => blake2_256(key).to_vec(),
+		"Blake2_128Concat" => [&blake2_128(key)[..], key].concat(),
+		"Twox128" => twox_128(key).to_vec(),
+		"Twox256" => twox_256(key).to_vec(),
+		"Twox64Concat" => [&twox_64(key)[..], key].concat(),
+		"Identity" => key.to_vec(),
+		other => return Err(format!("Unknown hasher `{}`", other)),
+	})
+}
+
+/// The key of a storage value, or the prefix of the keys of a map
+fn item_prefix(prefix: &str, name: &str) -> Vec<u8> {
+	[twox_128(prefix.as_bytes()), twox_128(name.as_bytes())].concat()
+}
+
+/// Remove all pairs whose key starts with `prefix` from `pairs`, and return them
+fn take_prefixed(pairs: &mut BTreeMap<Vec<u8>, Vec<u8>>, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+	let keys: Vec<_> = pairs
+		.range(prefix.to_vec()..)
+		.map(|(key, _)| key)
+		.take_while(|key| key.starts_with(prefix))
+		.cloned()
+		.collect();
+	keys.into_iter()
+		.filter_map(|key| pairs.remove(&key).map(|value| (key, value)))
+		.collect()
+}
+
+/// The modules described by encoded runtime metadata
+fn decode_modules(metadata: &[u8]) -> Result<Vec<ModuleMetadata>, String> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+		.map_err(|e| format!("Could not decode the metadata: {}", e))?;
+	match metadata.1 {
+		RuntimeMetadata::V12(metadata) => decoded(metadata.modules),
+		_ => Err("Only version 12 of the runtime metadata is supported".into()),
+	}
+}
+
+/// Unwrap a piece of metadata. Metadata that was decoded, rather than built in the runtime, is
+/// always in the `Decoded` variant.
+fn decoded<B: 'static, O: 'static>(value: DecodeDifferent<B, O>) -> Result<O, String> {
+	match value {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err("Metadata was not decoded".into()),
+	}
+}
+
+fn strip<'a>(name: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+	name.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+/// Split a list of types on the commas that aren't nested in another type
+fn split_top_level(list: &str) -> impl Iterator<Item = &str> {
+	let mut depth = 0;
+	let mut start = 0;
+	let mut parts = Vec::new();
+	for (i, c) in list.char_indices() {
+		match c {
+			'<' | '(' | '[' => depth += 1,
+			'>' | ')' | ']' => depth -= 1,
+			',' if depth == 0 => {
+				parts.push(&list[start..i]);
+				start = i + 1;
+			}
+			_ => {}
+		}
+	}
+	parts.push(&list[start..]);
+	parts.into_iter().filter(|part| !part.is_empty())
+}
+
+fn hex(value: &str) -> Result<Vec<u8>, String> {
+	from_hex(value).map_err(|e| format!("Invalid hex `{}`: {}", value, e))
+}
+
+fn write(output: &Option<PathBuf>, json: String) -> Result<(), String> {
+	match output {
+		Some(path) => fs::write(path, json)
+			.map_err(|e| format!("Could not write `{}`: {}", path.display(), e)),
+		None => {
+			println!("{}", json);
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	const CROWDFUND_TRIE: &[u8] = b"crowdfund";
+
+	fn account(id: u8) -> AccountId32 {
+		AccountId32::from([id; 32])
+	}
+
+	fn types() -> RuntimeTypes {
+		RuntimeTypes::of(Runtime::Super)
+	}
+
+	fn ss58(id: u8) -> Value {
+		Value::from(account(id).to_ss58check())
+	}
+
+	/// The super runtime's metadata, decoded the way the export gets it from a node
+	fn modules() -> Vec<ModuleMetadata> {
+		decode_modules(&super_runtime::Runtime::metadata().encode()).unwrap()
+	}
+
+	fn block() -> SnapshotBlock {
+		SnapshotBlock {
+			number: 7,
+			hash: format!("{:?}", H256::default()),
+		}
+	}
+
+	/// The key of an entry of a map whose keys are hashed with `Blake2_128Concat`
+	fn map_key(prefix: &str, name: &str, keys: &[&[u8]]) -> Vec<u8> {
+		let mut storage_key = item_prefix(prefix, name);
+		for key in keys {
+			storage_key.extend(blake2_128(key).iter().chain(key.iter()));
+		}
+		storage_key
+	}
+
+	fn member_score_key() -> Vec<u8> {
+		map_key(
+			"DoubleMap",
+			"MemberScore",
+			&[&3u32.encode(), &account(1).encode()],
+		)
+	}
+
+	fn child_root_key() -> Vec<u8> {
+		[
+			well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX,
+			CROWDFUND_TRIE,
+		]
+		.concat()
+	}
+
+	/// Storage with plain values, maps, double maps and a child trie
+	fn storage() -> Storage {
+		let mut storage = Storage::default();
+		let top = &mut storage.top;
+		top.insert(item_prefix("System", "Number"), 7u32.encode());
+		top.insert(
+			item_prefix("Balances", "TotalIssuance"),
+			(1u128 << 100).encode(),
+		);
+		top.insert(item_prefix("Sudo", "Key"), account(1).encode());
+		// The value type of accounts is opaque
+		top.insert(
+			map_key("System", "Account", &[&account(1).encode()]),
+			vec![1, 2, 3],
+		);
+		top.insert(
+			map_key("SimpleMap", "SimpleMap", &[&account(1).encode()]),
+			5u32.encode(),
+		);
+		top.insert(
+			map_key("SimpleMap", "SimpleMap", &[&account(2).encode()]),
+			6u32.encode(),
+		);
+		top.insert(
+			map_key(
+				"BasicToken",
+				"Allowances",
+				&[&account(1).encode(), &account(2).encode()],
+			),
+			100u64.encode(),
+		);
+		// The first key is a `GroupIndex`, which is opaque
+		top.insert(member_score_key(), 9u32.encode());
+		top.insert(well_known_keys::CODE.to_vec(), vec![0, 1, 2]);
+		top.insert(child_root_key(), vec![0xff; 32]);
+
+		storage.children_default.insert(
+			CROWDFUND_TRIE.to_vec(),
+			StorageChild {
+				data: vec![(b"a".to_vec(), b"1".to_vec())].into_iter().collect(),
+				child_info: ChildInfo::new_default(CROWDFUND_TRIE),
+			},
+		);
+		storage
+	}
+
+	#[test]
+	fn export_then_import_restores_the_storage() {
+		let snapshot = Snapshot::from_storage(block(), modules(), storage(), &types()).unwrap();
+		// Through JSON, as with a snapshot file
+		let json = serde_json::to_string(&snapshot).unwrap();
+		let snapshot: Snapshot = serde_json::from_str(&json).unwrap();
+		let imported = snapshot
+			.into_genesis(&types())
+			.unwrap()
+			.build_storage()
+			.unwrap();
+
+		// The child trie's root is left for the new chain to compute
+		let mut expected = storage();
+		expected.top.remove(&child_root_key());
+		assert_eq!(imported.top, expected.top);
+		assert_eq!(imported.children_default, expected.children_default);
+	}
+
+	#[test]
+	fn export_decodes_known_types() {
+		let snapshot = Snapshot::from_storage(block(), modules(), storage(), &types()).unwrap();
+		let snapshot = serde_json::to_value(&snapshot).unwrap();
+		let pallets = &snapshot["pallets"];
+
+		assert_eq!(pallets["System"]["Number"]["value"], json!(7));
+		assert_eq!(
+			pallets["Balances"]["TotalIssuance"]["value"],
+			json!((1u128 << 100).to_string())
+		);
+		assert_eq!(pallets["Sudo"]["Key"]["value"], ss58(1));
+
+		let entries = |pallet: &str, item: &str| pallets[pallet][item]["entries"].clone();
+		assert_eq!(
+			entries("System", "Account"),
+			json!([{ "key": ss58(1), "value": "0x010203" }])
+		);
+		let simple_map = entries("SimpleMap", "SimpleMap");
+		let simple_map = simple_map.as_array().unwrap();
+		assert_eq!(simple_map.len(), 2);
+		assert!(simple_map.contains(&json!({ "key": ss58(1), "value": 5 })));
+		assert!(simple_map.contains(&json!({ "key": ss58(2), "value": 6 })));
+		assert_eq!(
+			entries("BasicToken", "Allowances"),
+			json!([{ "key": ss58(1), "key2": ss58(2), "value": 100 }])
+		);
+		assert_eq!(
+			entries("DoubleMap", "MemberScore"),
+			json!([{ "rawKey": to_hex(&member_score_key(), false), "value": 9 }])
+		);
+
+		let code = to_hex(well_known_keys::CODE, false);
+		assert_eq!(snapshot["other"], json!({ code: "0x000102" }));
+		let crowdfund = to_hex(CROWDFUND_TRIE, false);
+		assert_eq!(
+			snapshot["childTries"],
+			json!({ crowdfund: { "0x61": "0x31" } })
+		);
+	}
+
+	#[test]
+	fn only_the_runtimes_types_are_recognized() {
+		let types = types();
+		assert_eq!(Ty::parse("T::BlockNumber", &types), Ty::U32);
+		assert_eq!(
+			Ty::parse("Vec<(T::AccountId, T::Balance)>", &types),
+			Ty::Vec(Box::new(Ty::Tuple(vec![Ty::AccountId, Ty::U128])))
+		);
+		assert_eq!(Ty::parse("GroupIndex", &types), Ty::Opaque);
+		assert_eq!(Ty::parse("BalanceOf<T>", &types), Ty::Opaque);
+		assert_eq!(Ty::parse("Option<FundIndex>", &types), Ty::Opaque);
+		assert_eq!(
+			Ty::parse("Vec<(T::AccountId, GroupIndex)>", &types),
+			Ty::Opaque
+		);
+	}
+
+	#[test]
+	fn runtime_types_follow_the_runtime() {
+		let types = RuntimeTypes {
+			balance: Ty::U64,
+			..types()
+		};
+		assert_eq!(Ty::parse("T::Balance", &types), Ty::U64);
+		assert_eq!(
+			decode_value(&Ty::parse("T::Balance", &types), &5u64.encode()),
+			json!(5)
+		);
+	}
+
+	#[test]
+	fn values_with_opaque_parts_stay_hex() {
+		let ty = Ty::parse("Vec<GroupIndex>", &types());
+		let bytes = vec![1u32, 2u32].encode();
+		let value = decode_value(&ty, &bytes);
+
+		assert_eq!(value, json!(to_hex(&bytes, false)));
+		assert_eq!(encode_value(&ty, &value), Ok(bytes));
+	}
+
+	#[test]
+	fn out_of_range_numbers_are_rejected() {
+		assert_eq!(encode_value(&Ty::U8, &json!(255)), Ok(vec![255]));
+		assert!(encode_value(&Ty::U8, &json!(256)).is_err());
+		assert!(encode_value(&Ty::U16, &json!(1 << 16)).is_err());
+		assert!(encode_value(&Ty::U32, &json!(1u64 << 32)).is_err());
+		assert!(encode_value(&Ty::U64, &json!(-1)).is_err());
+	}
+}
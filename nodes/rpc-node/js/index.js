@@ -29,6 +29,12 @@ const rpc = {
       description: "Gets the sum of the two storage values in sum-storage pallet via a runtime api.",
       params: [],
       type: "u32",
+    },
+    subscribeSum: {
+      description: "Pushes the sum of the two storage values whenever one of them changes.",
+      params: [],
+      pubsub: ["sum", "subscribeSum", "unsubscribeSum"],
+      type: "u32",
     }
   }
 }
@@ -55,7 +61,15 @@ async function main() {
 
   // Query the custom RPC that uses the runtimeAPI
   let directSum = ( await api.rpc.sumStorage.getSum() ).toNumber();
-  console.log(`The sum queried directly from the RPC is ${directSum}`);
+  console.log(`The sum queried directly from the RPC is ${directSum}\n`);
+
+  // Subscribe to the sum. The first notification carries the current sum, later ones only come
+  // when a thing changes. We stop after the first one so the script can finish.
+  let firstSum;
+  const pushed = new Promise((resolve) => { firstSum = resolve; });
+  const unsubscribe = await api.rpc.sumStorage.subscribeSum(firstSum);
+  console.log(`The sum pushed by the subscription is ${await pushed}`);
+  unsubscribe();
 }
 
 main().catch(console.error).finally(() => process.exit());
@@ -8,6 +8,10 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// The most blocks a single `sumStorage_getSumRange` call may visit.
+	#[structopt(long = "sum-range-limit", default_value = "1000")]
+	pub sum_range_limit: u64,
 }
 
 #[derive(Debug, StructOpt)]
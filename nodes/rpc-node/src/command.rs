@@ -117,10 +117,11 @@ pub fn run() -> sc_cli::Result<()> {
 		}
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let sum_range_limit = cli.sum_range_limit;
 			runner.run_node_until_exit(|config| async move {
 				match config.role {
 					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
+					_ => service::new_full(config, sum_range_limit),
 				}
 				.map_err(sc_cli::Error::Service)
 			})
@@ -2,16 +2,21 @@
 
 use std::sync::Arc;
 
+use frame_support::storage::StorageValue;
 use futures::channel::mpsc::Sender;
-use runtime::{opaque::Block, Hash};
+use jsonrpc_pubsub::manager::SubscriptionManager;
+use runtime::{opaque::Block, Hash, Runtime};
+use sc_client_api::{backend::Backend, BlockchainEvents, StorageProvider};
 use sc_consensus_manual_seal::{
 	rpc::{ManualSeal, ManualSealApi},
 	EngineCommand,
 };
+pub use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::storage::StorageKey;
 use sp_transaction_pool::TransactionPool;
 
 /// Full client dependencies.
@@ -24,13 +29,19 @@ pub struct FullDeps<C, P> {
 	pub deny_unsafe: DenyUnsafe,
 	/// A command stream to send authoring commands to manual seal consensus engine
 	pub command_sink: Sender<EngineCommand<Hash>>,
+	/// Executor to drive the subscriptions
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// The most blocks a single sum range query may visit
+	pub sum_range_limit: u64,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+pub fn create_full<C, P, BE>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
 where
+	BE: Backend<Block> + 'static,
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, BE>,
 	C: Send + Sync + 'static,
 	C::Api: BlockBuilder<Block>,
 	C::Api: sum_storage_runtime_api::SumStorageApi<Block>,
@@ -40,6 +51,8 @@ where
 	let FullDeps {
 		command_sink,
 		client,
+		subscription_executor,
+		sum_range_limit,
 		..
 	} = deps;
 
@@ -51,7 +64,21 @@ where
 	// Add a second RPC extension
 	// Because this one calls a Runtime API it needs a reference to the client.
 	io.extend_with(sum_storage_rpc::SumStorageApi::to_delegate(
-		sum_storage_rpc::SumStorage::new(client),
+		sum_storage_rpc::SumStorage::new(client.clone()).with_max_range_points(sum_range_limit),
+	));
+
+	// The subscription flavour of the same RPC pushes the sum whenever one of the things changes.
+	// It needs to know where the things live in storage to notice.
+	let thing_keys = vec![
+		StorageKey(sum_storage::Thing1::<Runtime>::hashed_key().to_vec()),
+		StorageKey(sum_storage::Thing2::<Runtime>::hashed_key().to_vec()),
+	];
+	io.extend_with(sum_storage_rpc::SumStorageSubscriptionApi::to_delegate(
+		sum_storage_rpc::SumStorageSubscriptions::new(
+			client,
+			SubscriptionManager::new(Arc::new(subscription_executor)),
+			thing_keys,
+		),
 	));
 
 	// The final RPC extension receives commands for the manual seal consensus engine.
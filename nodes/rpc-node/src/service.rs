@@ -78,7 +78,9 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// `sum_range_limit` caps the number of blocks one `sumStorage_getSumRange` call may visit.
+pub fn new_full(config: Configuration, sum_range_limit: u64) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -121,12 +123,14 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
 				pool: pool.clone(),
 				deny_unsafe,
 				command_sink: command_sink.clone(),
+				subscription_executor,
+				sum_range_limit,
 			};
 
 			crate::rpc::create_full(deps)
@@ -0,0 +1,20 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime amalgamator file (the `runtime/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+	/// Reports the assets an account holds in the basic token pallet.
+	pub trait BasicTokenApi<AccountId, AssetId> where
+		AccountId: Codec,
+		AssetId: Codec,
+	{
+		/// Every asset the account holds a balance of, and the balance, ordered by asset id. The
+		/// single token created by `init` is not included.
+		fn holdings(who: AccountId) -> Vec<(AssetId, u64)>;
+	}
+}
@@ -0,0 +1,134 @@
+//! Benchmarks for the basic token pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_std::{prelude::*, vec};
+
+/// Give `who` enough funds to reserve a few asset deposits
+fn fund_account<T: Config>(who: &T::AccountId) {
+	let amount = (T::Currency::minimum_balance() + T::AssetDeposit::get()) * 10u32.into();
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+/// A name or symbol of the longest length allowed
+fn longest_string<T: Config>() -> Vec<u8> {
+	vec![b'x'; T::StringLimit::get() as usize]
+}
+
+benchmarks! {
+	init {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let origin = T::InitOrigin::successful_origin();
+		// Chains usually issue the supply at genesis, which closes `init`
+		Init::<T>::kill();
+	}: {
+		Pallet::<T>::init(origin, owner.clone())?;
+	}
+	verify {
+		assert_eq!(Pallet::<T>::get_balance(&owner), INITIAL_SUPPLY);
+	}
+
+	transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		Pallet::<T>::deposit(&caller, 1_000);
+	}: _(RawOrigin::Signed(caller), recipient.clone(), 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::get_balance(&recipient), 1_000);
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, 0);
+	}: _(RawOrigin::Signed(caller.clone()), spender.clone(), 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::allowance(&caller, &spender), 1_000);
+	}
+
+	increase_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, 0);
+		Pallet::<T>::approve(RawOrigin::Signed(caller.clone()).into(), spender.clone(), 1_000)?;
+	}: _(RawOrigin::Signed(caller.clone()), spender.clone(), 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::allowance(&caller, &spender), 2_000);
+	}
+
+	decrease_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, 0);
+		Pallet::<T>::approve(RawOrigin::Signed(caller.clone()).into(), spender.clone(), 1_000)?;
+	}: _(RawOrigin::Signed(caller.clone()), spender.clone(), 400)
+	verify {
+		assert_eq!(Pallet::<T>::allowance(&caller, &spender), 600);
+	}
+
+	transfer_from {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		Pallet::<T>::deposit(&owner, 1_000);
+		Pallet::<T>::approve(RawOrigin::Signed(owner.clone()).into(), caller.clone(), 1_000)?;
+	}: _(RawOrigin::Signed(caller.clone()), owner.clone(), recipient.clone(), 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::get_balance(&recipient), 1_000);
+		assert_eq!(Pallet::<T>::allowance(&owner, &caller), 0);
+	}
+
+	mint {
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let origin = T::AdminOrigin::successful_origin();
+		let supply = Pallet::<T>::total_supply();
+	}: {
+		Pallet::<T>::mint(origin, recipient.clone(), 1_000)?;
+	}
+	verify {
+		assert_eq!(Pallet::<T>::get_balance(&recipient), 1_000);
+		assert_eq!(Pallet::<T>::total_supply(), supply + 1_000);
+	}
+
+	burn {
+		let holder: T::AccountId = account("holder", 0, 0);
+		let origin = T::AdminOrigin::successful_origin();
+		Pallet::<T>::deposit(&holder, 1_000);
+		let supply = Pallet::<T>::total_supply();
+	}: {
+		Pallet::<T>::burn(origin, holder.clone(), 1_000)?;
+	}
+	verify {
+		assert_eq!(Pallet::<T>::get_balance(&holder), 0);
+		assert_eq!(Pallet::<T>::total_supply(), supply - 1_000);
+	}
+
+	create_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), longest_string::<T>(), longest_string::<T>(), 12, 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::asset_balance(&caller, 0), 1_000);
+	}
+
+	transfer_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		fund_account::<T>(&caller);
+		Pallet::<T>::create_asset(
+			RawOrigin::Signed(caller.clone()).into(),
+			longest_string::<T>(),
+			longest_string::<T>(),
+			12,
+			2_000,
+		)?;
+	}: _(RawOrigin::Signed(caller), 0, recipient.clone(), 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::asset_balance(&recipient, 0), 1_000);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
@@ -2,34 +2,123 @@
 #![allow(clippy::unused_unit)]
 
 //! Simple Token Transfer
-//! 1. set total supply
+//! 1. set total supply and initial balances in the chain spec, or issue the supply with `init`
 //! 2. establish ownership upon configuration of circulating tokens
 //! 3. coordinate token transfers with the runtime functions
+//! 4. let owners approve others to spend on their behalf, ERC-20 style
+//! 5. let an admin origin mint and burn tokens
+//!
+//! Alongside the single token, anyone may create further assets by reserving a deposit. Each asset
+//! has its own metadata, supply and balances, and is identified by an `AssetId`.
 
 pub use pallet::*;
 
+use frame_support::{traits::Currency, RuntimeDebug};
+use parity_scale_codec::{Decode, Encode};
+
+/// The supply `init` issues, for chains whose genesis issued none.
+pub const INITIAL_SUPPLY: u64 = 21_000_000;
+
+/// The versions of this pallet's storage layout
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	/// `TotalSupply` defaulted to the initial supply, even before `init` issued it
+	V1,
+	/// `TotalSupply` is the sum of all balances
+	V2,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
+
+/// Simple index for identifying an asset.
+pub type AssetId = u32;
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+type AssetInfoOf<T> = AssetInfo<AccountIdOf<T>, BalanceOf<T>>;
+
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migration;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+	use crate::weights::WeightInfo;
+	use crate::{AssetId, AssetInfoOf, BalanceOf, Releases, INITIAL_SUPPLY};
+	use frame_support::{
+		dispatch::{DispatchResult, DispatchResultWithPostInfo},
+		pallet_prelude::*,
+		traits::ReservableCurrency,
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin that may mint and burn tokens.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin that may issue the initial supply with `init`, if genesis issued none.
+		type InitOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The currency in which asset deposits are reserved
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount held on deposit by the creator of an asset
+		type AssetDeposit: Get<BalanceOf<Self>>;
+
+		/// The longest name or symbol an asset may have, in bytes
+		type StringLimit: Get<u32>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
 	#[pallet::metadata(T::AccountId = "AccountId")]
 	#[pallet::generate_deposit(pub (super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Token was initialized by user
+		/// Token was initialized, issuing the initial supply to the account
 		Initialized(T::AccountId),
 		/// Tokens successfully transferred between users
 		Transfer(T::AccountId, T::AccountId, u64), // (from, to, value)
+		/// An owner set how much a spender may transfer on their behalf
+		Approval(T::AccountId, T::AccountId, u64), // (owner, spender, allowance)
+		/// New tokens were created
+		Minted(T::AccountId, u64), // (to, value)
+		/// Tokens were destroyed
+		Burned(T::AccountId, u64), // (from, value)
+		/// A new asset was created, and its whole supply given to its creator
+		AssetCreated(AssetId, T::AccountId, u64), // (asset, creator, supply)
+		/// An asset was successfully transferred between users
+		AssetTransfer(AssetId, T::AccountId, T::AccountId, u64), // (asset, from, to, value)
+	}
+
+	#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+	#[cfg_attr(feature = "std", derive(Debug))]
+	pub struct AssetInfo<AccountId, Balance> {
+		/// The account that created the asset and placed its deposit
+		pub owner: AccountId,
+		/// The amount of deposit placed
+		pub deposit: Balance,
+		/// The asset's name, such as "Recipe Dollar"
+		pub name: Vec<u8>,
+		/// The asset's ticker symbol, such as "RCD"
+		pub symbol: Vec<u8>,
+		/// How many decimal places front ends should show. Balances are always whole numbers.
+		pub decimals: u8,
+		/// The total amount of the asset held across all accounts
+		pub supply: u64,
 	}
 
 	#[pallet::storage]
@@ -37,26 +126,109 @@ pub mod pallet {
 	pub(super) type Balances<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
 
-	#[pallet::type_value]
-	pub(super) fn TotalSupplyDefaultValue<T: Config>() -> u64 {
-		21000000
-	}
+	/// How many of the owner's tokens each spender may still transfer, by owner and then spender
+	#[pallet::storage]
+	#[pallet::getter(fn allowance)]
+	pub(super) type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		u64,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset)]
+	pub(super) type Assets<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetId, AssetInfoOf<T>, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn asset_count)]
+	pub(super) type AssetCount<T: Config> = StorageValue<_, AssetId, ValueQuery>;
+
+	/// Each account's balance of each asset, by account and then asset, so that an account's
+	/// holdings can be listed. Empty balances are removed.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_balance)]
+	pub(super) type AssetBalances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AssetId,
+		u64,
+		ValueQuery,
+	>;
+
+	/// The sum of all balances
 	#[pallet::storage]
 	#[pallet::getter(fn total_supply)]
-	pub(super) type TotalSupply<T: Config> =
-		StorageValue<_, u64, ValueQuery, TotalSupplyDefaultValue<T>>;
+	pub(super) type TotalSupply<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// Whether the initial supply has been issued, either at genesis or by `init`
 	#[pallet::storage]
 	#[pallet::getter(fn is_init)]
 	pub(super) type Init<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+	/// The layout of this pallet's storage, so that upgrades know which migrations to run
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The balance each account starts with
+		pub balances: Vec<(T::AccountId, u64)>,
+		/// The total supply. Must equal the sum of `balances`.
+		pub total_supply: u64,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				balances: Vec::new(),
+				total_supply: 0,
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let sum = self
+				.balances
+				.iter()
+				.try_fold(0u64, |sum, (_, balance)| sum.checked_add(*balance))
+				.expect("Genesis balances must fit in u64");
+			assert_eq!(
+				sum, self.total_supply,
+				"Genesis balances must add up to the total supply"
+			);
+
+			for (who, balance) in &self.balances {
+				<Balances<T>>::insert(who, balance);
+			}
+			<TotalSupply<T>>::put(self.total_supply);
+			<StorageVersion<T>>::put(Releases::V2);
+			// A chain that issues its supply at genesis must not let anyone issue another
+			if self.total_supply > 0 {
+				Init::<T>::put(true);
+			}
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migration::migrate_total_supply::<T>()
+		}
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -64,48 +236,270 @@ pub mod pallet {
 		AlreadyInitialized,
 		/// Attempted to transfer more funds than were available
 		InsufficientFunds,
+		/// Attempted to spend more of someone else's funds than they approved
+		InsufficientAllowance,
+		/// Attempted to raise an allowance beyond `u64::MAX`
+		AllowanceOverflow,
+		/// Attempted to mint more tokens than the total supply can hold
+		SupplyOverflow,
+		/// The asset id specified does not exist
+		UnknownAsset,
+		/// An asset's name or symbol is longer than `StringLimit`
+		BadMetadata,
+		/// All asset ids have been used
+		NoAvailableAssetId,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Initialize the token
-		/// transfers the total_supply amout to the caller
-		#[pallet::weight(10_000)]
-		pub fn init(_origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let sender = ensure_signed(_origin)?;
+		/// issues the `INITIAL_SUPPLY` to `owner`, unless the supply was issued already
+		#[pallet::weight(T::WeightInfo::init())]
+		pub fn init(origin: OriginFor<T>, owner: T::AccountId) -> DispatchResultWithPostInfo {
+			T::InitOrigin::ensure_origin(origin)?;
 			ensure!(!Self::is_init(), <Error<T>>::AlreadyInitialized);
+			Self::total_supply()
+				.checked_add(INITIAL_SUPPLY)
+				.ok_or(<Error<T>>::SupplyOverflow)?;
 
-			<Balances<T>>::insert(sender, Self::total_supply());
+			Self::deposit(&owner, INITIAL_SUPPLY);
 
 			Init::<T>::put(true);
+			Self::deposit_event(Event::Initialized(owner));
 			Ok(().into())
 		}
 
 		/// Transfer tokens from one account to another
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
 			_origin: OriginFor<T>,
 			to: T::AccountId,
 			value: u64,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(_origin)?;
-			let sender_balance = Self::get_balance(&sender);
-			let receiver_balance = Self::get_balance(&to);
+			Self::transfer_funds(&sender, &to, value)?;
+
+			Self::deposit_event(Event::Transfer(sender, to, value));
+			Ok(().into())
+		}
+
+		/// Allow `spender` to transfer up to `value` of the caller's tokens, replacing any previous
+		/// allowance
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			spender: T::AccountId,
+			value: u64,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::set_allowance(owner, spender, value);
+			Ok(().into())
+		}
+
+		/// Raise `spender`'s allowance by `added_value`
+		#[pallet::weight(T::WeightInfo::increase_allowance())]
+		pub fn increase_allowance(
+			origin: OriginFor<T>,
+			spender: T::AccountId,
+			added_value: u64,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let allowance = Self::allowance(&owner, &spender)
+				.checked_add(added_value)
+				.ok_or(<Error<T>>::AllowanceOverflow)?;
+			Self::set_allowance(owner, spender, allowance);
+			Ok(().into())
+		}
+
+		/// Lower `spender`'s allowance by `subtracted_value`
+		#[pallet::weight(T::WeightInfo::decrease_allowance())]
+		pub fn decrease_allowance(
+			origin: OriginFor<T>,
+			spender: T::AccountId,
+			subtracted_value: u64,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let allowance = Self::allowance(&owner, &spender)
+				.checked_sub(subtracted_value)
+				.ok_or(<Error<T>>::InsufficientAllowance)?;
+			Self::set_allowance(owner, spender, allowance);
+			Ok(().into())
+		}
 
-			// Calculate new balances
-			let updated_from_balance = sender_balance
+		/// Transfer `value` of `owner`'s tokens to `to`, spending the caller's allowance
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			to: T::AccountId,
+			value: u64,
+		) -> DispatchResultWithPostInfo {
+			let spender = ensure_signed(origin)?;
+			let allowance = Self::allowance(&owner, &spender)
+				.checked_sub(value)
+				.ok_or(<Error<T>>::InsufficientAllowance)?;
+
+			Self::transfer_funds(&owner, &to, value)?;
+			<Allowances<T>>::insert(&owner, &spender, allowance);
+
+			Self::deposit_event(Event::Transfer(owner, to, value));
+			Ok(().into())
+		}
+
+		/// Create `value` new tokens in `to`'s account
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			value: u64,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::total_supply()
+				.checked_add(value)
+				.ok_or(<Error<T>>::SupplyOverflow)?;
+
+			Self::deposit(&to, value);
+
+			Self::deposit_event(Event::Minted(to, value));
+			Ok(().into())
+		}
+
+		/// Destroy `value` of `from`'s tokens
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			value: u64,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Self::withdraw(&from, value)?;
+
+			Self::deposit_event(Event::Burned(from, value));
+			Ok(().into())
+		}
+
+		/// Create a new asset with the given metadata, reserving `AssetDeposit` from the caller. The
+		/// caller receives the asset's whole `supply`.
+		#[pallet::weight(T::WeightInfo::create_asset())]
+		pub fn create_asset(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			supply: u64,
+		) -> DispatchResultWithPostInfo {
+			let creator = ensure_signed(origin)?;
+			let limit = T::StringLimit::get() as usize;
+			ensure!(
+				name.len() <= limit && symbol.len() <= limit,
+				<Error<T>>::BadMetadata
+			);
+
+			let id = Self::asset_count();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::NoAvailableAssetId)?;
+
+			let deposit = T::AssetDeposit::get();
+			T::Currency::reserve(&creator, deposit)?;
+
+			AssetCount::<T>::put(next_id);
+			<Assets<T>>::insert(
+				id,
+				AssetInfo {
+					owner: creator.clone(),
+					deposit,
+					name,
+					symbol,
+					decimals,
+					supply,
+				},
+			);
+			if supply > 0 {
+				<AssetBalances<T>>::insert(&creator, id, supply);
+			}
+
+			Self::deposit_event(Event::AssetCreated(id, creator, supply));
+			Ok(().into())
+		}
+
+		/// Transfer some of an asset from one account to another
+		#[pallet::weight(T::WeightInfo::transfer_asset())]
+		pub fn transfer_asset(
+			origin: OriginFor<T>,
+			id: AssetId,
+			to: T::AccountId,
+			value: u64,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(<Assets<T>>::contains_key(id), <Error<T>>::UnknownAsset);
+
+			let updated_from_balance = Self::asset_balance(&sender, id)
+				.checked_sub(value)
+				.ok_or(<Error<T>>::InsufficientFunds)?;
+			Self::set_asset_balance(&sender, id, updated_from_balance);
+			// Read the receiver's balance after writing the sender's, in case they are the same
+			let updated_to_balance = Self::asset_balance(&to, id)
+				.checked_add(value)
+				.expect("Entire supply of an asset fits in u64; qed");
+			Self::set_asset_balance(&to, id, updated_to_balance);
+
+			Self::deposit_event(Event::AssetTransfer(id, sender, to, value));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn transfer_funds(from: &T::AccountId, to: &T::AccountId, value: u64) -> DispatchResult {
+			let updated_from_balance = Self::get_balance(from)
 				.checked_sub(value)
 				.ok_or(<Error<T>>::InsufficientFunds)?;
-			let updated_to_balance = receiver_balance
+			<Balances<T>>::insert(from, updated_from_balance);
+			// Read the receiver's balance after writing the sender's, in case they are the same
+			let updated_to_balance = Self::get_balance(to)
 				.checked_add(value)
 				.expect("Entire supply fits in u64; qed");
+			<Balances<T>>::insert(to, updated_to_balance);
+			Ok(())
+		}
 
-			// Write new balances to storage
-			<Balances<T>>::insert(&sender, updated_from_balance);
-			<Balances<T>>::insert(&to, updated_to_balance);
+		fn set_allowance(owner: T::AccountId, spender: T::AccountId, value: u64) {
+			<Allowances<T>>::insert(&owner, &spender, value);
+			Self::deposit_event(Event::Approval(owner, spender, value));
+		}
 
-			Self::deposit_event(Event::Transfer(sender, to, value));
-			Ok(().into())
+		fn set_asset_balance(who: &T::AccountId, id: AssetId, balance: u64) {
+			if balance == 0 {
+				<AssetBalances<T>>::remove(who, id);
+			} else {
+				<AssetBalances<T>>::insert(who, id, balance);
+			}
+		}
+
+		/// Every asset `who` holds a balance of, and the balance, ordered by asset id.
+		pub fn holdings(who: &T::AccountId) -> Vec<(AssetId, u64)> {
+			let mut holdings: Vec<_> = <AssetBalances<T>>::iter_prefix(who).collect();
+			holdings.sort_unstable_by_key(|(id, _)| *id);
+			holdings
+		}
+
+		/// Remove tokens from an account, taking them out of the total supply.
+		///
+		/// Other pallets use this to charge in tokens, for example to pay transaction fees.
+		pub fn withdraw(who: &T::AccountId, value: u64) -> DispatchResult {
+			<Balances<T>>::try_mutate(who, |balance| -> DispatchResult {
+				*balance = balance
+					.checked_sub(value)
+					.ok_or(<Error<T>>::InsufficientFunds)?;
+				Ok(())
+			})?;
+			<TotalSupply<T>>::mutate(|supply| *supply = supply.saturating_sub(value));
+			Ok(())
+		}
+
+		/// Add tokens to an account, adding them to the total supply.
+		pub fn deposit(who: &T::AccountId, value: u64) {
+			<Balances<T>>::mutate(who, |balance| *balance = balance.saturating_add(value));
+			<TotalSupply<T>>::mutate(|supply| *supply = supply.saturating_add(value));
 		}
 	}
 }
@@ -0,0 +1,28 @@
+//! Storage migrations for the basic token pallet
+
+use super::*;
+use frame_support::{traits::Get, weights::Weight};
+
+/// Brings the storage of chains that started before the pallet had a genesis configuration up to
+/// date.
+///
+/// `TotalSupply` used to default to the initial supply, so on chains where `init` was never called
+/// it counted 21,000,000 tokens that nobody held. It becomes the sum of all balances, which is what
+/// it counts from now on. `Init` is carried forward unchanged: chains that issued the supply keep
+/// it, and chains that didn't can still issue it through `InitOrigin`.
+pub fn migrate_total_supply<T: Config>() -> Weight {
+	let db = T::DbWeight::get();
+	if StorageVersion::<T>::get() != Releases::V1 {
+		return db.reads(1);
+	}
+
+	let mut accounts: Weight = 0;
+	let supply = Balances::<T>::iter_values().fold(0u64, |supply, balance| {
+		accounts += 1;
+		supply.saturating_add(balance)
+	});
+	TotalSupply::<T>::put(supply);
+	StorageVersion::<T>::put(Releases::V2);
+
+	db.reads_writes(accounts.saturating_add(1), 2)
+}
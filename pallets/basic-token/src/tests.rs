@@ -1,11 +1,18 @@
-use crate::{self as basic_token, Config, Error};
-use frame_support::{assert_noop, assert_ok, construct_runtime, parameter_types};
+use crate::{
+	self as basic_token, AssetId, AssetInfo, Config, Error, Init, Releases, StorageVersion,
+	TotalSupply,
+};
+use frame_support::{
+	assert_noop, assert_ok, construct_runtime, parameter_types,
+	traits::{GenesisBuild, OnRuntimeUpgrade},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
@@ -18,7 +25,8 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		BasicToken: basic_token::{Module, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		BasicToken: basic_token::{Module, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -45,24 +53,64 @@ impl frame_system::Config for TestRuntime {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type MaxLocks = ();
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: u64 = 100;
+	pub const StringLimit: u32 = 8;
+}
 impl Config for TestRuntime {
 	type Event = ();
+	type AdminOrigin = system::EnsureRoot<u64>;
+	type InitOrigin = system::EnsureRoot<u64>;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type StringLimit = StringLimit;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
-		let storage = system::GenesisConfig::default()
+		Self::build_with(vec![], 0)
+	}
+
+	/// Starts with the given basic token balances and total supply in the genesis
+	pub fn build_with(token_balances: Vec<(u64, u64)>, total_supply: u64) -> TestExternalities {
+		let mut storage = system::GenesisConfig::default()
 			.build_storage::<TestRuntime>()
 			.unwrap();
+		// Users 1 and 2 can afford an asset deposit, user 3 can't
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: vec![(1, 1_000), (2, 1_000), (3, 50)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		basic_token::GenesisConfig::<TestRuntime> {
+			balances: token_balances,
+			total_supply,
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
 		TestExternalities::from(storage)
 	}
 }
@@ -70,17 +118,27 @@ impl ExternalityBuilder {
 #[test]
 fn init_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(BasicToken::init(Origin::signed(1)));
+		assert_ok!(BasicToken::init(Origin::root(), 1));
 		assert_eq!(BasicToken::get_balance(1), 21000000);
 	})
 }
 
+#[test]
+fn only_init_origin_can_init() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_noop!(
+			BasicToken::init(Origin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
 #[test]
 fn cant_double_init() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(BasicToken::init(Origin::signed(1)));
+		assert_ok!(BasicToken::init(Origin::root(), 1));
 		assert_noop!(
-			BasicToken::init(Origin::signed(1)),
+			BasicToken::init(Origin::root(), 1),
 			Error::<TestRuntime>::AlreadyInitialized
 		);
 	})
@@ -89,7 +147,7 @@ fn cant_double_init() {
 #[test]
 fn transfer_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(BasicToken::init(Origin::signed(1)));
+		assert_ok!(BasicToken::init(Origin::root(), 1));
 
 		// Transfer 100 tokens from user 1 to user 2
 		assert_ok!(BasicToken::transfer(Origin::signed(1), 2, 100));
@@ -99,13 +157,436 @@ fn transfer_works() {
 	})
 }
 
+#[test]
+fn transfer_to_self_keeps_balance() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+		assert_ok!(BasicToken::approve(Origin::signed(1), 2, 100));
+
+		assert_ok!(BasicToken::transfer(Origin::signed(1), 1, 100));
+		assert_ok!(BasicToken::transfer_from(Origin::signed(2), 1, 1, 100));
+
+		assert_eq!(BasicToken::get_balance(1), 21000000);
+		assert_eq!(BasicToken::total_supply(), 21000000);
+		// Moving the whole balance to yourself doesn't create any tokens either
+		assert_ok!(BasicToken::transfer(Origin::signed(1), 1, 21000000));
+		assert_eq!(BasicToken::get_balance(1), 21000000);
+	})
+}
+
 #[test]
 fn cant_spend_more_than_you_have() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(BasicToken::init(Origin::signed(1)));
+		assert_ok!(BasicToken::init(Origin::root(), 1));
 		assert_noop!(
 			BasicToken::transfer(Origin::signed(1), 2, 21000001),
 			Error::<TestRuntime>::InsufficientFunds
 		);
 	})
 }
+
+#[test]
+fn withdraw_and_deposit_track_total_supply() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+
+		assert_ok!(BasicToken::withdraw(&1, 1000));
+		assert_eq!(BasicToken::get_balance(1), 20999000);
+		assert_eq!(BasicToken::total_supply(), 20999000);
+
+		BasicToken::deposit(&2, 400);
+		assert_eq!(BasicToken::get_balance(2), 400);
+		assert_eq!(BasicToken::total_supply(), 20999400);
+	})
+}
+
+#[test]
+fn cant_withdraw_more_than_you_have() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+		assert_noop!(
+			BasicToken::withdraw(&2, 1),
+			Error::<TestRuntime>::InsufficientFunds
+		);
+	})
+}
+
+#[test]
+fn approve_sets_allowance() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::approve(Origin::signed(1), 2, 100));
+		assert_eq!(BasicToken::allowance(1, 2), 100);
+
+		// Approving again replaces the allowance
+		assert_ok!(BasicToken::approve(Origin::signed(1), 2, 30));
+		assert_eq!(BasicToken::allowance(1, 2), 30);
+		assert_eq!(BasicToken::allowance(2, 1), 0);
+	})
+}
+
+#[test]
+fn increase_and_decrease_allowance_work() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::increase_allowance(Origin::signed(1), 2, 100));
+		assert_ok!(BasicToken::increase_allowance(Origin::signed(1), 2, 50));
+		assert_eq!(BasicToken::allowance(1, 2), 150);
+
+		assert_ok!(BasicToken::decrease_allowance(Origin::signed(1), 2, 120));
+		assert_eq!(BasicToken::allowance(1, 2), 30);
+	})
+}
+
+#[test]
+fn allowance_cant_overflow_or_underflow() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::approve(Origin::signed(1), 2, u64::max_value()));
+		assert_noop!(
+			BasicToken::increase_allowance(Origin::signed(1), 2, 1),
+			Error::<TestRuntime>::AllowanceOverflow
+		);
+
+		assert_noop!(
+			BasicToken::decrease_allowance(Origin::signed(1), 3, 1),
+			Error::<TestRuntime>::InsufficientAllowance
+		);
+	})
+}
+
+#[test]
+fn transfer_from_spends_allowance() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+		assert_ok!(BasicToken::approve(Origin::signed(1), 2, 100));
+
+		// User 2 moves 60 of user 1's tokens to user 3
+		assert_ok!(BasicToken::transfer_from(Origin::signed(2), 1, 3, 60));
+
+		assert_eq!(BasicToken::get_balance(1), 20999940);
+		assert_eq!(BasicToken::get_balance(2), 0);
+		assert_eq!(BasicToken::get_balance(3), 60);
+		assert_eq!(BasicToken::allowance(1, 2), 40);
+	})
+}
+
+#[test]
+fn cant_transfer_from_more_than_approved() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+		assert_ok!(BasicToken::approve(Origin::signed(1), 2, 100));
+
+		assert_noop!(
+			BasicToken::transfer_from(Origin::signed(2), 1, 3, 101),
+			Error::<TestRuntime>::InsufficientAllowance
+		);
+		// Nobody else may spend user 2's allowance
+		assert_noop!(
+			BasicToken::transfer_from(Origin::signed(3), 1, 3, 1),
+			Error::<TestRuntime>::InsufficientAllowance
+		);
+	})
+}
+
+#[test]
+fn cant_transfer_from_more_than_owner_has() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::approve(Origin::signed(4), 2, 100));
+
+		assert_noop!(
+			BasicToken::transfer_from(Origin::signed(2), 4, 3, 100),
+			Error::<TestRuntime>::InsufficientFunds
+		);
+		assert_eq!(BasicToken::allowance(4, 2), 100);
+	})
+}
+
+#[test]
+fn mint_and_burn_track_total_supply() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+
+		assert_ok!(BasicToken::mint(Origin::root(), 2, 500));
+		assert_eq!(BasicToken::get_balance(2), 500);
+		assert_eq!(BasicToken::total_supply(), 21000500);
+
+		assert_ok!(BasicToken::burn(Origin::root(), 1, 1000));
+		assert_eq!(BasicToken::get_balance(1), 20999000);
+		assert_eq!(BasicToken::total_supply(), 20999500);
+	})
+}
+
+#[test]
+fn only_admin_can_mint_and_burn() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+
+		assert_noop!(
+			BasicToken::mint(Origin::signed(1), 1, 500),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			BasicToken::burn(Origin::signed(1), 1, 500),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn cant_mint_beyond_u64_or_burn_more_than_held() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+		assert_noop!(
+			BasicToken::mint(Origin::root(), 1, u64::max_value()),
+			Error::<TestRuntime>::SupplyOverflow
+		);
+		assert_noop!(
+			BasicToken::burn(Origin::root(), 2, 1),
+			Error::<TestRuntime>::InsufficientFunds
+		);
+	})
+}
+
+/// User 1 creates an asset with the given supply, which gets the next asset id
+fn create_asset(supply: u64) -> AssetId {
+	let id = BasicToken::asset_count();
+	assert_ok!(BasicToken::create_asset(
+		Origin::signed(1),
+		b"Recipe".to_vec(),
+		b"RCP".to_vec(),
+		12,
+		supply
+	));
+	id
+}
+
+#[test]
+fn create_asset_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(create_asset(500), 0);
+
+		assert_eq!(
+			BasicToken::asset(0),
+			Some(AssetInfo {
+				owner: 1,
+				deposit: 100,
+				name: b"Recipe".to_vec(),
+				symbol: b"RCP".to_vec(),
+				decimals: 12,
+				supply: 500,
+			})
+		);
+		assert_eq!(BasicToken::asset_balance(1, 0), 500);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(Balances::free_balance(1), 900);
+
+		// The single token is unaffected
+		assert_eq!(BasicToken::get_balance(1), 0);
+		assert_eq!(BasicToken::total_supply(), 0);
+	})
+}
+
+#[test]
+fn each_asset_gets_its_own_id() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(create_asset(500), 0);
+		assert_eq!(create_asset(700), 1);
+
+		assert_eq!(BasicToken::asset_count(), 2);
+		assert_eq!(BasicToken::asset_balance(1, 0), 500);
+		assert_eq!(BasicToken::asset_balance(1, 1), 700);
+		assert_eq!(Balances::reserved_balance(1), 200);
+	})
+}
+
+#[test]
+fn cant_create_asset_without_deposit() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_noop!(
+			BasicToken::create_asset(
+				Origin::signed(3),
+				b"Recipe".to_vec(),
+				b"RCP".to_vec(),
+				12,
+				500
+			),
+			pallet_balances::Error::<TestRuntime, _>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn cant_create_asset_with_long_metadata() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_noop!(
+			BasicToken::create_asset(
+				Origin::signed(1),
+				b"Recipe Dollar".to_vec(),
+				b"RCP".to_vec(),
+				12,
+				500
+			),
+			Error::<TestRuntime>::BadMetadata
+		);
+		assert_noop!(
+			BasicToken::create_asset(
+				Origin::signed(1),
+				b"Recipe".to_vec(),
+				b"RECIPEDOLLAR".to_vec(),
+				12,
+				500
+			),
+			Error::<TestRuntime>::BadMetadata
+		);
+	})
+}
+
+#[test]
+fn transfer_asset_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		let id = create_asset(500);
+
+		assert_ok!(BasicToken::transfer_asset(Origin::signed(1), id, 2, 200));
+
+		assert_eq!(BasicToken::asset_balance(1, id), 300);
+		assert_eq!(BasicToken::asset_balance(2, id), 200);
+		assert_eq!(BasicToken::asset(id).unwrap().supply, 500);
+	})
+}
+
+#[test]
+fn transfers_are_scoped_by_asset() {
+	ExternalityBuilder::build().execute_with(|| {
+		let first = create_asset(500);
+		let second = create_asset(500);
+		assert_ok!(BasicToken::transfer_asset(Origin::signed(1), first, 2, 200));
+
+		// User 2 holds none of the second asset
+		assert_noop!(
+			BasicToken::transfer_asset(Origin::signed(2), second, 3, 1),
+			Error::<TestRuntime>::InsufficientFunds
+		);
+		assert_noop!(
+			BasicToken::transfer_asset(Origin::signed(2), first, 3, 201),
+			Error::<TestRuntime>::InsufficientFunds
+		);
+		assert_noop!(
+			BasicToken::transfer_asset(Origin::signed(1), 7, 3, 1),
+			Error::<TestRuntime>::UnknownAsset
+		);
+	})
+}
+
+#[test]
+fn transfer_asset_to_self_keeps_balance() {
+	ExternalityBuilder::build().execute_with(|| {
+		let id = create_asset(500);
+
+		assert_ok!(BasicToken::transfer_asset(Origin::signed(1), id, 1, 200));
+
+		assert_eq!(BasicToken::asset_balance(1, id), 500);
+	})
+}
+
+#[test]
+fn holdings_lists_nonzero_balances() {
+	ExternalityBuilder::build().execute_with(|| {
+		let first = create_asset(500);
+		let second = create_asset(300);
+		let third = create_asset(100);
+		assert_eq!(
+			BasicToken::holdings(&1),
+			vec![(first, 500), (second, 300), (third, 100)]
+		);
+
+		// Emptied balances are no longer listed
+		assert_ok!(BasicToken::transfer_asset(
+			Origin::signed(1),
+			second,
+			2,
+			300
+		));
+		assert_eq!(BasicToken::holdings(&1), vec![(first, 500), (third, 100)]);
+		assert_eq!(BasicToken::holdings(&2), vec![(second, 300)]);
+		assert!(BasicToken::holdings(&3).is_empty());
+	})
+}
+
+#[test]
+fn genesis_sets_balances_and_supply() {
+	ExternalityBuilder::build_with(vec![(1, 600), (2, 400)], 1_000).execute_with(|| {
+		assert_eq!(BasicToken::get_balance(1), 600);
+		assert_eq!(BasicToken::get_balance(2), 400);
+		assert_eq!(BasicToken::total_supply(), 1_000);
+
+		// The supply was issued at genesis, so nobody can issue another
+		assert_noop!(
+			BasicToken::init(Origin::root(), 3),
+			Error::<TestRuntime>::AlreadyInitialized
+		);
+	})
+}
+
+#[test]
+fn empty_genesis_leaves_supply_to_init() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(BasicToken::total_supply(), 0);
+		assert!(!BasicToken::is_init());
+
+		assert_ok!(BasicToken::init(Origin::root(), 3));
+		assert_eq!(BasicToken::get_balance(3), 21000000);
+		assert_eq!(BasicToken::total_supply(), 21000000);
+	})
+}
+
+#[test]
+#[should_panic(expected = "Genesis balances must add up to the total supply")]
+fn genesis_balances_must_add_up() {
+	ExternalityBuilder::build_with(vec![(1, 600), (2, 400)], 900);
+}
+
+#[test]
+fn migration_counts_issued_supply() {
+	ExternalityBuilder::build().execute_with(|| {
+		// A chain from before the genesis configuration, where `init` issued the supply and user 1
+		// passed some on. `TotalSupply` was never written, because it defaulted to the supply.
+		StorageVersion::<TestRuntime>::kill();
+		Init::<TestRuntime>::put(true);
+		BasicToken::deposit(&1, 21000000);
+		assert_ok!(BasicToken::transfer(Origin::signed(1), 2, 100));
+		TotalSupply::<TestRuntime>::kill();
+
+		BasicToken::on_runtime_upgrade();
+
+		assert_eq!(BasicToken::total_supply(), 21000000);
+		assert!(BasicToken::is_init());
+		assert_eq!(StorageVersion::<TestRuntime>::get(), Releases::V2);
+	})
+}
+
+#[test]
+fn migration_keeps_uninitialized_chains_uninitialized() {
+	ExternalityBuilder::build().execute_with(|| {
+		// A chain from before the genesis configuration, where `init` was never called
+		StorageVersion::<TestRuntime>::kill();
+
+		BasicToken::on_runtime_upgrade();
+
+		assert_eq!(BasicToken::total_supply(), 0);
+		assert!(!BasicToken::is_init());
+
+		// The supply can still be issued
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+		assert_eq!(BasicToken::total_supply(), 21000000);
+	})
+}
+
+#[test]
+fn migration_runs_once() {
+	ExternalityBuilder::build_with(vec![(1, 1_000)], 1_000).execute_with(|| {
+		// Throw the supply off, to see whether the migration recounts it
+		TotalSupply::<TestRuntime>::put(5);
+
+		BasicToken::on_runtime_upgrade();
+
+		assert_eq!(BasicToken::total_supply(), 5);
+	})
+}
@@ -0,0 +1,26 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime amalgamator file (the `runtime/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+	/// Reports how many new tokens the block rewards pallet mints, and when.
+	pub trait BlockRewardsApi<BlockNumber, Balance> where
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// The reward for authoring the block with the given number
+		fn block_reward(number: BlockNumber) -> Balance;
+
+		/// The issuance curve, as the first block of each halving era and the reward paid for
+		/// every block in that era. The last entry is the era whose reward reached zero.
+		fn issuance_schedule() -> Vec<(BlockNumber, Balance)>;
+
+		/// The total reward minted for blocks one through `number`, inclusive
+		fn total_rewards(number: BlockNumber) -> Balance;
+	}
+}
@@ -0,0 +1,186 @@
+//! Block rewards for proof of work chains.
+//!
+//! In a proof of work chain, anyone who finds a seal may author a block, so there is no on-chain
+//! list of authors to pay. Instead, the miner names the account to be rewarded in a pre-runtime
+//! digest of the block it mines. Because the digest is part of the pre-hash that the seal commits
+//! to, nobody can swap in their own account without redoing the work.
+//!
+//! At the start of each block this pallet finds that account and mints the block reward into it.
+//! The reward starts at `BaseReward` and halves every `HalvingInterval` blocks, until it reaches
+//! zero, much like Bitcoin's.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+pub use pallet::*;
+
+use frame_support::{
+	traits::{Currency, FindAuthor, Get, Imbalance},
+	weights::Weight,
+	ConsensusEngineId,
+};
+use parity_scale_codec::Decode;
+use sp_consensus_pow::POW_ENGINE_ID;
+use sp_runtime::{
+	traits::{CheckedShr, Saturating, Zero},
+	SaturatedConversion,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the rewards are minted in
+		type Currency: Currency<Self::AccountId>;
+
+		/// Finds the account to reward among the block's pre-runtime digests
+		type FindAuthor: FindAuthor<Self::AccountId>;
+
+		/// The reward for each block before the first halving
+		type BaseReward: Get<BalanceOf<Self>>;
+
+		/// The number of blocks between halvings. Zero means the reward never halves.
+		type HalvingInterval: Get<Self::BlockNumber>;
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A block author has been rewarded. [author, reward]
+		Rewarded(T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let digest = frame_system::Pallet::<T>::digest();
+			let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
+
+			let author = match T::FindAuthor::find_author(pre_runtime_digests) {
+				Some(author) => author,
+				// Nobody asked to be rewarded
+				None => return T::DbWeight::get().reads(1),
+			};
+
+			let reward = Self::block_reward(now);
+			if !reward.is_zero() {
+				// Nothing is minted when the reward wouldn't reach the existential deposit of a new
+				// account. Dropping the positive imbalance raises the total issuance accordingly.
+				let minted = T::Currency::deposit_creating(&author, reward).peek();
+				if !minted.is_zero() {
+					Self::deposit_event(Event::Rewarded(author, minted));
+				}
+			}
+
+			T::DbWeight::get().reads_writes(2, 2)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The reward for authoring the block with the given number. The genesis block has none.
+	pub fn block_reward(number: T::BlockNumber) -> BalanceOf<T> {
+		if number.is_zero() {
+			return Zero::zero();
+		}
+		Self::era_reward(Self::era(number))
+	}
+
+	/// The issuance curve, as the first block of each halving era and its per-block reward. The
+	/// schedule ends with the first era that pays nothing.
+	pub fn issuance_schedule() -> Vec<(T::BlockNumber, BalanceOf<T>)> {
+		let interval = T::HalvingInterval::get();
+		if interval.is_zero() {
+			return vec![(1u32.into(), T::BaseReward::get())];
+		}
+
+		let mut schedule = Vec::new();
+		// With an interval of one, the first halving happens before block one is even mined.
+		let mut era = Self::era(1u32.into());
+		loop {
+			let start = interval.saturating_mul(era.into()).max(1u32.into());
+			let reward = Self::era_reward(era);
+			schedule.push((start, reward));
+			if reward.is_zero() {
+				return schedule;
+			}
+			era += 1;
+		}
+	}
+
+	/// The total reward minted for blocks one through `number`, inclusive.
+	pub fn total_rewards(number: T::BlockNumber) -> BalanceOf<T> {
+		let interval = T::HalvingInterval::get();
+		let schedule = Self::issuance_schedule();
+
+		let mut total: BalanceOf<T> = Zero::zero();
+		for (era, (start, reward)) in schedule.iter().enumerate() {
+			if *start > number {
+				break;
+			}
+			// The last block of this era that counts toward the total
+			let end = match schedule.get(era + 1) {
+				Some((next, _)) if !interval.is_zero() => (*next - 1u32.into()).min(number),
+				_ => number,
+			};
+			let blocks: u128 = (end - *start + 1u32.into()).saturated_into();
+			total = total.saturating_add(reward.saturating_mul(blocks.saturated_into()));
+		}
+		total
+	}
+
+	/// The number of halvings that happened before the given block.
+	fn era(number: T::BlockNumber) -> u32 {
+		let interval = T::HalvingInterval::get();
+		if interval.is_zero() {
+			return 0;
+		}
+		(number / interval).saturated_into()
+	}
+
+	/// The per-block reward after `era` halvings.
+	fn era_reward(era: u32) -> BalanceOf<T> {
+		// Shifting by at least the width of the balance type leaves nothing.
+		T::BaseReward::get()
+			.checked_shr(era)
+			.unwrap_or_else(Zero::zero)
+	}
+}
+
+/// Finds the author of a proof of work block, from the account its miner encoded in the
+/// `POW_ENGINE_ID` pre-runtime digest.
+pub struct PowAuthor<AccountId>(PhantomData<AccountId>);
+
+impl<AccountId: Decode> FindAuthor<AccountId> for PowAuthor<AccountId> {
+	fn find_author<'a, I>(digests: I) -> Option<AccountId>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		digests
+			.into_iter()
+			.find(|(id, _)| *id == POW_ENGINE_ID)
+			.and_then(|(_, mut data)| AccountId::decode(&mut data).ok())
+	}
+}
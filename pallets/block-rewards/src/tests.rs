@@ -0,0 +1,230 @@
+use crate::{self as block_rewards, Config, PowAuthor};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Currency, OnInitialize},
+};
+use frame_system::InitKind;
+use parity_scale_codec::Encode;
+use sp_consensus_pow::POW_ENGINE_ID;
+use sp_core::H256;
+use sp_runtime::{
+	generic::{Digest, DigestItem},
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime!(
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		BlockRewards: block_rewards::{Module, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for TestRuntime {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	// Above the reward of the later eras, so small rewards can't open new accounts
+	pub const ExistentialDeposit: u64 = 10;
+}
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const BaseReward: u64 = 1_000;
+	pub const HalvingInterval: u64 = 10;
+}
+impl Config for TestRuntime {
+	type Event = Event;
+	type Currency = Balances;
+	type FindAuthor = PowAuthor<u64>;
+	type BaseReward = BaseReward;
+	type HalvingInterval = HalvingInterval;
+}
+
+const MINER: u64 = 7;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::default()
+		.build_storage::<TestRuntime>()
+		.unwrap()
+		.into()
+}
+
+/// Start block `number` with the given pre-runtime digests, and run the rewards hook.
+fn author_block(number: u64, pre_runtime: Vec<DigestItem<H256>>) {
+	System::initialize(
+		&number,
+		&Default::default(),
+		&Digest { logs: pre_runtime },
+		InitKind::Full,
+	);
+	BlockRewards::on_initialize(number);
+}
+
+fn mined_by(author: u64) -> Vec<DigestItem<H256>> {
+	vec![DigestItem::PreRuntime(POW_ENGINE_ID, author.encode())]
+}
+
+#[test]
+fn author_is_rewarded() {
+	new_test_ext().execute_with(|| {
+		author_block(1, mined_by(MINER));
+
+		assert_eq!(Balances::free_balance(&MINER), BaseReward::get());
+		assert_eq!(Balances::total_issuance(), BaseReward::get());
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(Event::block_rewards(block_rewards::Event::Rewarded(
+				MINER,
+				BaseReward::get()
+			)))
+		);
+	})
+}
+
+#[test]
+fn rewards_below_the_existential_deposit_only_go_to_existing_accounts() {
+	new_test_ext().execute_with(|| {
+		// The seventh era pays 1_000 >> 7 = 7
+		let number = 7 * HalvingInterval::get();
+		assert!(BlockRewards::block_reward(number) < ExistentialDeposit::get());
+
+		author_block(number, mined_by(MINER));
+		assert_eq!(Balances::total_issuance(), 0);
+		assert!(System::events().is_empty());
+
+		let _ = Balances::deposit_creating(&MINER, ExistentialDeposit::get());
+		author_block(number + 1, mined_by(MINER));
+		assert_eq!(
+			Balances::free_balance(&MINER),
+			ExistentialDeposit::get() + 7
+		);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(Event::block_rewards(block_rewards::Event::Rewarded(
+				MINER, 7
+			)))
+		);
+	})
+}
+
+#[test]
+fn no_digest_no_reward() {
+	new_test_ext().execute_with(|| {
+		author_block(1, vec![]);
+
+		assert_eq!(Balances::total_issuance(), 0);
+		assert!(System::events().is_empty());
+	})
+}
+
+#[test]
+fn other_engines_are_ignored() {
+	new_test_ext().execute_with(|| {
+		author_block(1, vec![DigestItem::PreRuntime(*b"aura", MINER.encode())]);
+
+		assert_eq!(Balances::free_balance(&MINER), 0);
+	})
+}
+
+#[test]
+fn undecodable_author_is_ignored() {
+	new_test_ext().execute_with(|| {
+		author_block(1, vec![DigestItem::PreRuntime(POW_ENGINE_ID, vec![1, 2])]);
+
+		assert_eq!(Balances::total_issuance(), 0);
+	})
+}
+
+#[test]
+fn reward_halves() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BlockRewards::block_reward(0), 0);
+		assert_eq!(BlockRewards::block_reward(1), 1_000);
+		assert_eq!(BlockRewards::block_reward(9), 1_000);
+		assert_eq!(BlockRewards::block_reward(10), 500);
+		assert_eq!(BlockRewards::block_reward(25), 250);
+		assert_eq!(BlockRewards::block_reward(10 * 64), 0);
+		assert_eq!(BlockRewards::block_reward(u64::max_value()), 0);
+	})
+}
+
+#[test]
+fn issuance_schedule_ends_at_zero() {
+	new_test_ext().execute_with(|| {
+		let schedule = BlockRewards::issuance_schedule();
+
+		assert_eq!(schedule[0], (1, 1_000));
+		assert_eq!(schedule[1], (10, 500));
+		assert_eq!(schedule[2], (20, 250));
+		// 1_000 survives nine halvings
+		assert_eq!(schedule.len(), 11);
+		assert_eq!(schedule.last(), Some(&(100, 0)));
+	})
+}
+
+#[test]
+fn total_rewards_match_minted_rewards() {
+	new_test_ext().execute_with(|| {
+		for number in 1..=25 {
+			author_block(number, mined_by(MINER));
+			assert_eq!(
+				BlockRewards::total_rewards(number),
+				Balances::free_balance(&MINER)
+			);
+		}
+		assert_eq!(
+			BlockRewards::total_rewards(25),
+			9 * 1_000 + 10 * 500 + 6 * 250
+		);
+	})
+}
+
+#[test]
+fn total_rewards_are_capped() {
+	new_test_ext().execute_with(|| {
+		// 9 * 1_000 + 10 * (500 + 250 + 125 + 62 + 31 + 15 + 7 + 3 + 1)
+		assert_eq!(BlockRewards::total_rewards(1_000_000), 18_940);
+	})
+}
@@ -0,0 +1,33 @@
+//! Benchmarks for the charity pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+benchmarks! {
+	donate {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let amount = T::Currency::minimum_balance() * 10u32.into();
+		let pot = Pallet::<T>::pot();
+	}: _(RawOrigin::Signed(caller), amount)
+	verify {
+		assert_eq!(Pallet::<T>::pot(), pot + amount);
+	}
+
+	allocate {
+		let dest: T::AccountId = account("dest", 0, 0);
+		let amount = T::Currency::minimum_balance() * 10u32.into();
+		T::Currency::make_free_balance_be(&Pallet::<T>::account_id(), amount * 2u32.into());
+	}: _(RawOrigin::Root, dest.clone(), amount)
+	verify {
+		assert_eq!(T::Currency::free_balance(&dest), amount);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::new_test_ext(),
+	crate::tests::TestRuntime,
+);
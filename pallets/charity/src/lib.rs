@@ -29,8 +29,13 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use crate::BalanceOf;
 	use frame_support::{
 		dispatch::DispatchResultWithPostInfo,
@@ -38,6 +43,7 @@ pub mod pallet {
 		traits::{Currency, ExistenceRequirement::AllowDeath},
 	};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Zero;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -45,18 +51,31 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// The currency type that the charity deals in
 		type Currency: Currency<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::genesis_config]
-	#[derive(Default)]
-	pub struct GenesisConfig {}
+	pub struct GenesisConfig<T: Config> {
+		/// The funds the pot starts with. The pot always holds at least the existential deposit,
+		/// so smaller values are raised to it.
+		pub pot: BalanceOf<T>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { pot: Zero::zero() }
+		}
+	}
 
 	#[pallet::genesis_build]
-	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			let _ = T::Currency::make_free_balance_be(
 				&<Module<T>>::account_id(),
-				T::Currency::minimum_balance(),
+				self.pot.max(T::Currency::minimum_balance()),
 			);
 		}
 	}
@@ -83,7 +102,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Donate some funds to the charity
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::donate())]
 		pub fn donate(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let donor = ensure_signed(origin)?;
 
@@ -98,7 +117,7 @@ pub mod pallet {
 		///
 		/// Take funds from the Charity's pot and send them somewhere. This call requires root origin,
 		/// which means it must come from a governance mechanism such as Substrate's Democracy pallet.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::allocate())]
 		pub fn allocate(
 			origin: OriginFor<T>,
 			dest: T::AccountId,
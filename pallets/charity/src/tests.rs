@@ -23,7 +23,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-		Charity: charity::{Module, Call, Config, Storage, Event<T>},
+		Charity: charity::{Module, Call, Config<T>, Storage, Event<T>},
 	}
 );
 
@@ -73,6 +73,7 @@ impl pallet_balances::Config for TestRuntime {
 impl Config for TestRuntime {
 	type Event = Event;
 	type Currency = Balances;
+	type WeightInfo = ();
 }
 
 // An alternative to `ExternalityBuilder` which includes custom configuration
@@ -92,7 +93,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	.assimilate_storage::<TestRuntime>(&mut t)
 	.unwrap();*/
 
-	let charity_config = charity::GenesisConfig::default();
+	let charity_config = charity::GenesisConfig::<TestRuntime>::default();
 	GenesisBuild::<TestRuntime>::assimilate_storage(&charity_config, &mut t).unwrap();
 
 	let mut ext: sp_io::TestExternalities = t.into();
@@ -108,6 +109,20 @@ fn pot_min_balance_is_set() {
 	})
 }
 
+/// Charity pot starts with the funds given in the genesis config
+#[test]
+fn pot_genesis_balance_is_set() {
+	let mut t = system::GenesisConfig::default()
+		.build_storage::<TestRuntime>()
+		.unwrap();
+	let charity_config = charity::GenesisConfig::<TestRuntime> { pot: 50 };
+	GenesisBuild::<TestRuntime>::assimilate_storage(&charity_config, &mut t).unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(Charity::pot(), 50);
+	})
+}
+
 /// Verifying correct behavior of boilerplate
 #[test]
 fn new_test_ext_behaves() {
@@ -0,0 +1,23 @@
+//! Benchmarks for the loosely coupled check membership pallet
+
+use super::*;
+use account_set::AccountSet;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+// The membership source is filled up to its bound, the largest set the check can face.
+benchmarks! {
+	check_membership {
+		for i in 1..T::MembershipSource::max_count() {
+			T::MembershipSource::add(&account("member", i, 0))?;
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		T::MembershipSource::add(&caller)?;
+	}: _(RawOrigin::Signed(caller))
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::loose::tests::ExternalityBuilder::build(),
+	crate::loose::tests::TestRuntime,
+);
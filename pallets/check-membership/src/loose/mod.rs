@@ -12,8 +12,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use super::weights::WeightInfo;
 	use account_set::AccountSet;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
@@ -27,6 +32,9 @@ pub mod pallet {
 
 		/// A type that will supply a set of members to check access control against
 		type MembershipSource: AccountSet<AccountId = Self::AccountId>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
@@ -54,15 +62,16 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Checks whether the caller is a member of the set of account IDs provided by the
 		/// MembershipSource type. Emits an event if they are, and errors if not.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::check_membership())]
 		pub fn check_membership(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let caller = ensure_signed(origin)?;
 
-			// Get the members from the `vec-set` pallet
-			let members = T::MembershipSource::accounts();
-
-			// Check whether the caller is a member
-			ensure!(members.contains(&caller), Error::<T>::NotAMember);
+			// Ask the membership source whether the caller is a member, rather than collecting
+			// the whole set
+			ensure!(
+				T::MembershipSource::contains(&caller),
+				Error::<T>::NotAMember
+			);
 
 			// If the previous call didn't error, then the caller is a member, so emit the event
 			Self::deposit_event(Event::IsAMember(caller));
@@ -1,5 +1,6 @@
 use crate::loose::{self as check_membership, Config, Error};
 use frame_support::{assert_noop, assert_ok, construct_runtime, parameter_types};
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
@@ -17,6 +18,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		VecSet: vec_set::{Module, Call, Storage, Event<T>},
 		CheckMembership: check_membership::{Module, Call, Event<T>},
 	}
@@ -45,29 +47,62 @@ impl frame_system::Config for TestRuntime {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type MaxLocks = ();
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxMembers: u32 = 16;
+	pub const MembershipDeposit: u64 = 10;
+	pub const VoteThreshold: u32 = 2;
+}
 impl vec_set::Config for TestRuntime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	type AddOrigin = EnsureRoot<u64>;
+	type RemoveOrigin = EnsureRoot<u64>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = ();
+	type WeightInfo = ();
 }
 
 impl Config for TestRuntime {
 	type Event = Event;
 	type MembershipSource = VecSet;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
-		let storage = frame_system::GenesisConfig::default()
+		let mut storage = frame_system::GenesisConfig::default()
 			.build_storage::<TestRuntime>()
 			.unwrap();
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: vec![(1, 100)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
 		let mut ext = TestExternalities::from(storage);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
@@ -77,13 +112,14 @@ impl ExternalityBuilder {
 #[test]
 fn members_can_call() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(VecSet::add_member(Origin::signed(1)));
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
+		assert_ok!(VecSet::approve_request(Origin::root(), 1));
 
 		assert_ok!(CheckMembership::check_membership(Origin::signed(1)));
 
 		let expected_event = Event::check_membership(check_membership::Event::IsAMember(1));
 
-		assert_eq!(System::events()[1].event, expected_event,);
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 	})
 }
 
@@ -0,0 +1,41 @@
+//! Weights for check_membership_loose
+
+// ./target/release/kitchen-node benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=check_membership_loose
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/frame-weight-template.hbs
+// --output=./pallets/check-membership/src/loose/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for check_membership_loose.
+pub trait WeightInfo {
+	fn check_membership() -> Weight;
+}
+
+/// Weights for check_membership_loose using the recipes' kitchen node.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn check_membership() -> Weight {
+		(24_000_000 as Weight).saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+}
+
+// For tests and runtimes that don't care about weights
+impl WeightInfo for () {
+	fn check_membership() -> Weight {
+		(24_000_000 as Weight).saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+}
@@ -0,0 +1,34 @@
+//! Benchmarks for the tightly coupled check membership pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use frame_system::RawOrigin;
+
+/// Add `who` to the vec set, by requesting membership and having the `AddOrigin` approve it
+fn add_member<T: Config>(who: T::AccountId) -> Result<(), &'static str> {
+	let deposit = <T as vec_set::Config>::MembershipDeposit::get();
+	let amount = (<T as vec_set::Config>::Currency::minimum_balance() + deposit) * 10u32.into();
+	<T as vec_set::Config>::Currency::make_free_balance_be(&who, amount);
+	vec_set::Module::<T>::request_membership(RawOrigin::Signed(who.clone()).into())?;
+	let origin = <T as vec_set::Config>::AddOrigin::successful_origin();
+	vec_set::Module::<T>::approve_request(origin, who)?;
+	Ok(())
+}
+
+benchmarks! {
+	check_membership {
+		// The caller joins a full set, so the membership check reads the largest vector it can.
+		for i in 1..<T as vec_set::Config>::MaxMembers::get() {
+			add_member::<T>(account("member", i, 0))?;
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		add_member::<T>(caller.clone())?;
+	}: _(RawOrigin::Signed(caller))
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tight::tests::ExternalityBuilder::build(),
+	crate::tight::tests::TestRuntime,
+);
@@ -11,8 +11,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use super::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
@@ -21,6 +26,9 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config + vec_set::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
@@ -48,7 +56,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Checks whether the caller is a member of the set of account IDs provided by the `vec-set`
 		/// pallet. Emits an event if they are, and errors if not.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::check_membership())]
 		pub fn check_membership(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let caller = ensure_signed(origin)?;
 
@@ -0,0 +1,40 @@
+//! Benchmarks for the compounding interest pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	deposit_continuous {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 1_000)
+
+	withdraw_continuous {
+		let caller: T::AccountId = whitelisted_caller();
+		ContinuousAccount::<T>::put(ContinuousAccountData {
+			principal: I32F32::from_num(1_000),
+			deposit_date: frame_system::Module::<T>::block_number(),
+		});
+	}: _(RawOrigin::Signed(caller), 500)
+
+	deposit_discrete {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 1_000)
+	verify {
+		assert_eq!(Pallet::<T>::discrete_account(), 1_000);
+	}
+
+	withdraw_discrete {
+		let caller: T::AccountId = whitelisted_caller();
+		DiscreteAccount::<T>::put(1_000);
+	}: _(RawOrigin::Signed(caller), 500)
+	verify {
+		assert_eq!(Pallet::<T>::discrete_account(), 500);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
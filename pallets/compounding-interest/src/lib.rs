@@ -18,8 +18,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 	use sp_runtime::traits::Zero;
@@ -29,6 +34,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[derive(Encode, Decode, Default)]
@@ -96,7 +104,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Deposit some funds into the compounding interest account
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::deposit_continuous())]
 		fn deposit_continuous(origin: OriginFor<T>, val_to_add: u64) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
 
@@ -115,7 +123,7 @@ pub mod pallet {
 		}
 
 		/// Withdraw some funds from the compounding interest account
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::withdraw_continuous())]
 		fn withdraw_continuous(
 			origin: OriginFor<T>,
 			val_to_take: u64,
@@ -137,7 +145,7 @@ pub mod pallet {
 		}
 
 		/// Deposit some funds into the discrete interest account
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::deposit_discrete())]
 		pub fn deposit_discrete(
 			origin: OriginFor<T>,
 			val_to_add: u64,
@@ -155,7 +163,7 @@ pub mod pallet {
 		}
 
 		/// Withdraw some funds from the discrete interest account
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::withdraw_discrete())]
 		pub fn withdraw_discrete(
 			origin: OriginFor<T>,
 			val_to_take: u64,
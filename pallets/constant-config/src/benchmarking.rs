@@ -0,0 +1,29 @@
+//! Benchmarks for the constant config pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+
+benchmarks! {
+	add_value {
+		let caller: T::AccountId = whitelisted_caller();
+		let addend = T::MaxAddend::get();
+	}: _(RawOrigin::Signed(caller), addend)
+	verify {
+		assert_eq!(Pallet::<T>::single_value(), addend);
+	}
+
+	set_value {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(Pallet::<T>::single_value(), 42);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
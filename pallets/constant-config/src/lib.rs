@@ -13,8 +13,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo};
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
@@ -29,6 +34,9 @@ pub mod pallet {
 
 		/// Frequency with which the stored value is deleted
 		type ClearFrequency: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -63,7 +71,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Add to the stored value. The `val_to_add` parameter cannot exceed the specified manimum.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::add_value())]
 		pub fn add_value(origin: OriginFor<T>, val_to_add: u32) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 			ensure!(
@@ -91,7 +99,7 @@ pub mod pallet {
 
 		/// For testing purposes
 		/// Sets the stored value to a given value
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_value())]
 		pub fn set_value(origin: OriginFor<T>, value: u32) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 			SingleValue::<T>::put(value);
@@ -62,9 +62,10 @@ impl Config for TestRuntime {
 	type Event = Event;
 	type MaxAddend = MaxAddend;
 	type ClearFrequency = ClearFrequency;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
@@ -5,8 +5,13 @@
 #![allow(clippy::unused_unit)]
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
@@ -18,6 +23,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -41,7 +49,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// The only dispatchable call, updates the single storage item,
 		/// and emits an event.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::call())]
 		fn call(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let caller = ensure_signed(origin)?;
 
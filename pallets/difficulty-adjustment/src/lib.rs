@@ -0,0 +1,188 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+//! A pallet that adjusts the proof of work difficulty so that blocks keep arriving at a steady
+//! rate as miners join or leave the network.
+//!
+//! Each time the timestamp is set, the pallet records the block's difficulty and timestamp in a
+//! moving window. The difficulty for the next block is the average difficulty over the window,
+//! scaled by how far the observed block time strayed from the target block time. The adjustment
+//! is dampened and clamped so a few unusually fast or slow blocks can't swing it wildly.
+//!
+//! The current difficulty is meant to be exposed to the node through the
+//! `sp_consensus_pow::DifficultyApi` runtime API, where `sha3pow::RuntimePowAlgorithm` picks it up.
+//!
+//! The pallet also records how the chain hashes its proof of work. It is set at genesis and never
+//! changes, and is meant to be exposed through the `sha3pow_runtime_api::PowHashingApi` runtime API
+//! so that every node mines and verifies with the same hasher.
+
+use frame_support::traits::OnTimestampSet;
+use parity_scale_codec::{Decode, Encode};
+use sp_core::U256;
+use sp_runtime::{traits::UniqueSaturatedInto, RuntimeDebug};
+use sp_std::cmp::{max, min};
+
+pub use pallet::*;
+pub use sha3pow_runtime_api::{HashFunction, PowHashing};
+
+#[cfg(test)]
+mod tests;
+
+/// The difficulty type. This matches the `Difficulty` of the sha3pow algorithms.
+pub type Difficulty = U256;
+
+/// The difficulty a block was mined at, and the time it was authored.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct DifficultyAndTimestamp<Moment> {
+	pub difficulty: Difficulty,
+	pub timestamp: Moment,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_timestamp::Config {
+		/// The block time the difficulty adjustment aims for
+		type TargetBlockTime: Get<Self::Moment>;
+
+		/// The number of past blocks the moving average is taken over
+		type AdjustmentWindow: Get<u32>;
+
+		/// How strongly the observed block time is pulled back toward the target before it is
+		/// used. Higher values make the adjustment slower and smoother.
+		type DampFactor: Get<u128>;
+
+		/// The most the observed block time may differ from the target, as a factor in either
+		/// direction, when computing a single adjustment
+		type ClampFactor: Get<u128>;
+
+		/// The difficulty never drops below this value
+		type MinDifficulty: Get<Difficulty>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn past_difficulties_and_timestamps)]
+	pub(super) type PastDifficultiesAndTimestamps<T: Config> =
+		StorageValue<_, Vec<DifficultyAndTimestamp<T::Moment>>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn difficulty)]
+	pub(super) type CurrentDifficulty<T: Config> = StorageValue<_, Difficulty, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn hashing)]
+	pub(super) type Hashing<T: Config> = StorageValue<_, PowHashing, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		/// The difficulty of the first block after genesis
+		pub initial_difficulty: Difficulty,
+		/// How the chain hashes its proof of work
+		pub hashing: PowHashing,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {
+				initial_difficulty: U256::from(1_000_000),
+				hashing: PowHashing::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			CurrentDifficulty::<T>::put(self.initial_difficulty);
+			Hashing::<T>::put(self.hashing);
+		}
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+/// Pull the actual time delta toward the goal. A damp factor of one leaves it untouched.
+fn damp(actual: u128, goal: u128, damp_factor: u128) -> u128 {
+	let damp_factor = max(damp_factor, 1);
+	actual.saturating_add((damp_factor - 1).saturating_mul(goal)) / damp_factor
+}
+
+/// Keep the actual time delta within a factor of the goal in either direction.
+fn clamp(actual: u128, goal: u128, clamp_factor: u128) -> u128 {
+	let clamp_factor = max(clamp_factor, 1);
+	max(
+		goal / clamp_factor,
+		min(actual, goal.saturating_mul(clamp_factor)),
+	)
+}
+
+// The timestamp pallet calls this hook once per block, right after the block's timestamp is set.
+// That makes it the natural place to record the block and retarget the difficulty.
+impl<T: Config> OnTimestampSet<T::Moment> for Module<T> {
+	fn on_timestamp_set(now: T::Moment) {
+		let target_block_time: u128 = T::TargetBlockTime::get().unique_saturated_into();
+		let window = max(T::AdjustmentWindow::get(), 2) as usize;
+
+		// Record this block, dropping the oldest entries once the window is full.
+		let mut data = PastDifficultiesAndTimestamps::<T>::get();
+		data.push(DifficultyAndTimestamp {
+			difficulty: Self::difficulty(),
+			timestamp: now,
+		});
+		if data.len() > window {
+			let excess = data.len() - window;
+			data.drain(..excess);
+		}
+
+		// Until the window fills up, pretend the missing blocks arrived exactly on target
+		// at the current difficulty.
+		let missing = window - data.len();
+
+		let mut ts_delta = data
+			.windows(2)
+			.map(|pair| {
+				let prev: u128 = pair[0].timestamp.unique_saturated_into();
+				let cur: u128 = pair[1].timestamp.unique_saturated_into();
+				cur.saturating_sub(prev)
+			})
+			.fold(0u128, |acc, delta| acc.saturating_add(delta))
+			.saturating_add(target_block_time.saturating_mul(missing as u128));
+		if ts_delta == 0 {
+			ts_delta = 1;
+		}
+
+		let diff_sum = data
+			.iter()
+			.fold(U256::zero(), |acc, d| acc.saturating_add(d.difficulty))
+			.saturating_add(Self::difficulty().saturating_mul(U256::from(missing)));
+
+		// A full window spans one fewer gap than it has blocks.
+		let goal = max(target_block_time.saturating_mul(window as u128 - 1), 1);
+		let adj_ts = clamp(
+			damp(ts_delta, goal, T::DampFactor::get()),
+			goal,
+			T::ClampFactor::get(),
+		);
+
+		// The average difficulty over the window, scaled by how much faster or slower than the
+		// target the blocks arrived.
+		let difficulty = diff_sum.saturating_mul(U256::from(goal))
+			/ U256::from(window).saturating_mul(U256::from(adj_ts));
+
+		PastDifficultiesAndTimestamps::<T>::put(data);
+		CurrentDifficulty::<T>::put(max(difficulty, T::MinDifficulty::get()));
+	}
+}
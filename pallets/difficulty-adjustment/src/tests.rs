@@ -0,0 +1,202 @@
+use crate::{self as difficulty_adjustment, Config, Difficulty, HashFunction, PowHashing};
+use frame_support::{construct_runtime, parameter_types, traits::OnTimestampSet};
+use sp_core::{H256, U256};
+use sp_io::TestExternalities;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime!(
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+		DifficultyAdjustment: difficulty_adjustment::{Module, Storage, Config},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for TestRuntime {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+impl pallet_timestamp::Config for TestRuntime {
+	type Moment = u64;
+	type OnTimestampSet = DifficultyAdjustment;
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TargetBlockTime: u64 = 1_000;
+	pub const AdjustmentWindow: u32 = 10;
+	pub const DampFactor: u128 = 3;
+	pub const ClampFactor: u128 = 2;
+	pub MinDifficulty: Difficulty = U256::from(1_000);
+}
+impl Config for TestRuntime {
+	type TargetBlockTime = TargetBlockTime;
+	type AdjustmentWindow = AdjustmentWindow;
+	type DampFactor = DampFactor;
+	type ClampFactor = ClampFactor;
+	type MinDifficulty = MinDifficulty;
+}
+
+const INITIAL_DIFFICULTY: u64 = 1_000_000;
+
+const HASHING: PowHashing = PowHashing {
+	hash_function: HashFunction::Keccak256,
+	memory_cost: 64,
+};
+
+fn new_test_ext() -> TestExternalities {
+	GenesisConfig {
+		frame_system: Some(Default::default()),
+		difficulty_adjustment: Some(difficulty_adjustment::GenesisConfig {
+			initial_difficulty: U256::from(INITIAL_DIFFICULTY),
+			hashing: HASHING,
+		}),
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+/// Simulate `blocks` blocks authored `block_time` milliseconds apart, starting after `start`.
+/// Returns the timestamp of the last block.
+fn author_blocks(start: u64, blocks: u64, block_time: u64) -> u64 {
+	let mut now = start;
+	for _ in 0..blocks {
+		now += block_time;
+		DifficultyAdjustment::on_timestamp_set(now);
+	}
+	now
+}
+
+#[test]
+fn genesis_sets_initial_difficulty() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			DifficultyAdjustment::difficulty(),
+			U256::from(INITIAL_DIFFICULTY)
+		);
+		assert!(DifficultyAdjustment::past_difficulties_and_timestamps().is_empty());
+	})
+}
+
+#[test]
+fn genesis_sets_hashing() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(DifficultyAdjustment::hashing(), HASHING);
+		// Retargeting leaves the hashing alone.
+		author_blocks(0, 20, 500);
+		assert_eq!(DifficultyAdjustment::hashing(), HASHING);
+	})
+}
+
+#[test]
+fn on_target_blocks_keep_difficulty() {
+	new_test_ext().execute_with(|| {
+		author_blocks(0, 25, TargetBlockTime::get());
+		assert_eq!(
+			DifficultyAdjustment::difficulty(),
+			U256::from(INITIAL_DIFFICULTY)
+		);
+	})
+}
+
+#[test]
+fn fast_blocks_raise_difficulty() {
+	new_test_ext().execute_with(|| {
+		let mut last = U256::from(INITIAL_DIFFICULTY);
+		let mut now = author_blocks(0, 1, TargetBlockTime::get());
+		for _ in 0..10 {
+			now = author_blocks(now, 1, TargetBlockTime::get() / 4);
+			let difficulty = DifficultyAdjustment::difficulty();
+			assert!(difficulty > last);
+			last = difficulty;
+		}
+	})
+}
+
+#[test]
+fn slow_blocks_lower_difficulty() {
+	new_test_ext().execute_with(|| {
+		let mut last = U256::from(INITIAL_DIFFICULTY);
+		let mut now = author_blocks(0, 1, TargetBlockTime::get());
+		for _ in 0..10 {
+			now = author_blocks(now, 1, TargetBlockTime::get() * 4);
+			let difficulty = DifficultyAdjustment::difficulty();
+			assert!(difficulty < last);
+			last = difficulty;
+		}
+	})
+}
+
+#[test]
+fn single_adjustment_is_clamped() {
+	new_test_ext().execute_with(|| {
+		// Fill the window with on-target blocks, then author one extremely slow block.
+		let now = author_blocks(0, AdjustmentWindow::get() as u64, TargetBlockTime::get());
+		author_blocks(now, 1, TargetBlockTime::get() * 1_000);
+
+		// However long the block took, the difficulty at most halves in a single adjustment.
+		assert_eq!(
+			DifficultyAdjustment::difficulty(),
+			U256::from(INITIAL_DIFFICULTY / 2)
+		);
+	})
+}
+
+#[test]
+fn difficulty_respects_minimum() {
+	new_test_ext().execute_with(|| {
+		author_blocks(0, 500, TargetBlockTime::get() * 100);
+		assert_eq!(DifficultyAdjustment::difficulty(), MinDifficulty::get());
+	})
+}
+
+#[test]
+fn window_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let now = author_blocks(0, 25, TargetBlockTime::get());
+		let past = DifficultyAdjustment::past_difficulties_and_timestamps();
+
+		assert_eq!(past.len(), AdjustmentWindow::get() as usize);
+		assert_eq!(past.last().map(|d| d.timestamp), Some(now));
+	})
+}
@@ -0,0 +1,163 @@
+//! Benchmarks for the double map pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_std::{convert::TryFrom, prelude::*};
+
+/// The group the members of the benchmarks join
+const GROUP: GroupIndex = 1;
+
+/// Give `who` enough funds to reserve the membership deposit
+fn fund_account<T: Config>(who: &T::AccountId) {
+	let amount = (T::Currency::minimum_balance() + T::MembershipDeposit::get()) * 10u32.into();
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+/// Request membership for `who`, after funding them
+fn request<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	fund_account::<T>(who);
+	Pallet::<T>::request_membership(RawOrigin::Signed(who.clone()).into())?;
+	Ok(())
+}
+
+/// Add `who` to `AllMembers`, the way the `AddOrigin` would
+fn join_all_members<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	request::<T>(who)?;
+	Pallet::<T>::approve_request(T::AddOrigin::successful_origin(), who.clone())?;
+	Ok(())
+}
+
+/// Make `count` members the whole of `AllMembers`, all in the same group. The calls decode and
+/// search the whole vector, so their cost grows with it.
+fn populate<T: Config>(count: u32) {
+	let members: Vec<T::AccountId> = (0..count).map(|i| account("member", i, 0)).collect();
+	for (i, member) in members.iter().enumerate() {
+		MemberScore::<T>::insert(GROUP, member, i as u32);
+		GroupMembership::<T>::insert(member, GROUP);
+	}
+	let members = BoundedVec::try_from(members).expect("benchmarks stay within MaxMembers");
+	AllMembers::<T>::put(members);
+}
+
+fn join<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	join_all_members::<T>(who)?;
+	Pallet::<T>::join_a_group(RawOrigin::Signed(who.clone()).into(), GROUP, 0)?;
+	Ok(())
+}
+
+benchmarks! {
+	request_membership {
+		let m in 0 .. T::MaxMembers::get() - 1;
+		populate::<T>(m);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Pallet::<T>::request(&caller).is_some());
+	}
+
+	withdraw_request {
+		let caller: T::AccountId = whitelisted_caller();
+		request::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Pallet::<T>::request(&caller).is_none());
+	}
+
+	approve_request {
+		let m in 0 .. T::MaxMembers::get() - 1;
+		populate::<T>(m);
+		let applicant: T::AccountId = account("applicant", 0, 0);
+		request::<T>(&applicant)?;
+		let origin = T::AddOrigin::successful_origin();
+	}: {
+		Pallet::<T>::approve_request(origin, applicant.clone())?;
+	}
+	verify {
+		assert!(Pallet::<T>::all_members().contains(&applicant));
+	}
+
+	reject_request {
+		let applicant: T::AccountId = account("applicant", 0, 0);
+		request::<T>(&applicant)?;
+		let origin = T::AddOrigin::successful_origin();
+	}: {
+		Pallet::<T>::reject_request(origin, applicant.clone())?;
+	}
+	verify {
+		assert!(Pallet::<T>::request(&applicant).is_none());
+	}
+
+	// The vote that reaches the threshold, and admits the applicant
+	vote {
+		// Every vote counts, so there are enough members to reach the threshold
+		let m in T::VoteThreshold::get() .. T::MaxMembers::get() - 1;
+		populate::<T>(m);
+		let voter: T::AccountId = account("member", 0, 0);
+		let applicant: T::AccountId = account("applicant", 0, 0);
+		request::<T>(&applicant)?;
+		// Earlier votes from other members, who only count while they are members
+		let earlier_votes: Vec<T::AccountId> = (1..T::VoteThreshold::get())
+			.map(|i| account("member", i, 0))
+			.collect();
+		Requests::<T>::mutate(&applicant, |request| {
+			if let Some(request) = request {
+				request.votes = earlier_votes;
+			}
+		});
+	}: _(RawOrigin::Signed(voter), applicant.clone())
+	verify {
+		assert!(Pallet::<T>::all_members().contains(&applicant));
+	}
+
+	join_a_group {
+		let m in 0 .. T::MaxMembers::get() - 1;
+		populate::<T>(m);
+		let caller: T::AccountId = whitelisted_caller();
+		join_all_members::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), GROUP, 42)
+	verify {
+		assert_eq!(Pallet::<T>::member_score(GROUP, &caller), 42);
+	}
+
+	remove_member {
+		let m in 0 .. T::MaxMembers::get() - 1;
+		populate::<T>(m);
+		let caller: T::AccountId = whitelisted_caller();
+		join::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::member_score(GROUP, &caller), 0);
+	}
+
+	// Every other member shares the caller's group, so all their scores are removed
+	remove_group_score {
+		populate::<T>(T::MaxMembers::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		join::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), GROUP)
+	verify {
+		let member: T::AccountId = account("member", T::MaxMembers::get() - 2, 0);
+		assert_eq!(Pallet::<T>::member_score(GROUP, &member), 0);
+	}
+
+	kick_member {
+		let m in 1 .. T::MaxMembers::get();
+		populate::<T>(m);
+		let member: T::AccountId = account("member", m - 1, 0);
+		let origin = T::RemoveOrigin::successful_origin();
+	}: {
+		Pallet::<T>::kick_member(origin, member.clone())?;
+	}
+	verify {
+		assert!(!Pallet::<T>::all_members().contains(&member));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
@@ -3,24 +3,81 @@
 //! the first key might be a group identifier
 //! the second key might be a unique identifier
 //! `remove_prefix` enables clean removal of all values with the group identifier
+//!
+//! Joining `AllMembers` works as joining the `vec-set` pallet's set does, which documents the
+//! membership requests, deposits and votes. Like that set, `AllMembers` never grows past the
+//! runtime's `MaxMembers`, and the calls that search it are charged for its actual size.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
+use account_set::{AccountSet, OnMembershipChanged};
+pub use account_set::{BoundedVec, MembershipRequest};
+use frame_support::{dispatch::DispatchResult, traits::Currency};
+use sp_std::collections::btree_set::BTreeSet;
+
 pub use pallet::*;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
+type MembershipRequestOf<T> = MembershipRequest<AccountIdOf<T>, BalanceOf<T>>;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+	use crate::{
+		weights::WeightInfo, BalanceOf, BoundedVec, MembershipRequestOf, NegativeImbalanceOf,
+		OnMembershipChanged,
+	};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{OnUnbalanced, ReservableCurrency},
+	};
 	use frame_system::pallet_prelude::*;
-	use sp_std::vec::Vec;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// A maximum number of members. When `AllMembers` reaches this number, no new members may
+		/// join.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// The currency membership deposits are reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved while a membership request is pending
+		#[pallet::constant]
+		type MembershipDeposit: Get<BalanceOf<Self>>;
+
+		/// Handler for the deposits of rejected requests
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The origin that approves or rejects membership requests
+		type AddOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin that removes members against their will
+		type RemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of members' votes that admits an applicant
+		#[pallet::constant]
+		type VoteThreshold: Get<u32>;
+
+		/// Told whenever an account joins or leaves `AllMembers`
+		type MembershipChanged: OnMembershipChanged<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	pub type GroupIndex = u32; // this is Encode (which is necessary for double_map)
@@ -44,7 +101,13 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn all_members)]
-	pub(super) type AllMembers<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	pub(super) type AllMembers<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxMembers>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn request)]
+	pub(super) type Requests<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, MembershipRequestOf<T>, OptionQuery>;
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
@@ -65,57 +128,181 @@ pub mod pallet {
 		RemoveMember(T::AccountId),
 		/// Remove all members with GroupId
 		RemoveGroup(GroupIndex),
+		/// Requested membership
+		MembershipRequested(T::AccountId),
+		/// Withdrew a membership request
+		RequestWithdrawn(T::AccountId),
+		/// Rejected a membership request and slashed its deposit
+		RequestRejected(T::AccountId),
+		/// A member voted for an applicant (voter, applicant)
+		Voted(T::AccountId, T::AccountId),
+		/// Removed a member from `AllMembers` and their group
+		MemberKicked(T::AccountId),
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Join the `AllMembers` vec before joining a group
-		#[pallet::weight(10_000)]
-		pub fn join_all_members(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let new_member = ensure_signed(origin)?;
+		/// Ask to join the `AllMembers` vec, reserving the membership deposit
+		///
+		/// Charged for a full `AllMembers` up front, and refunded down to its actual size.
+		#[pallet::weight(T::WeightInfo::request_membership(T::MaxMembers::get()))]
+		pub fn request_membership(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let applicant = ensure_signed(origin)?;
+			let members = Self::all_members();
 			ensure!(
-				!Self::is_member(&new_member),
+				!members.contains(&applicant),
 				"already a member, can't join"
 			);
-			<AllMembers<T>>::append(&new_member);
+			ensure!(
+				(members.len() as u32) < T::MaxMembers::get(),
+				"membership limit reached"
+			);
+			ensure!(
+				!<Requests<T>>::contains_key(&applicant),
+				"already requested membership"
+			);
+
+			let request = MembershipRequestOf::<T>::reserve::<T::Currency>(
+				&applicant,
+				T::MembershipDeposit::get(),
+			)?;
+			<Requests<T>>::insert(&applicant, request);
+
+			Self::deposit_event(Event::MembershipRequested(applicant));
+			Ok(Some(T::WeightInfo::request_membership(members.len() as u32)).into())
+		}
+
+		/// Withdraw the caller's membership request, returning the deposit
+		#[pallet::weight(T::WeightInfo::withdraw_request())]
+		pub fn withdraw_request(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let applicant = ensure_signed(origin)?;
+			let request = <Requests<T>>::take(&applicant).ok_or("no membership request")?;
+			request.refund::<T::Currency>(&applicant);
 
-			Self::deposit_event(Event::NewMember(new_member));
+			Self::deposit_event(Event::RequestWithdrawn(applicant));
 			Ok(().into())
 		}
 
+		/// Admit an applicant to `AllMembers`, returning their deposit
+		///
+		/// Charged for a full `AllMembers` up front, and refunded down to its actual size.
+		#[pallet::weight(T::WeightInfo::approve_request(T::MaxMembers::get()))]
+		pub fn approve_request(
+			origin: OriginFor<T>,
+			applicant: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+			let request = <Requests<T>>::get(&applicant).ok_or("no membership request")?;
+			let members = Self::all_members();
+			let member_count = members.len() as u32;
+			Self::admit(members, applicant, request)?;
+			Ok(Some(T::WeightInfo::approve_request(member_count)).into())
+		}
+
+		/// Turn an applicant down, slashing their deposit
+		#[pallet::weight(T::WeightInfo::reject_request())]
+		pub fn reject_request(
+			origin: OriginFor<T>,
+			applicant: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+			let request = <Requests<T>>::take(&applicant).ok_or("no membership request")?;
+			request.slash::<T::Currency, T::Slash>(&applicant);
+
+			Self::deposit_event(Event::RequestRejected(applicant));
+			Ok(().into())
+		}
+
+		/// Vote, as a member, to admit an applicant. The applicant is admitted with the vote that
+		/// reaches the `VoteThreshold`.
+		///
+		/// Charged for a full `AllMembers` up front, and refunded down to its actual size.
+		#[pallet::weight(T::WeightInfo::vote(T::MaxMembers::get()))]
+		pub fn vote(origin: OriginFor<T>, applicant: T::AccountId) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+			let members = Self::all_members();
+			let member_count = members.len() as u32;
+			ensure!(members.contains(&voter), "not a member, can't vote");
+			let mut request = <Requests<T>>::get(&applicant).ok_or("no membership request")?;
+			let admitted = request
+				.vote(voter.clone(), T::VoteThreshold::get(), |v| {
+					members.contains(v)
+				})
+				.map_err(|_| "already voted")?;
+
+			if admitted {
+				Self::admit(members, applicant.clone(), request)?;
+			} else {
+				<Requests<T>>::insert(&applicant, request);
+			}
+			Self::deposit_event(Event::Voted(voter, applicant));
+			Ok(Some(T::WeightInfo::vote(member_count)).into())
+		}
+
+		/// Remove a member from `AllMembers` and their group, against their will
+		///
+		/// Charged for a full `AllMembers` up front, and refunded down to its actual size.
+		#[pallet::weight(T::WeightInfo::kick_member(T::MaxMembers::get()))]
+		pub fn kick_member(
+			origin: OriginFor<T>,
+			member_to_remove: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::RemoveOrigin::ensure_origin(origin)?;
+			let mut members = Self::all_members();
+			let member_count = members.len() as u32;
+			let index = members
+				.iter()
+				.position(|m| m == &member_to_remove)
+				.ok_or("not a member, can't remove")?;
+			members.remove(index);
+			<AllMembers<T>>::put(members);
+			let group_id = <GroupMembership<T>>::take(member_to_remove.clone());
+			<MemberScore<T>>::remove(&group_id, &member_to_remove);
+			T::MembershipChanged::on_member_removed(&member_to_remove);
+
+			Self::deposit_event(Event::MemberKicked(member_to_remove));
+			Ok(Some(T::WeightInfo::kick_member(member_count)).into())
+		}
+
 		/// Put MemberScore (for testing purposes)
-		#[pallet::weight(10_000)]
+		///
+		/// Charged for a full `AllMembers` up front, and refunded down to its actual size.
+		#[pallet::weight(T::WeightInfo::join_a_group(T::MaxMembers::get()))]
 		pub fn join_a_group(
 			origin: OriginFor<T>,
 			index: GroupIndex,
 			score: u32,
 		) -> DispatchResultWithPostInfo {
 			let member = ensure_signed(origin)?;
-			ensure!(Self::is_member(&member), "not a member, can't remove");
+			let members = Self::all_members();
+			ensure!(members.contains(&member), "not a member, can't remove");
 			<MemberScore<T>>::insert(&index, &member, score);
 			<GroupMembership<T>>::insert(&member, &index);
 
 			Self::deposit_event(Event::MemberJoinsGroup(member, index, score));
-			Ok(().into())
+			Ok(Some(T::WeightInfo::join_a_group(members.len() as u32)).into())
 		}
 
 		/// Remove a member
-		#[pallet::weight(10_000)]
+		///
+		/// Charged for a full `AllMembers` up front, and refunded down to its actual size.
+		#[pallet::weight(T::WeightInfo::remove_member(T::MaxMembers::get()))]
 		pub fn remove_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let member_to_remove = ensure_signed(origin)?;
+			let members = Self::all_members();
 			ensure!(
-				Self::is_member(&member_to_remove),
+				members.contains(&member_to_remove),
 				"not a member, can't remove"
 			);
 			let group_id = <GroupMembership<T>>::take(member_to_remove.clone());
 			<MemberScore<T>>::remove(&group_id, &member_to_remove);
 
 			Self::deposit_event(Event::RemoveMember(member_to_remove));
-			Ok(().into())
+			Ok(Some(T::WeightInfo::remove_member(members.len() as u32)).into())
 		}
 
 		/// Remove group score
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::remove_group_score())]
 		pub fn remove_group_score(
 			origin: OriginFor<T>,
 			group: GroupIndex,
@@ -143,4 +330,61 @@ impl<T: Config> Pallet<T> {
 	fn is_member(who: &T::AccountId) -> bool {
 		Self::all_members().contains(who)
 	}
+
+	// adds an applicant to `AllMembers`, unless it is full, and returns their deposit
+	fn admit(
+		members: BoundedVec<T::AccountId, T::MaxMembers>,
+		new_member: T::AccountId,
+		request: MembershipRequestOf<T>,
+	) -> DispatchResult {
+		Self::insert_member(members, &new_member)?;
+		<Requests<T>>::remove(&new_member);
+		request.refund::<T::Currency>(&new_member);
+
+		Self::deposit_event(Event::NewMember(new_member));
+		Ok(())
+	}
+
+	// appends a new member to `AllMembers`, unless it is full. Callers check that they aren't a
+	// member yet.
+	fn insert_member(
+		mut members: BoundedVec<T::AccountId, T::MaxMembers>,
+		new_member: &T::AccountId,
+	) -> DispatchResult {
+		members
+			.try_push(new_member.clone())
+			.map_err(|_| "membership limit reached")?;
+		<AllMembers<T>>::put(members);
+		T::MembershipChanged::on_member_added(new_member);
+		Ok(())
+	}
+}
+
+impl<T: Config> AccountSet for Pallet<T> {
+	type AccountId = T::AccountId;
+
+	fn accounts() -> BTreeSet<T::AccountId> {
+		Self::all_members().into_iter().collect::<BTreeSet<_>>()
+	}
+
+	fn contains(who: &T::AccountId) -> bool {
+		Self::is_member(who)
+	}
+
+	fn count() -> u32 {
+		Self::all_members().len() as u32
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &T::AccountId) -> DispatchResult {
+		let members = Self::all_members();
+		frame_support::ensure!(!members.contains(who), "already a member, can't join");
+		Self::insert_member(members, who)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_count() -> u32 {
+		use frame_support::traits::Get;
+		T::MaxMembers::get()
+	}
 }
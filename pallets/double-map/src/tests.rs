@@ -1,11 +1,15 @@
-use crate::{self as double_map, Config, GroupMembership, MemberScore};
+use crate::{self as double_map, weights::WeightInfo, Config, GroupMembership, MemberScore};
+use account_set::{AccountSet, OnMembershipChanged};
 use frame_support::{assert_noop, assert_ok, construct_runtime, parameter_types};
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
 type Block = frame_system::mocking::MockBlock<TestRuntime>;
@@ -17,6 +21,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		DoubleMap: double_map::{Module, Call, Storage, Event<T>},
 	}
 );
@@ -44,46 +49,234 @@ impl frame_system::Config for TestRuntime {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type MaxLocks = ();
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxMembers: u32 = 3;
+	pub const MembershipDeposit: u64 = 10;
+	pub const VoteThreshold: u32 = 2;
+}
 impl Config for TestRuntime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	type AddOrigin = EnsureRoot<u64>;
+	type RemoveOrigin = EnsureRoot<u64>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = RecordMembershipChanges;
+	type WeightInfo = ();
+}
+
+/// A membership change reported to the runtime
+#[derive(Clone, Debug, PartialEq)]
+pub enum MembershipChange {
+	Added(u64),
+	Removed(u64),
+}
+
+thread_local! {
+	static MEMBERSHIP_CHANGES: RefCell<Vec<MembershipChange>> = RefCell::new(Vec::new());
+}
+
+/// Records the membership changes the pallet reports, so the tests can check them
+pub struct RecordMembershipChanges;
+
+impl OnMembershipChanged<u64> for RecordMembershipChanges {
+	fn on_member_added(who: &u64) {
+		MEMBERSHIP_CHANGES.with(|c| c.borrow_mut().push(MembershipChange::Added(*who)));
+	}
+
+	fn on_member_removed(who: &u64) {
+		MEMBERSHIP_CHANGES.with(|c| c.borrow_mut().push(MembershipChange::Removed(*who)));
+	}
+}
+
+fn membership_changes() -> Vec<MembershipChange> {
+	MEMBERSHIP_CHANGES.with(|c| c.borrow().clone())
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
-		let storage = frame_system::GenesisConfig::default()
+		let mut storage = frame_system::GenesisConfig::default()
 			.build_storage::<TestRuntime>()
 			.unwrap();
+		// Accounts 1 to 4 can afford the membership deposit, account 5 can't
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 5)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
 		let mut ext = TestExternalities::from(storage);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
 }
 
+/// Request membership for `who`, and approve it
+fn join_all_members(who: u64) {
+	assert_ok!(DoubleMap::request_membership(Origin::signed(who)));
+	assert_ok!(DoubleMap::approve_request(Origin::root(), who));
+}
+
+#[test]
+fn request_membership_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(DoubleMap::request_membership(Origin::signed(1)));
+		// correct panic upon a second request
+		assert_noop!(
+			DoubleMap::request_membership(Origin::signed(1)),
+			"already requested membership"
+		);
+		// 5 can't afford the deposit
+		assert_noop!(
+			DoubleMap::request_membership(Origin::signed(5)),
+			pallet_balances::Error::<TestRuntime, _>::InsufficientBalance
+		);
+
+		// correct event emission
+		let expected_event = Event::double_map(double_map::Event::MembershipRequested(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+		// correct storage changes
+		assert_eq!(DoubleMap::request(1).map(|r| r.deposit), Some(10));
+		assert_eq!(Balances::reserved_balance(1), 10);
+	})
+}
+
+#[test]
+fn withdraw_request_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_noop!(
+			DoubleMap::withdraw_request(Origin::signed(1)),
+			"no membership request"
+		);
+		assert_ok!(DoubleMap::request_membership(Origin::signed(1)));
+		assert_ok!(DoubleMap::withdraw_request(Origin::signed(1)));
+
+		let expected_event = Event::double_map(double_map::Event::RequestWithdrawn(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+		assert_eq!(DoubleMap::request(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
+	})
+}
+
 #[test]
-fn join_all_members_works() {
+fn approve_request_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(DoubleMap::join_all_members(Origin::signed(1)));
+		assert_ok!(DoubleMap::request_membership(Origin::signed(1)));
+		assert_noop!(
+			DoubleMap::approve_request(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(DoubleMap::approve_request(Origin::root(), 1));
 		// correct panic upon existing member trying to join
 		assert_noop!(
-			DoubleMap::join_all_members(Origin::signed(1)),
+			DoubleMap::request_membership(Origin::signed(1)),
 			"already a member, can't join"
 		);
 
 		// correct event emission
 		let expected_event = Event::double_map(double_map::Event::NewMember(1));
-
-		assert_eq!(System::events()[0].event, expected_event,);
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 		// correct storage changes
 		assert_eq!(DoubleMap::all_members(), vec![1]);
+		assert_eq!(DoubleMap::request(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
+	})
+}
+
+#[test]
+fn reject_request_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(DoubleMap::request_membership(Origin::signed(1)));
+		assert_noop!(
+			DoubleMap::reject_request(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(DoubleMap::reject_request(Origin::root(), 1));
+
+		let expected_event = Event::double_map(double_map::Event::RequestRejected(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+		// the deposit is slashed
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(DoubleMap::all_members().is_empty());
+	})
+}
+
+#[test]
+fn votes_admit_applicants() {
+	ExternalityBuilder::build().execute_with(|| {
+		join_all_members(1);
+		join_all_members(2);
+		assert_ok!(DoubleMap::request_membership(Origin::signed(3)));
+
+		// expected panics
+		assert_noop!(
+			DoubleMap::vote(Origin::signed(4), 3),
+			"not a member, can't vote"
+		);
+		assert_noop!(
+			DoubleMap::vote(Origin::signed(1), 4),
+			"no membership request"
+		);
+
+		assert_ok!(DoubleMap::vote(Origin::signed(1), 3));
+		assert_noop!(DoubleMap::vote(Origin::signed(1), 3), "already voted");
+		assert_eq!(DoubleMap::all_members(), vec![1, 2]);
+
+		// the second vote reaches the threshold
+		assert_ok!(DoubleMap::vote(Origin::signed(2), 3));
+		let expected_event = Event::double_map(double_map::Event::Voted(2, 3));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+		assert_eq!(DoubleMap::all_members(), vec![1, 2, 3]);
+		assert_eq!(Balances::free_balance(3), 100);
+	})
+}
+
+#[test]
+fn kick_member_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		join_all_members(1);
+		assert_ok!(DoubleMap::join_a_group(Origin::signed(1), 3, 5));
+
+		assert_noop!(
+			DoubleMap::kick_member(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(DoubleMap::kick_member(Origin::root(), 1));
+
+		let expected_event = Event::double_map(double_map::Event::MemberKicked(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+		assert!(DoubleMap::all_members().is_empty());
+		assert!(!<GroupMembership<TestRuntime>>::contains_key(1));
+		assert!(!<MemberScore<TestRuntime>>::contains_key(3, 1));
+
+		assert_noop!(
+			DoubleMap::kick_member(Origin::root(), 1),
+			"not a member, can't remove"
+		);
 	})
 }
 
@@ -96,13 +289,13 @@ fn group_join_works() {
 			"not a member, can't remove"
 		);
 
-		assert_ok!(DoubleMap::join_all_members(Origin::signed(1)));
+		join_all_members(1);
 		assert_ok!(DoubleMap::join_a_group(Origin::signed(1), 3, 5));
 
 		// correct event emission
 		let expected_event = Event::double_map(double_map::Event::MemberJoinsGroup(1, 3, 5));
 
-		assert_eq!(System::events()[1].event, expected_event,);
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 
 		// correct storage changes
 		assert_eq!(DoubleMap::group_membership(1), 3);
@@ -114,7 +307,7 @@ fn group_join_works() {
 fn remove_member_works() {
 	ExternalityBuilder::build().execute_with(|| {
 		// action: user 1 joins
-		assert_ok!(DoubleMap::join_all_members(Origin::signed(1)));
+		join_all_members(1);
 		// action: user 1 joins group 3 with score 5
 		assert_ok!(DoubleMap::join_a_group(Origin::signed(1), 3, 5));
 		// action: remove user 1
@@ -123,7 +316,7 @@ fn remove_member_works() {
 		// check: correct event emitted
 		let expected_event = Event::double_map(double_map::Event::RemoveMember(1));
 
-		assert_eq!(System::events()[2].event, expected_event,);
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 
 		// check: user 1 should no longer belongs to group 3
 		assert!(!<GroupMembership<TestRuntime>>::contains_key(1));
@@ -134,9 +327,9 @@ fn remove_member_works() {
 #[test]
 fn remove_group_score_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(DoubleMap::join_all_members(Origin::signed(1)));
-		assert_ok!(DoubleMap::join_all_members(Origin::signed(2)));
-		assert_ok!(DoubleMap::join_all_members(Origin::signed(3)));
+		join_all_members(1);
+		join_all_members(2);
+		join_all_members(3);
 		assert_ok!(DoubleMap::join_a_group(Origin::signed(1), 3, 5));
 		assert_ok!(DoubleMap::join_a_group(Origin::signed(2), 3, 5));
 		assert_ok!(DoubleMap::join_a_group(Origin::signed(3), 3, 5));
@@ -156,7 +349,7 @@ fn remove_group_score_works() {
 		// correct event emitted
 		let expected_event = Event::double_map(double_map::Event::RemoveGroup(3));
 
-		assert_eq!(System::events()[6].event, expected_event,);
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 
 		// check: user 1, 2, 3 should no longer in the group
 		assert!(!<MemberScore<TestRuntime>>::contains_key(3, 1));
@@ -164,3 +357,123 @@ fn remove_group_score_works() {
 		assert!(!<MemberScore<TestRuntime>>::contains_key(3, 3));
 	})
 }
+
+#[test]
+fn membership_changes_are_reported() {
+	ExternalityBuilder::build().execute_with(|| {
+		join_all_members(1);
+		join_all_members(2);
+		assert_ok!(DoubleMap::request_membership(Origin::signed(3)));
+		assert_ok!(DoubleMap::vote(Origin::signed(1), 3));
+		assert_ok!(DoubleMap::vote(Origin::signed(2), 3));
+		assert_ok!(DoubleMap::kick_member(Origin::root(), 2));
+		assert_ok!(DoubleMap::join_a_group(Origin::signed(3), 1, 5));
+		// Leaving a group keeps 3 in `AllMembers`, so it isn't reported
+		assert_ok!(DoubleMap::remove_member(Origin::signed(3)));
+
+		// Failed calls don't report anything
+		assert_noop!(
+			DoubleMap::kick_member(Origin::root(), 2),
+			"not a member, can't remove"
+		);
+
+		assert_eq!(
+			membership_changes(),
+			vec![
+				MembershipChange::Added(1),
+				MembershipChange::Added(2),
+				MembershipChange::Added(3),
+				MembershipChange::Removed(2),
+			]
+		);
+	})
+}
+
+#[test]
+fn account_set_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert!(!<DoubleMap as AccountSet>::contains(&1));
+		assert_eq!(<DoubleMap as AccountSet>::count(), 0);
+
+		join_all_members(3);
+		join_all_members(1);
+
+		assert!(<DoubleMap as AccountSet>::contains(&1));
+		assert!(<DoubleMap as AccountSet>::contains(&3));
+		assert!(!<DoubleMap as AccountSet>::contains(&2));
+		assert_eq!(<DoubleMap as AccountSet>::count(), 2);
+		assert_eq!(
+			<DoubleMap as AccountSet>::accounts()
+				.into_iter()
+				.collect::<Vec<_>>(),
+			vec![1, 3]
+		);
+	})
+}
+
+#[test]
+fn votes_of_departed_members_dont_count() {
+	ExternalityBuilder::build().execute_with(|| {
+		join_all_members(1);
+		join_all_members(2);
+		join_all_members(3);
+		assert_ok!(DoubleMap::request_membership(Origin::signed(4)));
+		assert_ok!(DoubleMap::vote(Origin::signed(1), 4));
+
+		// 1 leaves, so their vote is dropped and 2's vote doesn't reach the threshold
+		assert_ok!(DoubleMap::kick_member(Origin::root(), 1));
+		assert_ok!(DoubleMap::vote(Origin::signed(2), 4));
+		assert_eq!(DoubleMap::request(4).map(|r| r.votes), Some(vec![2]));
+
+		assert_ok!(DoubleMap::vote(Origin::signed(3), 4));
+		assert_eq!(DoubleMap::request(4), None);
+		assert!(<DoubleMap as AccountSet>::contains(&4));
+	})
+}
+
+#[test]
+fn cant_exceed_max_members() {
+	ExternalityBuilder::build().execute_with(|| {
+		join_all_members(1);
+		join_all_members(2);
+		assert_ok!(DoubleMap::request_membership(Origin::signed(3)));
+		assert_ok!(DoubleMap::request_membership(Origin::signed(4)));
+		assert_ok!(DoubleMap::approve_request(Origin::root(), 3));
+
+		// `AllMembers` is full, so 4 can't be admitted, and nobody else can ask
+		assert_noop!(
+			DoubleMap::approve_request(Origin::root(), 4),
+			"membership limit reached"
+		);
+		assert_ok!(DoubleMap::vote(Origin::signed(1), 4));
+		assert_noop!(
+			DoubleMap::vote(Origin::signed(2), 4),
+			"membership limit reached"
+		);
+		assert_ok!(DoubleMap::withdraw_request(Origin::signed(4)));
+		assert_noop!(
+			DoubleMap::request_membership(Origin::signed(4)),
+			"membership limit reached"
+		);
+	})
+}
+
+#[test]
+fn calls_are_charged_for_the_current_members() {
+	ExternalityBuilder::build().execute_with(|| {
+		join_all_members(1);
+
+		assert_ok!(DoubleMap::request_membership(Origin::signed(2)));
+		let post_info = DoubleMap::approve_request(Origin::root(), 2).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::approve_request(1))
+		);
+
+		let post_info = DoubleMap::kick_member(Origin::root(), 2).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::kick_member(2))
+		);
+	})
+}
@@ -0,0 +1,36 @@
+//! Benchmarks for the fixed point pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_arithmetic::Permill;
+use substrate_fixed::types::U16F16;
+
+benchmarks! {
+	update_permill {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), Permill::from_percent(50))
+	verify {
+		assert_eq!(Pallet::<T>::permill_value(), Permill::from_percent(50));
+	}
+
+	update_fixed {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), U16F16::from_num(3) / 2)
+	verify {
+		assert_eq!(Pallet::<T>::fixed_value(), U16F16::from_num(3) / 2);
+	}
+
+	update_manual {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 1 << 15)
+	verify {
+		assert_eq!(Pallet::<T>::manual_value(), 1 << 15);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
@@ -19,8 +19,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 	use sp_arithmetic::{traits::Saturating, Permill};
@@ -30,6 +35,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::type_value]
@@ -90,7 +98,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Update the Permill accumulator implementation's value by multiplying it
 		/// by the new factor given in the extrinsic
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::update_permill())]
 		pub fn update_permill(
 			origin: OriginFor<T>,
 			new_factor: Permill,
@@ -113,7 +121,7 @@ pub mod pallet {
 
 		/// Update the Substrate-fixed accumulator implementation's value by multiplying it
 		/// by the new factor given in the extrinsic
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::update_fixed())]
 		pub fn update_fixed(
 			origin: OriginFor<T>,
 			new_factor: U16F16,
@@ -137,7 +145,7 @@ pub mod pallet {
 
 		/// Update the manually-implemented accumulator's value by multiplying it
 		/// by the new factor given in the extrinsic
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::update_manual())]
 		pub fn update_manual(origin: OriginFor<T>, new_factor: u32) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
 
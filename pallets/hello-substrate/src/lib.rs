@@ -10,14 +10,22 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 	use sp_runtime::print;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config {
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
@@ -29,7 +37,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Increase the value associated with a particular key
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::say_hello())]
 		pub fn say_hello(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			// Ensure that the caller is a regular keypair account
 			let caller = ensure_signed(origin)?;
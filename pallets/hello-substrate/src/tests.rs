@@ -54,9 +54,11 @@ impl frame_system::Config for TestRuntime {
 	type SS58Prefix = ();
 }
 
-impl Config for TestRuntime {}
+impl Config for TestRuntime {
+	type WeightInfo = ();
+}
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
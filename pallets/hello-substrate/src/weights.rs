@@ -0,0 +1,41 @@
+//! Weights for hello_substrate
+
+// ./target/release/kitchen-node benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=hello_substrate
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/frame-weight-template.hbs
+// --output=./pallets/hello-substrate/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for hello_substrate.
+pub trait WeightInfo {
+	fn say_hello() -> Weight;
+}
+
+/// Weights for hello_substrate using the recipes' kitchen node.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn say_hello() -> Weight {
+		(12_000_000 as Weight)
+	}
+}
+
+// For tests and runtimes that don't care about weights
+impl WeightInfo for () {
+	fn say_hello() -> Weight {
+		(12_000_000 as Weight)
+	}
+}
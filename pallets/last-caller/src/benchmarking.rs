@@ -0,0 +1,14 @@
+//! Benchmarks for the last caller pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	call {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Caller::<T>::get(), caller);
+	}
+}
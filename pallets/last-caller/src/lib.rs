@@ -4,8 +4,13 @@
 #![allow(clippy::unused_unit)]
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
@@ -15,6 +20,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -38,7 +46,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		// The only dispatchable call, updates the single storage item,
 		// and emits an event.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::call())]
 		fn call(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let caller = ensure_signed(origin)?;
 
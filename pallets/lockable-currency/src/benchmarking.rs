@@ -0,0 +1,31 @@
+//! Benchmarks for the lockable currency pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, UnfilteredDispatchable};
+use frame_system::RawOrigin;
+
+/// Give the whitelisted caller funds to lock
+fn funded_caller<T: Config>() -> (T::AccountId, BalanceOf<T>) {
+	let caller: T::AccountId = whitelisted_caller();
+	let amount = T::Currency::minimum_balance() * 10u32.into();
+	T::Currency::make_free_balance_be(&caller, amount * 2u32.into());
+	(caller, amount)
+}
+
+benchmarks! {
+	lock_capital {
+		let (caller, amount) = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller), amount)
+
+	extend_lock {
+		let (caller, amount) = funded_caller::<T>();
+		// The calls are private, so the lock is set up by dispatching one
+		Call::<T>::lock_capital(amount).dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+	}: _(RawOrigin::Signed(caller), amount * 2u32.into())
+
+	unlock_all {
+		let (caller, amount) = funded_caller::<T>();
+		Call::<T>::lock_capital(amount).dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+	}: _(RawOrigin::Signed(caller))
+}
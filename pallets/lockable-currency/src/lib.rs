@@ -1,17 +1,23 @@
 //! A pallet to demonstrate the `LockableCurrency` trait
 //! borrows collateral locking logic from pallet_staking
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons};
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
 	const EXAMPLE_ID: LockIdentifier = *b"example ";
 
-	type BalanceOf<T> =
+	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::config]
@@ -21,6 +27,9 @@ pub mod pallet {
 
 		/// The overarching event type
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -42,7 +51,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Locks the specified amount of tokens from the caller
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::lock_capital())]
 		fn lock_capital(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let user = ensure_signed(origin)?;
 
@@ -53,7 +62,7 @@ pub mod pallet {
 		}
 
 		/// Extends the lock period
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::extend_lock())]
 		fn extend_lock(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let user = ensure_signed(origin)?;
 
@@ -64,7 +73,7 @@ pub mod pallet {
 		}
 
 		/// Releases all locked tokens
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::unlock_all())]
 		fn unlock_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let user = ensure_signed(origin)?;
 
@@ -0,0 +1,130 @@
+//! Benchmarks for the map set pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+/// `count` accounts to fill the set with
+fn accounts<T: Config>(count: u32) -> Vec<T::AccountId> {
+	(0..count).map(|i| account("member", i, 0)).collect()
+}
+
+/// Make `members` the whole set. Map lookups hardly depend on the size of the set, which is what
+/// these benchmarks measure against the vec set's.
+fn set_members<T: Config>(members: Vec<T::AccountId>) {
+	MemberCount::<T>::put(members.len() as u32);
+	for member in members {
+		Members::<T>::insert(&member, ());
+	}
+}
+
+/// Give `who` enough funds to reserve the membership deposit
+fn fund_account<T: Config>(who: &T::AccountId) {
+	let amount = (T::Currency::minimum_balance() + T::MembershipDeposit::get()) * 10u32.into();
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+/// Request membership for `who`, after funding them
+fn request<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	fund_account::<T>(who);
+	Pallet::<T>::request_membership(RawOrigin::Signed(who.clone()).into())?;
+	Ok(())
+}
+
+// The components and their ranges match the vec set's benchmarks, so the two layouts can be
+// compared directly.
+benchmarks! {
+	request_membership {
+		let m in 0 .. T::MaxMembers::get() - 1;
+		set_members::<T>(accounts::<T>(m));
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Requests::<T>::contains_key(&caller));
+	}
+
+	withdraw_request {
+		let caller: T::AccountId = whitelisted_caller();
+		request::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Requests::<T>::contains_key(&caller));
+	}
+
+	approve_request {
+		let m in 0 .. T::MaxMembers::get() - 1;
+		set_members::<T>(accounts::<T>(m));
+		let applicant: T::AccountId = account("applicant", 0, 0);
+		request::<T>(&applicant)?;
+		let origin = T::AddOrigin::successful_origin();
+	}: {
+		Pallet::<T>::approve_request(origin, applicant.clone())?;
+	}
+	verify {
+		assert!(Members::<T>::contains_key(&applicant));
+	}
+
+	reject_request {
+		let applicant: T::AccountId = account("applicant", 0, 0);
+		request::<T>(&applicant)?;
+		let origin = T::AddOrigin::successful_origin();
+	}: {
+		Pallet::<T>::reject_request(origin, applicant.clone())?;
+	}
+	verify {
+		assert!(!Requests::<T>::contains_key(&applicant));
+	}
+
+	// The vote that reaches the threshold, and admits the applicant
+	vote {
+		// Every vote counts, so there are enough members to reach the threshold
+		let m in T::VoteThreshold::get() .. T::MaxMembers::get() - 1;
+		let members = accounts::<T>(m);
+		let voter = members[0].clone();
+		let earlier_votes = members[1..T::VoteThreshold::get() as usize].to_vec();
+		set_members::<T>(members);
+		let applicant: T::AccountId = account("applicant", 0, 0);
+		request::<T>(&applicant)?;
+		Requests::<T>::mutate(&applicant, |request| {
+			if let Some(request) = request {
+				request.votes = earlier_votes;
+			}
+		});
+	}: _(RawOrigin::Signed(voter), applicant.clone())
+	verify {
+		assert!(Members::<T>::contains_key(&applicant));
+	}
+
+	remove_member {
+		let m in 1 .. T::MaxMembers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let mut members = accounts::<T>(m - 1);
+		members.push(caller.clone());
+		set_members::<T>(members);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Members::<T>::contains_key(&caller));
+	}
+
+	kick_member {
+		let m in 1 .. T::MaxMembers::get();
+		let members = accounts::<T>(m);
+		let old_member = members[0].clone();
+		set_members::<T>(members);
+		let origin = T::RemoveOrigin::successful_origin();
+	}: {
+		Pallet::<T>::kick_member(origin, old_member.clone())?;
+	}
+	verify {
+		assert!(!Members::<T>::contains_key(&old_member));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
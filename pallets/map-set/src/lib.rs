@@ -2,28 +2,81 @@
 #![allow(clippy::unused_unit)]
 //! A pallet that implements a storage set on top of a storage map and demonstrates performance
 //! tradeoffs when using vec sets.
+//!
+//! The set never grows past the runtime's `MaxMembers`, and the calls are charged for the number of
+//! members it actually holds.
+//!
+//! Joining works exactly as in the `vec-set` pallet, which documents the membership requests,
+//! deposits and votes. Only the storage of the set differs.
 
-use account_set::AccountSet;
-use frame_support::storage::IterableStorageMap;
+pub use account_set::MembershipRequest;
+use account_set::{AccountSet, OnMembershipChanged};
+use frame_support::{storage::IterableStorageMap, traits::Currency};
 use sp_std::collections::btree_set::BTreeSet;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 pub use pallet::*;
 
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
+type MembershipRequestOf<T> = MembershipRequest<AccountIdOf<T>, BalanceOf<T>>;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+	use crate::{
+		weights::WeightInfo, BalanceOf, MembershipRequestOf, NegativeImbalanceOf,
+		OnMembershipChanged,
+	};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{OnUnbalanced, ReservableCurrency},
+	};
 	use frame_system::pallet_prelude::*;
 
-	/// A maximum number of members. When membership reaches this number, no new members may join.
-	pub const MAX_MEMBERS: u32 = 16;
-
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// A maximum number of members. When membership reaches this number, no new members may
+		/// join.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// The currency membership deposits are reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved while a membership request is pending
+		#[pallet::constant]
+		type MembershipDeposit: Get<BalanceOf<Self>>;
+
+		/// Handler for the deposits of rejected requests
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The origin that approves or rejects membership requests
+		type AddOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin that removes members against their will
+		type RemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of members' votes that admits an applicant
+		#[pallet::constant]
+		type VoteThreshold: Get<u32>;
+
+		/// Told whenever an account joins or leaves the set
+		type MembershipChanged: OnMembershipChanged<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -34,6 +87,11 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type MemberCount<T> = StorageValue<_, u32, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn request)]
+	pub(super) type Requests<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, MembershipRequestOf<T>, OptionQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
@@ -49,6 +107,14 @@ pub mod pallet {
 		MemberAdded(T::AccountId),
 		/// Removed a member
 		MemberRemoved(T::AccountId),
+		/// Requested membership
+		MembershipRequested(T::AccountId),
+		/// Withdrew a membership request
+		RequestWithdrawn(T::AccountId),
+		/// Rejected a membership request and slashed its deposit
+		RequestRejected(T::AccountId),
+		/// A member voted for an applicant (voter, applicant)
+		Voted(T::AccountId, T::AccountId),
 	}
 
 	#[pallet::error]
@@ -59,18 +125,164 @@ pub mod pallet {
 		NotMember,
 		/// Cannot add another member because the limit is already reached
 		MembershipLimitReached,
+		/// Cannot request membership because a request is already pending
+		AlreadyRequested,
+		/// There is no pending membership request for this account
+		NoRequest,
+		/// Cannot vote for the same applicant twice
+		AlreadyVoted,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Adds a member to the membership set
-		#[pallet::weight(10_000)]
-		pub fn add_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let new_member = ensure_signed(origin)?;
+		/// Asks to join the membership set, reserving the membership deposit
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::request_membership(T::MaxMembers::get()))]
+		pub fn request_membership(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let applicant = ensure_signed(origin)?;
+
+			ensure!(
+				!Members::<T>::contains_key(&applicant),
+				Error::<T>::AlreadyMember
+			);
+			let member_count = MemberCount::<T>::get();
+			ensure!(
+				member_count < T::MaxMembers::get(),
+				Error::<T>::MembershipLimitReached
+			);
+			ensure!(
+				!Requests::<T>::contains_key(&applicant),
+				Error::<T>::AlreadyRequested
+			);
+
+			let request = MembershipRequestOf::<T>::reserve::<T::Currency>(
+				&applicant,
+				T::MembershipDeposit::get(),
+			)?;
+			Requests::<T>::insert(&applicant, request);
+
+			Self::deposit_event(Event::MembershipRequested(applicant));
+			Ok(Some(T::WeightInfo::request_membership(member_count)).into())
+		}
+
+		/// Withdraws the caller's pending membership request, returning the deposit
+		#[pallet::weight(T::WeightInfo::withdraw_request())]
+		pub fn withdraw_request(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let applicant = ensure_signed(origin)?;
+
+			let request = Requests::<T>::take(&applicant).ok_or(Error::<T>::NoRequest)?;
+			request.refund::<T::Currency>(&applicant);
+
+			Self::deposit_event(Event::RequestWithdrawn(applicant));
+			Ok(().into())
+		}
+
+		/// Admits an applicant, returning their deposit
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::approve_request(T::MaxMembers::get()))]
+		pub fn approve_request(
+			origin: OriginFor<T>,
+			applicant: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+
+			let request = Requests::<T>::get(&applicant).ok_or(Error::<T>::NoRequest)?;
+			let member_count = Self::admit(applicant, request)?;
+			Ok(Some(T::WeightInfo::approve_request(member_count)).into())
+		}
+
+		/// Turns an applicant down, slashing their deposit
+		#[pallet::weight(T::WeightInfo::reject_request())]
+		pub fn reject_request(
+			origin: OriginFor<T>,
+			applicant: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+
+			let request = Requests::<T>::take(&applicant).ok_or(Error::<T>::NoRequest)?;
+			request.slash::<T::Currency, T::Slash>(&applicant);
+
+			Self::deposit_event(Event::RequestRejected(applicant));
+			Ok(().into())
+		}
+
+		/// Votes, as a member, to admit an applicant. The applicant is admitted with the vote that
+		/// reaches the `VoteThreshold`.
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::vote(T::MaxMembers::get()))]
+		pub fn vote(origin: OriginFor<T>, applicant: T::AccountId) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+
+			ensure!(Members::<T>::contains_key(&voter), Error::<T>::NotMember);
+			let member_count = MemberCount::<T>::get();
+
+			let mut request = Requests::<T>::get(&applicant).ok_or(Error::<T>::NoRequest)?;
+			let admitted = request
+				.vote(
+					voter.clone(),
+					T::VoteThreshold::get(),
+					Members::<T>::contains_key,
+				)
+				.map_err(|_| Error::<T>::AlreadyVoted)?;
+
+			if admitted {
+				Self::admit(applicant.clone(), request)?;
+			} else {
+				Requests::<T>::insert(&applicant, request);
+			}
+			Self::deposit_event(Event::Voted(voter, applicant));
+			Ok(Some(T::WeightInfo::vote(member_count)).into())
+		}
+
+		/// Removes the caller from the membership set
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::remove_member(T::MaxMembers::get()))]
+		pub fn remove_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let old_member = ensure_signed(origin)?;
+
+			let member_count = Self::evict(old_member)?;
+			Ok(Some(T::WeightInfo::remove_member(member_count)).into())
+		}
+
+		/// Removes a member from the membership set against their will
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::kick_member(T::MaxMembers::get()))]
+		pub fn kick_member(
+			origin: OriginFor<T>,
+			old_member: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::RemoveOrigin::ensure_origin(origin)?;
+
+			let member_count = Self::evict(old_member)?;
+			Ok(Some(T::WeightInfo::kick_member(member_count)).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Adds an applicant to the set, and resolves their request by returning the deposit.
+		/// Returns the number of members before the addition.
+		fn admit(
+			new_member: T::AccountId,
+			request: MembershipRequestOf<T>,
+		) -> Result<u32, DispatchError> {
+			let member_count = Self::insert_member(&new_member)?;
+			Requests::<T>::remove(&new_member);
+			request.refund::<T::Currency>(&new_member);
+			Self::deposit_event(Event::MemberAdded(new_member));
+			Ok(member_count)
+		}
 
+		/// Inserts a new member into the set, unless they are already in it or it is full, and
+		/// returns the number of members before the insertion.
+		pub(super) fn insert_member(new_member: &T::AccountId) -> Result<u32, DispatchError> {
 			let member_count = MemberCount::<T>::get();
 			ensure!(
-				member_count < MAX_MEMBERS,
+				member_count < T::MaxMembers::get(),
 				Error::<T>::MembershipLimitReached
 			);
 
@@ -78,31 +290,29 @@ pub mod pallet {
 			// member is already present in the list. Because the membership is stored as a hash
 			// map this check is constant time O(1)
 			ensure!(
-				!Members::<T>::contains_key(&new_member),
+				!Members::<T>::contains_key(new_member),
 				Error::<T>::AlreadyMember
 			);
 
-			// Insert the new member and emit the event
-			Members::<T>::insert(&new_member, ());
+			Members::<T>::insert(new_member, ());
 			MemberCount::<T>::put(member_count + 1); // overflow check not necessary because of maximum
-			Self::deposit_event(Event::MemberAdded(new_member));
-			Ok(().into())
+			T::MembershipChanged::on_member_added(new_member);
+			Ok(member_count)
 		}
 
-		/// Removes a member.
-		#[pallet::weight(10_000)]
-		pub fn remove_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let old_member = ensure_signed(origin)?;
-
+		/// Removes a member from the set, and returns the number of members before the removal.
+		fn evict(old_member: T::AccountId) -> Result<u32, DispatchError> {
 			ensure!(
 				Members::<T>::contains_key(&old_member),
 				Error::<T>::NotMember
 			);
 
+			let member_count = MemberCount::<T>::get();
 			Members::<T>::remove(&old_member);
-			MemberCount::<T>::mutate(|v| *v -= 1);
+			MemberCount::<T>::put(member_count - 1);
+			T::MembershipChanged::on_member_removed(&old_member);
 			Self::deposit_event(Event::MemberRemoved(old_member));
-			Ok(().into())
+			Ok(member_count)
 		}
 	}
 }
@@ -115,4 +325,23 @@ impl<T: Config> AccountSet for Module<T> {
 			.map(|(acct, _)| acct)
 			.collect::<BTreeSet<_>>()
 	}
+
+	fn contains(who: &T::AccountId) -> bool {
+		Members::<T>::contains_key(who)
+	}
+
+	fn count() -> u32 {
+		MemberCount::<T>::get()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &T::AccountId) -> frame_support::dispatch::DispatchResult {
+		Self::insert_member(who).map(|_| ())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_count() -> u32 {
+		use frame_support::traits::Get;
+		T::MaxMembers::get()
+	}
 }
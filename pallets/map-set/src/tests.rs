@@ -1,11 +1,15 @@
-use crate::{self as map_set, *};
+use crate::{self as map_set, weights::WeightInfo, *};
+use account_set::{AccountSet, OnMembershipChanged};
 use frame_support::{assert_noop, assert_ok, construct_runtime, parameter_types};
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
 type Block = frame_system::mocking::MockBlock<TestRuntime>;
@@ -17,6 +21,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		MapSet: map_set::{Module, Call, Storage, Event<T>},
 	}
 );
@@ -44,51 +49,252 @@ impl frame_system::Config for TestRuntime {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type MaxLocks = ();
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxMembers: u32 = 16;
+	pub const MembershipDeposit: u64 = 10;
+	pub const VoteThreshold: u32 = 2;
+}
 impl Config for TestRuntime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	type AddOrigin = EnsureRoot<u64>;
+	type RemoveOrigin = EnsureRoot<u64>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = RecordMembershipChanges;
+	type WeightInfo = ();
+}
+
+/// A membership change reported to the runtime
+#[derive(Clone, Debug, PartialEq)]
+pub enum MembershipChange {
+	Added(u64),
+	Removed(u64),
+}
+
+thread_local! {
+	static MEMBERSHIP_CHANGES: RefCell<Vec<MembershipChange>> = RefCell::new(Vec::new());
+}
+
+/// Records the membership changes the pallet reports, so the tests can check them
+pub struct RecordMembershipChanges;
+
+impl OnMembershipChanged<u64> for RecordMembershipChanges {
+	fn on_member_added(who: &u64) {
+		MEMBERSHIP_CHANGES.with(|c| c.borrow_mut().push(MembershipChange::Added(*who)));
+	}
+
+	fn on_member_removed(who: &u64) {
+		MEMBERSHIP_CHANGES.with(|c| c.borrow_mut().push(MembershipChange::Removed(*who)));
+	}
+}
+
+fn membership_changes() -> Vec<MembershipChange> {
+	MEMBERSHIP_CHANGES.with(|c| c.borrow().clone())
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
-		let storage = frame_system::GenesisConfig::default()
+		let mut storage = frame_system::GenesisConfig::default()
 			.build_storage::<TestRuntime>()
 			.unwrap();
+		// Accounts 0 to 19 can afford the membership deposit, account 20 can't
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: (0..20).map(|i| (i, 100)).chain(Some((20, 5))).collect(),
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
 		let mut ext = TestExternalities::from(storage);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
 }
 
+/// The members, in order
+fn members() -> Vec<u64> {
+	MapSet::accounts().into_iter().collect()
+}
+
+/// Request membership for `who`, and approve it
+fn add_member(who: u64) {
+	assert_ok!(MapSet::request_membership(Origin::signed(who)));
+	assert_ok!(MapSet::approve_request(Origin::root(), who));
+}
+
+#[test]
+fn request_membership_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(MapSet::request_membership(Origin::signed(1)));
+
+		let expected_event = Event::map_set(map_set::Event::MembershipRequested(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(MapSet::request(1).map(|r| r.deposit), Some(10));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(members(), Vec::<u64>::new());
+	})
+}
+
+#[test]
+fn request_membership_handles_errors() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(MapSet::request_membership(Origin::signed(1)));
+		assert_noop!(
+			MapSet::request_membership(Origin::signed(1)),
+			Error::<TestRuntime>::AlreadyRequested
+		);
+
+		assert_ok!(MapSet::approve_request(Origin::root(), 1));
+		assert_noop!(
+			MapSet::request_membership(Origin::signed(1)),
+			Error::<TestRuntime>::AlreadyMember
+		);
+
+		// 20 can't afford the deposit
+		assert_noop!(
+			MapSet::request_membership(Origin::signed(20)),
+			pallet_balances::Error::<TestRuntime, _>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn withdraw_request_returns_the_deposit() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(MapSet::request_membership(Origin::signed(1)));
+		assert_ok!(MapSet::withdraw_request(Origin::signed(1)));
+
+		let expected_event = Event::map_set(map_set::Event::RequestWithdrawn(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(MapSet::request(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		assert_noop!(
+			MapSet::withdraw_request(Origin::signed(1)),
+			Error::<TestRuntime>::NoRequest
+		);
+	})
+}
+
 #[test]
-fn add_member_works() {
+fn approve_request_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(MapSet::add_member(Origin::signed(1)));
+		assert_ok!(MapSet::request_membership(Origin::signed(1)));
+		assert_ok!(MapSet::approve_request(Origin::root(), 1));
 
 		let expected_event = Event::map_set(map_set::Event::MemberAdded(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 
-		assert_eq!(System::events()[0].event, expected_event,);
+		assert_eq!(members(), vec![1]);
+		assert_eq!(MapSet::request(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
+	})
+}
 
-		assert!(<Members<TestRuntime>>::contains_key(1));
+#[test]
+fn approve_request_handles_errors() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(MapSet::request_membership(Origin::signed(1)));
+		assert_noop!(
+			MapSet::approve_request(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+
+		assert_noop!(
+			MapSet::approve_request(Origin::root(), 2),
+			Error::<TestRuntime>::NoRequest
+		);
 	})
 }
 
 #[test]
-fn cant_add_duplicate_members() {
+fn reject_request_slashes_the_deposit() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(MapSet::add_member(Origin::signed(1)));
+		assert_ok!(MapSet::request_membership(Origin::signed(1)));
+		assert_noop!(
+			MapSet::reject_request(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(MapSet::reject_request(Origin::root(), 1));
 
+		let expected_event = Event::map_set(map_set::Event::RequestRejected(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(MapSet::request(1), None);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(members(), Vec::<u64>::new());
+	})
+}
+
+#[test]
+fn votes_admit_applicants_at_the_threshold() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		add_member(2);
+		assert_ok!(MapSet::request_membership(Origin::signed(3)));
+
+		assert_ok!(MapSet::vote(Origin::signed(1), 3));
+		assert_eq!(MapSet::request(3).map(|r| r.votes), Some(vec![1]));
+		assert_eq!(members(), vec![1, 2]);
+
+		// The second vote reaches the threshold
+		assert_ok!(MapSet::vote(Origin::signed(2), 3));
+		let expected_event = Event::map_set(map_set::Event::Voted(2, 3));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(members(), vec![1, 2, 3]);
+		assert_eq!(MapSet::request(3), None);
+		assert_eq!(Balances::free_balance(3), 100);
+	})
+}
+
+#[test]
+fn vote_handles_errors() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		assert_ok!(MapSet::request_membership(Origin::signed(3)));
+
+		// 2 is NOT a member
 		assert_noop!(
-			MapSet::add_member(Origin::signed(1)),
-			Error::<TestRuntime>::AlreadyMember
+			MapSet::vote(Origin::signed(2), 3),
+			Error::<TestRuntime>::NotMember
+		);
+
+		assert_noop!(
+			MapSet::vote(Origin::signed(1), 4),
+			Error::<TestRuntime>::NoRequest
+		);
+
+		assert_ok!(MapSet::vote(Origin::signed(1), 3));
+		assert_noop!(
+			MapSet::vote(Origin::signed(1), 3),
+			Error::<TestRuntime>::AlreadyVoted
 		);
 	})
 }
@@ -96,14 +302,21 @@ fn cant_add_duplicate_members() {
 #[test]
 fn cant_exceed_max_members() {
 	ExternalityBuilder::build().execute_with(|| {
+		// Request for the 17th member while there's still room
+		assert_ok!(MapSet::request_membership(Origin::signed(16)));
+
 		// Add 16 members, reaching the max
 		for i in 0..16 {
-			assert_ok!(MapSet::add_member(Origin::signed(i)));
+			add_member(i);
 		}
 
 		// Try to add the 17th member exceeding the max
 		assert_noop!(
-			MapSet::add_member(Origin::signed(16)),
+			MapSet::approve_request(Origin::root(), 16),
+			Error::<TestRuntime>::MembershipLimitReached
+		);
+		assert_noop!(
+			MapSet::request_membership(Origin::signed(17)),
 			Error::<TestRuntime>::MembershipLimitReached
 		);
 	})
@@ -112,16 +325,15 @@ fn cant_exceed_max_members() {
 #[test]
 fn remove_member_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(MapSet::add_member(Origin::signed(1)));
+		add_member(1);
 		assert_ok!(MapSet::remove_member(Origin::signed(1)));
 
 		// check correct event emission
 		let expected_event = Event::map_set(map_set::Event::MemberRemoved(1));
-
-		assert_eq!(System::events()[1].event, expected_event,);
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 
 		// check storage changes
-		assert!(!<Members<TestRuntime>>::contains_key(1));
+		assert_eq!(members(), Vec::<u64>::new());
 	})
 }
 
@@ -135,3 +347,118 @@ fn remove_member_handles_errors() {
 		);
 	})
 }
+
+#[test]
+fn kick_member_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		assert_noop!(
+			MapSet::kick_member(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(MapSet::kick_member(Origin::root(), 1));
+
+		let expected_event = Event::map_set(map_set::Event::MemberRemoved(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(members(), Vec::<u64>::new());
+		assert_noop!(
+			MapSet::kick_member(Origin::root(), 1),
+			Error::<TestRuntime>::NotMember
+		);
+	})
+}
+
+#[test]
+fn calls_are_charged_for_the_current_members() {
+	ExternalityBuilder::build().execute_with(|| {
+		for i in 0..3 {
+			add_member(i);
+		}
+
+		assert_ok!(MapSet::request_membership(Origin::signed(3)));
+		let post_info = MapSet::approve_request(Origin::root(), 3).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::approve_request(3))
+		);
+
+		let post_info = MapSet::remove_member(Origin::signed(3)).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::remove_member(4))
+		);
+	})
+}
+
+#[test]
+fn membership_changes_are_reported() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		add_member(2);
+		assert_ok!(MapSet::request_membership(Origin::signed(3)));
+		assert_ok!(MapSet::vote(Origin::signed(1), 3));
+		assert_ok!(MapSet::vote(Origin::signed(2), 3));
+		assert_ok!(MapSet::kick_member(Origin::root(), 2));
+		assert_ok!(MapSet::remove_member(Origin::signed(3)));
+
+		// Failed calls don't report anything
+		assert_noop!(
+			MapSet::kick_member(Origin::root(), 2),
+			Error::<TestRuntime>::NotMember
+		);
+
+		assert_eq!(
+			membership_changes(),
+			vec![
+				MembershipChange::Added(1),
+				MembershipChange::Added(2),
+				MembershipChange::Added(3),
+				MembershipChange::Removed(2),
+				MembershipChange::Removed(3),
+			]
+		);
+	})
+}
+
+#[test]
+fn account_set_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert!(!<MapSet as AccountSet>::contains(&1));
+		assert_eq!(<MapSet as AccountSet>::count(), 0);
+
+		add_member(3);
+		add_member(1);
+
+		assert!(<MapSet as AccountSet>::contains(&1));
+		assert!(<MapSet as AccountSet>::contains(&3));
+		assert!(!<MapSet as AccountSet>::contains(&2));
+		assert_eq!(<MapSet as AccountSet>::count(), 2);
+		assert_eq!(
+			<MapSet as AccountSet>::accounts()
+				.into_iter()
+				.collect::<Vec<_>>(),
+			vec![1, 3]
+		);
+	})
+}
+
+#[test]
+fn votes_of_departed_members_dont_count() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		add_member(2);
+		add_member(3);
+		assert_ok!(MapSet::request_membership(Origin::signed(4)));
+		assert_ok!(MapSet::vote(Origin::signed(1), 4));
+
+		// 1 leaves, so their vote is dropped and 2's vote doesn't reach the threshold
+		assert_ok!(MapSet::kick_member(Origin::root(), 1));
+		assert_ok!(MapSet::vote(Origin::signed(2), 4));
+		assert_eq!(MapSet::request(4).map(|r| r.votes), Some(vec![2]));
+
+		assert_ok!(MapSet::vote(Origin::signed(3), 4));
+		assert_eq!(MapSet::request(4), None);
+		assert!(<MapSet as AccountSet>::contains(&4));
+	})
+}
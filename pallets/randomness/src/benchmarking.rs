@@ -0,0 +1,21 @@
+//! Benchmarks for the randomness pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	consume_randomness {
+		let caller: T::AccountId = whitelisted_caller();
+		let nonce = Pallet::<T>::nonce();
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert_eq!(Pallet::<T>::nonce(), nonce.wrapping_add(1));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
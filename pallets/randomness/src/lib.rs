@@ -8,8 +8,13 @@ use sp_std::vec::Vec;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_support::{sp_runtime::app_crypto::sp_core::H256, traits::Randomness};
 	use frame_system::pallet_prelude::*;
@@ -22,6 +27,9 @@ pub mod pallet {
 		/// implements the trait. When installing this pallet in a runtime, you
 		/// must make sure to give it a randomness source that suits its needs.
 		type RandomnessSource: Randomness<H256>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
@@ -45,7 +53,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Grab a random seed and random value from the randomness collective flip pallet
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::consume_randomness())]
 		pub fn consume_randomness(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 
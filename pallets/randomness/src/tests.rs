@@ -55,9 +55,10 @@ impl frame_system::Config for TestRuntime {
 impl Config for TestRuntime {
 	type Event = Event;
 	type RandomnessSource = CollectiveFlip;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> sp_io::TestExternalities {
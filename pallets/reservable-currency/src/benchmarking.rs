@@ -0,0 +1,58 @@
+//! Benchmarks for the reservable currency pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, ReservableCurrency};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Zero};
+
+/// An amount well above the existential deposit
+fn amount<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance() * 10u32.into()
+}
+
+benchmarks! {
+	reserve_funds {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), amount::<T>())
+	verify {
+		assert_eq!(T::Currency::reserved_balance(&caller), amount::<T>());
+	}
+
+	unreserve_funds {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		T::Currency::reserve(&caller, amount::<T>())?;
+	}: _(RawOrigin::Signed(caller.clone()), amount::<T>())
+	verify {
+		assert!(T::Currency::reserved_balance(&caller).is_zero());
+	}
+
+	transfer_funds {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let dest: T::AccountId = account("dest", 0, 0);
+	}: _(RawOrigin::Signed(caller), dest.clone(), amount::<T>())
+	verify {
+		assert_eq!(T::Currency::free_balance(&dest), amount::<T>());
+	}
+
+	unreserve_and_transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let to_punish: T::AccountId = account("to_punish", 0, 0);
+		T::Currency::make_free_balance_be(&to_punish, BalanceOf::<T>::max_value() / 2u32.into());
+		T::Currency::reserve(&to_punish, amount::<T>())?;
+		let dest: T::AccountId = account("dest", 0, 0);
+	}: _(RawOrigin::Signed(caller), to_punish.clone(), dest.clone(), amount::<T>())
+	verify {
+		assert!(T::Currency::reserved_balance(&to_punish).is_zero());
+		assert_eq!(T::Currency::free_balance(&dest), amount::<T>());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::new_test_ext(),
+	crate::tests::TestRuntime,
+);
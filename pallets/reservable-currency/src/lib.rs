@@ -7,8 +7,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{
 		dispatch::DispatchResultWithPostInfo,
 		pallet_prelude::*,
@@ -16,7 +21,7 @@ pub mod pallet {
 	};
 	use frame_system::pallet_prelude::*;
 
-	type BalanceOf<T> =
+	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::config]
@@ -26,6 +31,9 @@ pub mod pallet {
 
 		/// Currency type for this pallet.
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
@@ -48,7 +56,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Reserves the specified amount of funds from the caller
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::reserve_funds())]
 		pub fn reserve_funds(
 			origin: OriginFor<T>,
 			amount: BalanceOf<T>,
@@ -65,7 +73,7 @@ pub mod pallet {
 		}
 
 		/// Unreserves the specified amount of funds from the caller
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::unreserve_funds())]
 		pub fn unreserve_funds(
 			origin: OriginFor<T>,
 			amount: BalanceOf<T>,
@@ -82,7 +90,7 @@ pub mod pallet {
 		}
 
 		/// Transfers funds. Essentially a wrapper around the Currency's own transfer method
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::transfer_funds())]
 		pub fn transfer_funds(
 			origin: OriginFor<T>,
 			dest: T::AccountId,
@@ -100,7 +108,7 @@ pub mod pallet {
 
 		/// Atomically unreserves funds and and transfers them.
 		/// might be useful in closed economic systems
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::unreserve_and_transfer())]
 		pub fn unreserve_and_transfer(
 			origin: OriginFor<T>,
 			to_punish: T::AccountId,
@@ -68,6 +68,7 @@ impl pallet_balances::Config for TestRuntime {
 impl Config for TestRuntime {
 	type Event = Event;
 	type Currency = Balances;
+	type WeightInfo = ();
 }
 
 // An alternative to `ExternalityBuilder` which includes custom configuration
@@ -0,0 +1,38 @@
+//! Benchmarks for the ringbuffer queue pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	add_to_queue {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 42, true)
+	verify {
+		assert_eq!(Pallet::<T>::range(), (0, 1));
+	}
+
+	add_multiple {
+		// The queue is indexed by a `u8`, so it holds at most 255 items before it wraps around.
+		let n in 0 .. 255;
+		let caller: T::AccountId = whitelisted_caller();
+		let integers = (0..n as i32).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), integers, true)
+	verify {
+		assert_eq!(Pallet::<T>::range(), (0, n as u8));
+	}
+
+	pop_from_queue {
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::add_to_queue(RawOrigin::Signed(caller.clone()).into(), 42, true)?;
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert_eq!(Pallet::<T>::range(), (1, 1));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
@@ -14,8 +14,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 	use sp_std::vec::Vec;
@@ -25,6 +30,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
@@ -66,7 +74,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Add an item to the queue
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::add_to_queue())]
 		pub fn add_to_queue(
 			origin: OriginFor<T>,
 			integer: i32,
@@ -82,7 +90,7 @@ pub mod pallet {
 		}
 
 		/// Add several items to the queue
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::add_multiple(integers.len() as u32))]
 		pub fn add_multiple(
 			origin: OriginFor<T>,
 			integers: Vec<i32>,
@@ -100,7 +108,7 @@ pub mod pallet {
 		}
 
 		/// Remove and return an item from the queue
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::pop_from_queue())]
 		pub fn pop_from_queue(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			// only a user can pop from the queue
 			let _user = ensure_signed(origin)?;
@@ -53,9 +53,10 @@ impl frame_system::Config for TestRuntime {
 
 impl Config for TestRuntime {
 	type Event = Event;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
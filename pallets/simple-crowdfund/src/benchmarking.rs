@@ -0,0 +1,105 @@
+//! Benchmarks for the simple crowdfund pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Zero};
+
+/// Give `who` plenty of funds to pay deposits and contributions with
+fn fund_account<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// The amount each contribution makes
+fn contribution<T: Config>() -> BalanceOf<T> {
+	T::MinContribution::get() * 10u32.into()
+}
+
+/// Create a fund that ends `duration` blocks from now and whose goal is a single contribution
+fn create_fund<T: Config>(duration: u32) -> Result<FundIndex, &'static str> {
+	let creator: T::AccountId = account("creator", 0, 0);
+	fund_account::<T>(&creator);
+	let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+	let end = frame_system::Module::<T>::block_number() + duration.into();
+
+	let index = FundCount::<T>::get();
+	Pallet::<T>::create(
+		RawOrigin::Signed(creator).into(),
+		beneficiary,
+		contribution::<T>(),
+		end,
+	)?;
+	Ok(index)
+}
+
+/// Contribute to the fund at `index` from `who`
+fn contribute<T: Config>(who: &T::AccountId, index: FundIndex) -> Result<(), &'static str> {
+	fund_account::<T>(who);
+	Pallet::<T>::contribute(
+		RawOrigin::Signed(who.clone()).into(),
+		index,
+		contribution::<T>(),
+	)?;
+	Ok(())
+}
+
+benchmarks! {
+	create {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+		let end = frame_system::Module::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller), beneficiary, contribution::<T>(), end)
+	verify {
+		assert_eq!(FundCount::<T>::get(), 1);
+	}
+
+	contribute {
+		let index = create_fund::<T>(10)?;
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), index, contribution::<T>())
+	verify {
+		assert_eq!(Pallet::<T>::contribution_get(index, &caller), contribution::<T>());
+	}
+
+	withdraw {
+		let index = create_fund::<T>(10)?;
+		let caller: T::AccountId = whitelisted_caller();
+		contribute::<T>(&caller, index)?;
+		frame_system::Module::<T>::set_block_number(20u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		assert!(Pallet::<T>::contribution_get(index, &caller).is_zero());
+	}
+
+	dissolve {
+		let index = create_fund::<T>(10)?;
+		let contributor: T::AccountId = account("contributor", 0, 0);
+		contribute::<T>(&contributor, index)?;
+		let retired = frame_system::Module::<T>::block_number() + 10u32.into() + T::RetirementPeriod::get();
+		frame_system::Module::<T>::set_block_number(retired);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert!(Pallet::<T>::funds(index).is_none());
+	}
+
+	dispense {
+		let index = create_fund::<T>(10)?;
+		let contributor: T::AccountId = account("contributor", 0, 0);
+		contribute::<T>(&contributor, index)?;
+		frame_system::Module::<T>::set_block_number(20u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert!(Pallet::<T>::funds(index).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::new_test_ext(),
+	crate::tests::TestRuntime,
+);
@@ -26,12 +26,17 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 const PALLET_ID: ModuleId = ModuleId(*b"ex/cfund");
 
 /// Simple index for identifying a fund.
 
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use crate::{AccountIdOf, BalanceOf, FundIndex, FundInfoOf};
 	use frame_support::sp_runtime::traits::Zero;
 	use frame_support::traits::{
@@ -60,6 +65,9 @@ pub mod pallet {
 		/// The period of time (in blocks) after an unsuccessful crowdfund ending during which
 		/// contributors are able to withdraw their funds. After this period, their funds are lost.
 		type RetirementPeriod: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[derive(Encode, Decode, Default, PartialEq, Eq)]
@@ -128,7 +136,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a new fund
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::create())]
 		pub fn create(
 			origin: OriginFor<T>,
 			beneficiary: AccountIdOf<T>,
@@ -172,7 +180,7 @@ pub mod pallet {
 		}
 
 		/// Contribute funds to an existing fund
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::contribute())]
 		pub fn contribute(
 			origin: OriginFor<T>,
 			index: FundIndex,
@@ -209,7 +217,7 @@ pub mod pallet {
 		}
 
 		/// Withdraw full balance of a contributor to a fund
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::withdraw())]
 		pub fn withdraw(origin: OriginFor<T>, index: FundIndex) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
@@ -243,7 +251,7 @@ pub mod pallet {
 		/// Dissolve an entire crowdfund after its retirement period has expired.
 		/// Anyone can call this function, and they are incentivized to do so because
 		/// they inherit the deposit.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::dissolve())]
 		pub fn dissolve(origin: OriginFor<T>, index: FundIndex) -> DispatchResultWithPostInfo {
 			let reporter = ensure_signed(origin)?;
 
@@ -282,7 +290,7 @@ pub mod pallet {
 		/// Dispense a payment to the beneficiary of a successful crowdfund.
 		/// The beneficiary receives the contributed funds and the caller receives
 		/// the deposit as a reward to incentivize clearing settled crowdfunds out of storage.
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::dispense())]
 		pub fn dispense(origin: OriginFor<T>, index: FundIndex) -> DispatchResultWithPostInfo {
 			let caller = ensure_signed(origin)?;
 
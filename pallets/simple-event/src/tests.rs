@@ -59,9 +59,10 @@ impl system::Config for Test {
 
 impl simple_event::Config for Test {
 	type Event = Event;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
@@ -0,0 +1,42 @@
+//! Benchmarks for the simple map pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	set_single_entry {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), 42)
+	verify {
+		assert_eq!(Pallet::<T>::simple_map(&caller), 42);
+	}
+
+	get_single_entry {
+		let caller: T::AccountId = whitelisted_caller();
+		let account: T::AccountId = account("account", 0, 0);
+		SimpleMap::<T>::insert(&account, 42);
+	}: _(RawOrigin::Signed(caller), account)
+
+	take_single_entry {
+		let caller: T::AccountId = whitelisted_caller();
+		SimpleMap::<T>::insert(&caller, 42);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!SimpleMap::<T>::contains_key(&caller));
+	}
+
+	increase_single_entry {
+		let caller: T::AccountId = whitelisted_caller();
+		SimpleMap::<T>::insert(&caller, 42);
+	}: _(RawOrigin::Signed(caller.clone()), 8)
+	verify {
+		assert_eq!(Pallet::<T>::simple_map(&caller), 50);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
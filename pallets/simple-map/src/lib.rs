@@ -10,8 +10,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
@@ -19,6 +24,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -63,7 +71,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set the value stored at a particular key
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_single_entry())]
 		pub fn set_single_entry(origin: OriginFor<T>, entry: u32) -> DispatchResultWithPostInfo {
 			// A user can only set their own entry
 			let user = ensure_signed(origin)?;
@@ -75,7 +83,7 @@ pub mod pallet {
 		}
 
 		/// Read the value stored at a particular key and emit it in an event
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::get_single_entry())]
 		pub fn get_single_entry(
 			origin: OriginFor<T>,
 			account: T::AccountId,
@@ -94,7 +102,7 @@ pub mod pallet {
 
 		/// Read the value stored at a particular key, while removing it from the map.
 		/// Also emit the read value in an event
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::take_single_entry())]
 		pub fn take_single_entry(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			// A user can only take (delete) their own entry
 			let user = ensure_signed(origin)?;
@@ -109,7 +117,7 @@ pub mod pallet {
 		}
 
 		/// Increase the value associated with a particular key
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::increase_single_entry())]
 		pub fn increase_single_entry(
 			origin: OriginFor<T>,
 			add_this_val: u32,
@@ -0,0 +1,85 @@
+//! Benchmarks for the storage cache pallet
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+/// The group is an unbounded vector, so the king swaps are measured against a group of this size.
+const MEMBERS: u32 = 100;
+
+/// Fill the group with `MEMBERS` members, the caller being the last of them, and crown an account
+/// outside of the group so the caller can take its place.
+fn setup_swap<T: Config>() -> T::AccountId {
+	let mut members = (1..MEMBERS)
+		.map(|i| account("member", i, 0))
+		.collect::<Vec<T::AccountId>>();
+	let caller: T::AccountId = whitelisted_caller();
+	members.push(caller.clone());
+	GroupMembers::<T>::put(members);
+	KingMember::<T>::put(account::<T::AccountId>("king", 0, 0));
+	caller
+}
+
+benchmarks! {
+	increase_value_no_cache {
+		let caller: T::AccountId = whitelisted_caller();
+		SomeCopyValue::<T>::put(7);
+	}: _(RawOrigin::Signed(caller), 2)
+	verify {
+		assert_eq!(Pallet::<T>::some_copy_value(), 16);
+	}
+
+	increase_value_w_copy {
+		let caller: T::AccountId = whitelisted_caller();
+		SomeCopyValue::<T>::put(7);
+	}: _(RawOrigin::Signed(caller), 2)
+	verify {
+		assert_eq!(Pallet::<T>::some_copy_value(), 16);
+	}
+
+	swap_king_no_cache {
+		let caller = setup_swap::<T>();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::king_member(), caller);
+	}
+
+	swap_king_with_cache {
+		let caller = setup_swap::<T>();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::king_member(), caller);
+	}
+
+	set_copy {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(Pallet::<T>::some_copy_value(), 42);
+	}
+
+	set_king {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::king_member(), caller);
+	}
+
+	mock_add_member {
+		let members = (0..MEMBERS - 1)
+			.map(|i| account("member", i, 0))
+			.collect::<Vec<T::AccountId>>();
+		GroupMembers::<T>::put(members);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Pallet::<T>::is_member(&caller));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
@@ -9,8 +9,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 	use sp_std::prelude::*;
@@ -19,6 +24,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -59,7 +67,7 @@ pub mod pallet {
 		///  (Copy) inefficient way of updating value in storage
 		///
 		/// storage value -> storage_value * 2 + input_val
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::increase_value_no_cache())]
 		pub fn increase_value_no_cache(
 			origin: OriginFor<T>,
 			some_val: u32,
@@ -84,7 +92,7 @@ pub mod pallet {
 		/// (Copy) more efficient value change
 		///
 		/// storage value -> storage_value * 2 + input_val
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::increase_value_w_copy())]
 		pub fn increase_value_w_copy(
 			origin: OriginFor<T>,
 			some_val: u32,
@@ -108,7 +116,7 @@ pub mod pallet {
 		/// swaps the king account with Origin::signed() if
 		/// (1) other account is member &&
 		/// (2) existing king isn't
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::swap_king_no_cache())]
 		pub fn swap_king_no_cache(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let new_king = ensure_signed(origin)?;
 			let existing_king = <KingMember<T>>::get();
@@ -138,7 +146,7 @@ pub mod pallet {
 		/// swaps the king account with Origin::signed() if
 		/// (1) other account is member &&
 		/// (2) existing king isn't
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::swap_king_with_cache())]
 		pub fn swap_king_with_cache(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let new_king = ensure_signed(origin)?;
 			let existing_king = <KingMember<T>>::get();
@@ -166,21 +174,21 @@ pub mod pallet {
 		}
 
 		// ---- for testing purposes ----
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_copy())]
 		pub fn set_copy(origin: OriginFor<T>, val: u32) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 			<SomeCopyValue<T>>::put(val);
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_king())]
 		pub fn set_king(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let user = ensure_signed(origin)?;
 			<KingMember<T>>::put(user);
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::mock_add_member())]
 		pub fn mock_add_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let added = ensure_signed(origin)?;
 			ensure!(!Self::is_member(&added), "member already in group");
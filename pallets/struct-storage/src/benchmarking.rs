@@ -0,0 +1,40 @@
+//! Benchmarks for the struct storage pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	insert_inner_thing {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 1, T::Hash::default(), 10u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::inner_things_by_numbers(1).number, 1);
+	}
+
+	insert_super_thing_with_existing_inner {
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::insert_inner_thing(
+			RawOrigin::Signed(caller.clone()).into(),
+			1,
+			T::Hash::default(),
+			10u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), 1, 2)
+	verify {
+		assert_eq!(Pallet::<T>::super_things_by_super_numbers(2).inner_thing.number, 1);
+	}
+
+	insert_super_thing_with_new_inner {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 1, T::Hash::default(), 10u32.into(), 2)
+	verify {
+		assert_eq!(Pallet::<T>::super_things_by_super_numbers(2).inner_thing.number, 1);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::ExternalityBuilder::build(),
+	crate::tests::TestRuntime,
+);
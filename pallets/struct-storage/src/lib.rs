@@ -10,8 +10,13 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
@@ -19,6 +24,9 @@ pub mod pallet {
 	pub trait Config: pallet_balances::Config + frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[derive(Encode, Decode, Clone, Default, RuntimeDebug)]
@@ -69,7 +77,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Stores an `InnerThing` struct in the storage map
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::insert_inner_thing())]
 		pub fn insert_inner_thing(
 			origin: OriginFor<T>,
 			number: u32,
@@ -89,7 +97,7 @@ pub mod pallet {
 
 		/// Stores a `SuperThing` struct in the storage map using an `InnerThing` that was already
 		/// stored
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::insert_super_thing_with_existing_inner())]
 		pub fn insert_super_thing_with_existing_inner(
 			origin: OriginFor<T>,
 			inner_number: u32,
@@ -112,7 +120,7 @@ pub mod pallet {
 		}
 
 		/// Stores a `SuperThing` struct in the storage map using a new `InnerThing`
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::insert_super_thing_with_new_inner())]
 		pub fn insert_super_thing_with_new_inner(
 			origin: OriginFor<T>,
 			inner_number: u32,
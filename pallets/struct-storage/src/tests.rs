@@ -82,9 +82,10 @@ impl pallet_balances::Config for TestRuntime {
 
 impl Config for TestRuntime {
 	type Event = Event;
+	type WeightInfo = ();
 }
 
-struct ExternalityBuilder;
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
@@ -0,0 +1,59 @@
+//! Weights for struct_storage
+
+// ./target/release/kitchen-node benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=struct_storage
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/frame-weight-template.hbs
+// --output=./pallets/struct-storage/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for struct_storage.
+pub trait WeightInfo {
+	fn insert_inner_thing() -> Weight;
+	fn insert_super_thing_with_existing_inner() -> Weight;
+	fn insert_super_thing_with_new_inner() -> Weight;
+}
+
+/// Weights for struct_storage using the recipes' kitchen node.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn insert_inner_thing() -> Weight {
+		(21_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn insert_super_thing_with_existing_inner() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn insert_super_thing_with_new_inner() -> Weight {
+		(31_000_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For tests and runtimes that don't care about weights
+impl WeightInfo for () {
+	fn insert_inner_thing() -> Weight {
+		(21_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn insert_super_thing_with_existing_inner() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn insert_super_thing_with_new_inner() -> Weight {
+		(31_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
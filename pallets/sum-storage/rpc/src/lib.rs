@@ -1,17 +1,97 @@
 //! RPC interface for the transaction payment module.
 
-use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use futures::{future, stream, StreamExt, TryStreamExt};
+use jsonrpc_core::{
+	// The subscription manager is still built on futures 0.1
+	futures::{Future as _, Sink as _},
+	Error as RpcError,
+	ErrorCode,
+	Result,
+};
 use jsonrpc_derive::rpc;
-use sp_api::ProvideRuntimeApi;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_client_api::{backend::Backend, BlockchainEvents, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, UniqueSaturatedFrom, UniqueSaturatedInto},
+};
 use std::sync::Arc;
-use sum_storage_runtime_api::SumStorageApi as SumStorageRuntimeApi;
+use sum_storage_runtime_api::{SumOverflow, SumStorageApi as SumStorageRuntimeApi};
+
+#[cfg(test)]
+mod tests;
 
 #[rpc]
-pub trait SumStorageApi<BlockHash> {
+pub trait SumStorageApi<BlockHash, BlockNumber> {
 	#[rpc(name = "sumStorage_getSum")]
 	fn get_sum(&self, at: Option<BlockHash>) -> Result<u32>;
+
+	/// Like `sumStorage_getSum`, but fails if the sum overflows. Runtimes older than version 2 of
+	/// the runtime API can't tell, and are answered with their plain sum.
+	#[rpc(name = "sumStorage_getSumChecked")]
+	fn get_sum_checked(&self, at: Option<BlockHash>) -> Result<u32>;
+
+	/// Both values and their sum, read from the same block. Runtimes older than version 2 of the
+	/// runtime API only report the sum.
+	#[rpc(name = "sumStorage_getSumWithOperands")]
+	fn get_sum_with_operands(&self, at: Option<BlockHash>) -> Result<SumDetails>;
+
+	/// The sum at every `step`th block of the canonical chain from `from` up to and including
+	/// `to`, as `(block_number, hash, sum)` triples. `to` defaults to the best block and `step` to
+	/// one.
+	#[rpc(name = "sumStorage_getSumRange")]
+	fn get_sum_range(
+		&self,
+		from: BlockNumber,
+		to: Option<BlockNumber>,
+		step: Option<BlockNumber>,
+	) -> Result<Vec<(BlockNumber, BlockHash, u32)>>;
+}
+
+/// Error code of `sumStorage_getSumChecked` when the sum does not fit in a `u32`.
+pub const SUM_OVERFLOW_ERROR: i64 = 1;
+
+/// The answer of `sumStorage_getSumWithOperands`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SumDetails {
+	/// `Thing1`, unless the runtime is too old to report it
+	pub thing1: Option<u32>,
+	/// `Thing2`, unless the runtime is too old to report it
+	pub thing2: Option<u32>,
+	/// The sum, or `None` if it overflowed
+	pub sum: Option<u32>,
+}
+
+/// The most blocks a single `sumStorage_getSumRange` call will query, unless configured otherwise.
+pub const DEFAULT_MAX_RANGE_POINTS: u64 = 1_000;
+
+#[rpc(server)]
+pub trait SumStorageSubscriptionApi {
+	/// RPC Metadata
+	type Metadata;
+
+	/// Pushes the sum right away, and then again whenever `Thing1` or `Thing2` changes in a new
+	/// best block.
+	#[pubsub(
+		subscription = "sumStorage_sum",
+		subscribe,
+		name = "sumStorage_subscribeSum"
+	)]
+	fn subscribe_sum(&self, metadata: Self::Metadata, subscriber: Subscriber<u32>);
+
+	/// Stops pushing the sum to a subscriber.
+	#[pubsub(
+		subscription = "sumStorage_sum",
+		unsubscribe,
+		name = "sumStorage_unsubscribeSum"
+	)]
+	fn unsubscribe_sum(&self, metadata: Option<Self::Metadata>, id: SubscriptionId)
+		-> Result<bool>;
 }
 
 /// A struct that implements the `SumStorageApi`.
@@ -19,6 +99,7 @@ pub struct SumStorage<C, M> {
 	// If you have more generics, no need to SumStorage<C, M, N, P, ...>
 	// just use a tuple like SumStorage<C, (M, N, P, ...)>
 	client: Arc<C>,
+	max_range_points: u64,
 	_marker: std::marker::PhantomData<M>,
 }
 
@@ -27,9 +108,17 @@ impl<C, M> SumStorage<C, M> {
 	pub fn new(client: Arc<C>) -> Self {
 		Self {
 			client,
+			max_range_points: DEFAULT_MAX_RANGE_POINTS,
 			_marker: Default::default(),
 		}
 	}
+
+	/// Limit how many blocks a single range query may visit. Each one is a runtime call, so this
+	/// bounds the work one request can cause.
+	pub fn with_max_range_points(mut self, max_range_points: u64) -> Self {
+		self.max_range_points = max_range_points;
+		self
+	}
 }
 
 /// Error type of this RPC api.
@@ -49,7 +138,7 @@ impl<C, M> SumStorage<C, M> {
 // 	}
 // }
 
-impl<C, Block> SumStorageApi<<Block as BlockT>::Hash> for SumStorage<C, Block>
+impl<C, Block> SumStorageApi<<Block as BlockT>::Hash, NumberFor<Block>> for SumStorage<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static,
@@ -64,10 +153,232 @@ where
 			self.client.info().best_hash));
 
 		let runtime_api_result = api.get_sum(&at);
-		runtime_api_result.map_err(|e| RpcError {
-			code: ErrorCode::ServerError(9876), // No real reason for this value
-			message: "Something wrong".into(),
-			data: Some(format!("{:?}", e).into()),
+		runtime_api_result.map_err(runtime_error)
+	}
+
+	fn get_sum_checked(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+		let hash = self.at_or_best(at);
+		let at = BlockId::hash(hash);
+		if !self.has_v2_api(&at)? {
+			return self.get_sum(Some(hash));
+		}
+
+		self.client
+			.runtime_api()
+			.get_sum_checked(&at)
+			.map_err(runtime_error)?
+			.map_err(|SumOverflow| RpcError {
+				code: ErrorCode::ServerError(SUM_OVERFLOW_ERROR),
+				message: "The sum does not fit in a u32".into(),
+				data: None,
+			})
+	}
+
+	fn get_sum_with_operands(&self, at: Option<<Block as BlockT>::Hash>) -> Result<SumDetails> {
+		let at = BlockId::hash(self.at_or_best(at));
+		let api = self.client.runtime_api();
+		if !self.has_v2_api(&at)? {
+			// Version 1 only knows the plain sum, and has no way to report an overflow.
+			let sum = api.get_sum(&at).map_err(runtime_error)?;
+			return Ok(SumDetails {
+				thing1: None,
+				thing2: None,
+				sum: Some(sum),
+			});
+		}
+
+		let details = api.get_sum_with_operands(&at).map_err(runtime_error)?;
+		Ok(SumDetails {
+			thing1: Some(details.thing1),
+			thing2: Some(details.thing2),
+			sum: details.sum,
 		})
 	}
+
+	fn get_sum_range(
+		&self,
+		from: NumberFor<Block>,
+		to: Option<NumberFor<Block>>,
+		step: Option<NumberFor<Block>>,
+	) -> Result<Vec<(NumberFor<Block>, <Block as BlockT>::Hash, u32)>> {
+		let from: u64 = from.unique_saturated_into();
+		let to: u64 = to
+			.unwrap_or_else(|| self.client.info().best_number)
+			.unique_saturated_into();
+		let step: u64 = step.map_or(1, UniqueSaturatedInto::unique_saturated_into);
+
+		if step == 0 {
+			return Err(RpcError::invalid_params("step must be at least 1"));
+		}
+		if to < from {
+			return Err(RpcError::invalid_params(format!(
+				"range ends at block {} before it starts at block {}",
+				to, from
+			)));
+		}
+		let points = (to - from) / step + 1;
+		if points > self.max_range_points {
+			return Err(RpcError::invalid_params(format!(
+				"range covers {} blocks, but at most {} are allowed per request",
+				points, self.max_range_points
+			)));
+		}
+
+		let api = self.client.runtime_api();
+		(0..points)
+			.map(|i| {
+				let number = NumberFor::<Block>::unique_saturated_from(from + i * step);
+				// Only blocks of the canonical chain have a hash for their number.
+				let hash = self
+					.client
+					.hash(number)
+					.map_err(runtime_error)?
+					.ok_or_else(|| {
+						RpcError::invalid_params(format!("block {} is not known yet", number))
+					})?;
+				let sum = api.get_sum(&BlockId::hash(hash)).map_err(runtime_error)?;
+				Ok((number, hash, sum))
+			})
+			.collect()
+	}
+}
+
+impl<C, Block> SumStorage<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: SumStorageRuntimeApi<Block>,
+{
+	/// The given block, or the best block if none is given.
+	fn at_or_best(&self, at: Option<<Block as BlockT>::Hash>) -> <Block as BlockT>::Hash {
+		at.unwrap_or_else(|| self.client.info().best_hash)
+	}
+
+	/// Whether the runtime at `at` has version 2 of the runtime API, with the checked methods.
+	fn has_v2_api(&self, at: &BlockId<Block>) -> Result<bool> {
+		self.client
+			.runtime_api()
+			.has_api_with::<dyn SumStorageRuntimeApi<Block>, _>(at, |version| version >= 2)
+			.map_err(runtime_error)
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(9876), // No real reason for this value
+		message: "Something wrong".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+/// A struct that implements the `SumStorageSubscriptionApi`.
+pub struct SumStorageSubscriptions<C, BE, Block> {
+	client: Arc<C>,
+	subscriptions: SubscriptionManager,
+	// The storage keys of `Thing1` and `Thing2`. They depend on the name the pallet is given in
+	// the runtime, so the node has to tell us.
+	keys: Vec<StorageKey>,
+	_marker: std::marker::PhantomData<(BE, Block)>,
+}
+
+impl<C, BE, Block> SumStorageSubscriptions<C, BE, Block> {
+	/// Create new `SumStorageSubscriptions` that watch the storage under `keys` for changes.
+	pub fn new(client: Arc<C>, subscriptions: SubscriptionManager, keys: Vec<StorageKey>) -> Self {
+		Self {
+			client,
+			subscriptions,
+			keys,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, BE, Block> SumStorageSubscriptionApi for SumStorageSubscriptions<C, BE, Block>
+where
+	Block: BlockT,
+	BE: Backend<Block> + 'static,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block> + BlockchainEvents<Block> + StorageProvider<Block, BE>,
+	C::Api: SumStorageRuntimeApi<Block>,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn subscribe_sum(&self, _metadata: Self::Metadata, subscriber: Subscriber<u32>) {
+		let client = self.client.clone();
+		let keys = self.keys.clone();
+
+		self.subscriptions.add(subscriber, move |sink| {
+			let best = client.info().best_hash;
+			let initial = sum_at(&*client, best);
+			let mut last = watched_values(&*client, best, &keys);
+
+			// Only new best blocks count, and only if one of the things differs from the last
+			// block we looked at. Comparing the values, rather than trusting storage change
+			// notifications, also catches re-orgs onto a fork with different values.
+			let updates = client
+				.import_notification_stream()
+				.filter(|notification| future::ready(notification.is_new_best))
+				.filter_map(move |notification| {
+					let values = watched_values(&*client, notification.hash, &keys);
+					let changed = values != last;
+					last = values;
+					future::ready(if changed {
+						Some(sum_at(&*client, notification.hash))
+					} else {
+						None
+					})
+				});
+
+			// The stream is built with futures 0.3, but the subscription manager and its sinks
+			// still speak futures 0.1, so the stream is bridged over like sc-rpc does.
+			let sums = stream::iter(vec![initial])
+				.chain(updates)
+				.map(Ok::<_, ()>)
+				.boxed()
+				.compat();
+
+			sink.sink_map_err(|e| log::warn!("Error sending sum notification: {:?}", e))
+				.send_all(sums)
+				// The stream only ends when the subscriber is gone, there is nothing left to do.
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_sum(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
+
+/// Ask the runtime for the sum at the given block.
+fn sum_at<C, Block>(client: &C, hash: Block::Hash) -> Result<u32>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: SumStorageRuntimeApi<Block>,
+{
+	client
+		.runtime_api()
+		.get_sum(&BlockId::hash(hash))
+		.map_err(runtime_error)
+}
+
+/// Read the raw values under `keys` at the given block. Keys that can't be read count as empty.
+fn watched_values<C, BE, Block>(
+	client: &C,
+	hash: Block::Hash,
+	keys: &[StorageKey],
+) -> Vec<Option<StorageData>>
+where
+	Block: BlockT,
+	BE: Backend<Block>,
+	C: StorageProvider<Block, BE>,
+{
+	keys.iter()
+		.map(|key| client.storage(&BlockId::hash(hash), key).ok().flatten())
+		.collect()
 }
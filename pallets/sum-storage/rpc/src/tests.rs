@@ -0,0 +1,342 @@
+use super::*;
+use futures::{compat::Future01CompatExt, executor};
+use jsonrpc_core::futures::{
+	future::{ExecuteError, Executor},
+	Future as Future01, Stream as Stream01,
+};
+use sc_client_api::{
+	in_mem, BlockImportNotification, FinalityNotifications, ImportNotifications, KeyIterator,
+	StorageEventStream,
+};
+use sp_blockchain::{BlockStatus, Info};
+use sp_consensus::BlockOrigin;
+use sp_core::{
+	storage::{ChildInfo, PrefixedStorageKey},
+	H256,
+};
+use sp_runtime::{
+	testing::{Block as RawBlock, ExtrinsicWrapper, Header},
+	traits::Header as HeaderT,
+};
+use sp_utils::mpsc::{tracing_unbounded, TracingUnboundedSender};
+use std::{collections::HashMap, sync::Mutex};
+use sum_storage_runtime_api::SumWithOperands;
+
+type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+const THING1: &[u8] = b"thing1";
+const THING2: &[u8] = b"thing2";
+
+#[derive(Default)]
+struct Chain {
+	headers: HashMap<H256, Header>,
+	// The watched storage of every block
+	storage: HashMap<H256, HashMap<Vec<u8>, u32>>,
+	best: H256,
+	import_sinks: Vec<TracingUnboundedSender<BlockImportNotification<Block>>>,
+}
+
+/// A client that only knows the two watched values of each block. It imports blocks on request,
+/// and answers the runtime API from its best block.
+#[derive(Clone, Default)]
+struct TestClient(Arc<Mutex<Chain>>);
+
+impl TestClient {
+	/// A client with only a genesis block
+	fn new(thing1: u32, thing2: u32) -> Self {
+		let client = Self::default();
+		client.import(thing1, thing2);
+		client
+	}
+
+	/// Import a new best block with the given values, and notify the import subscribers
+	fn import(&self, thing1: u32, thing2: u32) {
+		let mut chain = self.0.lock().unwrap();
+		let (number, parent_hash) = match chain.headers.get(&chain.best) {
+			Some(parent) => (parent.number + 1, chain.best),
+			None => (0, Default::default()),
+		};
+		let header = Header::new(
+			number,
+			Default::default(),
+			Default::default(),
+			parent_hash,
+			Default::default(),
+		);
+		let hash = header.hash();
+
+		let storage = vec![(THING1.to_vec(), thing1), (THING2.to_vec(), thing2)];
+		chain.storage.insert(hash, storage.into_iter().collect());
+		chain.headers.insert(hash, header.clone());
+		chain.best = hash;
+
+		let notification = BlockImportNotification {
+			hash,
+			origin: BlockOrigin::Own,
+			header,
+			is_new_best: true,
+			tree_route: None,
+		};
+		chain
+			.import_sinks
+			.retain(|sink| sink.unbounded_send(notification.clone()).is_ok());
+	}
+
+	fn best_values(&self) -> (u32, u32) {
+		let chain = self.0.lock().unwrap();
+		let storage = &chain.storage[&chain.best];
+		(storage[THING1], storage[THING2])
+	}
+
+	fn hash_of(&self, id: &BlockId<Block>) -> Option<H256> {
+		let chain = self.0.lock().unwrap();
+		match id {
+			BlockId::Hash(hash) => Some(*hash).filter(|hash| chain.headers.contains_key(hash)),
+			BlockId::Number(number) => chain
+				.headers
+				.iter()
+				.find(|(_, header)| header.number == *number)
+				.map(|(hash, _)| *hash),
+		}
+	}
+}
+
+sp_api::mock_impl_runtime_apis! {
+	impl SumStorageRuntimeApi<Block> for TestClient {
+		fn get_sum(&self) -> u32 {
+			let (thing1, thing2) = self.best_values();
+			thing1 + thing2
+		}
+
+		fn get_sum_checked(&self) -> std::result::Result<u32, SumOverflow> {
+			let (thing1, thing2) = self.best_values();
+			thing1.checked_add(thing2).ok_or(SumOverflow)
+		}
+
+		fn get_sum_with_operands(&self) -> SumWithOperands {
+			let (thing1, thing2) = self.best_values();
+			SumWithOperands {
+				thing1,
+				thing2,
+				sum: thing1.checked_add(thing2),
+			}
+		}
+	}
+}
+
+impl HeaderBackend<Block> for TestClient {
+	fn header(&self, id: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+		let hash = self.hash_of(&id);
+		let chain = self.0.lock().unwrap();
+		Ok(hash.and_then(|hash| chain.headers.get(&hash).cloned()))
+	}
+
+	fn info(&self) -> Info<Block> {
+		let chain = self.0.lock().unwrap();
+		let best = &chain.headers[&chain.best];
+		let genesis_hash = chain
+			.headers
+			.iter()
+			.find(|(_, header)| header.number == 0)
+			.map(|(hash, _)| *hash)
+			.unwrap_or_default();
+		Info {
+			best_hash: chain.best,
+			best_number: best.number,
+			genesis_hash,
+			finalized_hash: genesis_hash,
+			finalized_number: 0,
+			number_leaves: 1,
+		}
+	}
+
+	fn status(&self, id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+		Ok(match self.hash_of(&id) {
+			Some(_) => BlockStatus::InChain,
+			None => BlockStatus::Unknown,
+		})
+	}
+
+	fn number(&self, hash: H256) -> sp_blockchain::Result<Option<u64>> {
+		let chain = self.0.lock().unwrap();
+		Ok(chain.headers.get(&hash).map(|header| header.number))
+	}
+
+	fn hash(&self, number: u64) -> sp_blockchain::Result<Option<H256>> {
+		Ok(self.hash_of(&BlockId::Number(number)))
+	}
+}
+
+impl BlockchainEvents<Block> for TestClient {
+	fn import_notification_stream(&self) -> ImportNotifications<Block> {
+		let (sink, stream) = tracing_unbounded("test_import_notifications");
+		self.0.lock().unwrap().import_sinks.push(sink);
+		stream
+	}
+
+	fn finality_notification_stream(&self) -> FinalityNotifications<Block> {
+		unimplemented!("the sum subscription doesn't follow finality")
+	}
+
+	fn storage_changes_notification_stream(
+		&self,
+		_filter_keys: Option<&[StorageKey]>,
+		_child_filter_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
+	) -> sp_blockchain::Result<StorageEventStream<H256>> {
+		unimplemented!("the sum subscription compares values instead")
+	}
+}
+
+impl StorageProvider<Block, in_mem::Backend<Block>> for TestClient {
+	fn storage(
+		&self,
+		id: &BlockId<Block>,
+		key: &StorageKey,
+	) -> sp_blockchain::Result<Option<StorageData>> {
+		let hash = self.hash_of(id);
+		let chain = self.0.lock().unwrap();
+		Ok(hash
+			.and_then(|hash| chain.storage.get(&hash))
+			.and_then(|storage| storage.get(&key.0))
+			// Stored the way SCALE encodes a `u32`
+			.map(|value| StorageData(value.to_le_bytes().to_vec())))
+	}
+
+	fn storage_keys(
+		&self,
+		_id: &BlockId<Block>,
+		_key_prefix: &StorageKey,
+	) -> sp_blockchain::Result<Vec<StorageKey>> {
+		unimplemented!()
+	}
+
+	fn storage_hash(
+		&self,
+		_id: &BlockId<Block>,
+		_key: &StorageKey,
+	) -> sp_blockchain::Result<Option<H256>> {
+		unimplemented!()
+	}
+
+	fn storage_pairs(
+		&self,
+		_id: &BlockId<Block>,
+		_key_prefix: &StorageKey,
+	) -> sp_blockchain::Result<Vec<(StorageKey, StorageData)>> {
+		unimplemented!()
+	}
+
+	fn storage_keys_iter<'a>(
+		&self,
+		_id: &BlockId<Block>,
+		_prefix: Option<&'a StorageKey>,
+		_start_key: Option<&StorageKey>,
+	) -> sp_blockchain::Result<
+		KeyIterator<'a, <in_mem::Backend<Block> as Backend<Block>>::State, Block>,
+	> {
+		unimplemented!()
+	}
+
+	fn child_storage(
+		&self,
+		_id: &BlockId<Block>,
+		_child_info: &ChildInfo,
+		_key: &StorageKey,
+	) -> sp_blockchain::Result<Option<StorageData>> {
+		unimplemented!()
+	}
+
+	fn child_storage_keys(
+		&self,
+		_id: &BlockId<Block>,
+		_child_info: &ChildInfo,
+		_key_prefix: &StorageKey,
+	) -> sp_blockchain::Result<Vec<StorageKey>> {
+		unimplemented!()
+	}
+
+	fn child_storage_hash(
+		&self,
+		_id: &BlockId<Block>,
+		_child_info: &ChildInfo,
+		_key: &StorageKey,
+	) -> sp_blockchain::Result<Option<H256>> {
+		unimplemented!()
+	}
+
+	fn max_key_changes_range(
+		&self,
+		_first: u64,
+		_last: BlockId<Block>,
+	) -> sp_blockchain::Result<Option<(u64, BlockId<Block>)>> {
+		unimplemented!()
+	}
+
+	fn key_changes(
+		&self,
+		_first: u64,
+		_last: BlockId<Block>,
+		_storage_key: Option<&PrefixedStorageKey>,
+		_key: &StorageKey,
+	) -> sp_blockchain::Result<Vec<(u64, u32)>> {
+		unimplemented!()
+	}
+}
+
+/// Runs each subscription on a thread of its own
+struct ThreadExecutor;
+
+type BoxedFuture01 = Box<dyn Future01<Item = (), Error = ()> + Send>;
+
+impl Executor<BoxedFuture01> for ThreadExecutor {
+	fn execute(
+		&self,
+		future: BoxedFuture01,
+	) -> std::result::Result<(), ExecuteError<BoxedFuture01>> {
+		std::thread::spawn(move || executor::block_on(future.compat()));
+		Ok(())
+	}
+}
+
+fn subscriptions(
+	client: TestClient,
+) -> SumStorageSubscriptions<TestClient, in_mem::Backend<Block>, Block> {
+	SumStorageSubscriptions::new(
+		Arc::new(client),
+		SubscriptionManager::new(Arc::new(ThreadExecutor)),
+		vec![StorageKey(THING1.to_vec()), StorageKey(THING2.to_vec())],
+	)
+}
+
+/// Wait for the next message the subscriber is sent, and return its `result`
+fn next_sum<S>(transport: S) -> (serde_json::Value, S)
+where
+	S: Stream01<Item = String, Error = ()>,
+{
+	let (message, transport) = executor::block_on(transport.into_future().compat())
+		.map_err(|_| ())
+		.expect("the transport stays open");
+	let message: serde_json::Value =
+		serde_json::from_str(&message.expect("a notification arrives")).unwrap();
+	(message["params"]["result"].clone(), transport)
+}
+
+#[test]
+fn subscribe_sum_pushes_changes() {
+	let client = TestClient::new(1, 2);
+	let api = subscriptions(client.clone());
+	let (subscriber, id, transport) = Subscriber::new_test("sumStorage_sum");
+	api.subscribe_sum(Default::default(), subscriber);
+	assert!(matches!(executor::block_on(id.compat()), Ok(Ok(_))));
+
+	// The current sum is pushed right away
+	let (sum, transport) = next_sum(transport);
+	assert_eq!(sum, 3);
+
+	// A block that leaves both values alone is not pushed, so the next notification is for the
+	// block that changes `Thing1`
+	client.import(1, 2);
+	client.import(5, 2);
+	let (sum, _) = next_sum(transport);
+	assert_eq!(sum, 7);
+}
@@ -2,10 +2,33 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::unnecessary_mut_passed)]
 
+use parity_scale_codec::{Decode, Encode};
+
+/// The sum of the two values does not fit in a `u32`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SumOverflow;
+
+/// Both values together with their sum, as read from the same block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SumWithOperands {
+	pub thing1: u32,
+	pub thing2: u32,
+	/// `None` if the sum overflowed
+	pub sum: Option<u32>,
+}
+
 // Here we declare the runtime API. It is implemented it the `impl` block in
 // runtime amalgamator file (the `runtime/src/lib.rs`)
+//
+// Version 2 added `get_sum_checked` and `get_sum_with_operands`. Clients should check the version
+// the runtime reports before calling them, runtimes built against version 1 do not have them.
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait SumStorageApi {
 		fn get_sum() -> u32;
+		fn get_sum_checked() -> Result<u32, SumOverflow>;
+		fn get_sum_with_operands() -> SumWithOperands;
 	}
 }
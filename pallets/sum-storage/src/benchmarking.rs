@@ -0,0 +1,27 @@
+//! Benchmarks for the sum storage pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	set_thing_1 {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(Pallet::<T>::thing1(), 42);
+	}
+
+	set_thing_2 {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(Pallet::<T>::thing2(), 42);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::tests::new_test_ext(),
+	crate::tests::TestRuntime,
+);
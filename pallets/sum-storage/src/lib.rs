@@ -9,16 +9,25 @@ pub use pallet::*;
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+	use crate::weights::WeightInfo;
 
 	/// The module's configuration trait.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
@@ -47,7 +56,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Sets the first simple storage value
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_thing_1())]
 		pub fn set_thing_1(origin: OriginFor<T>, val: u32) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 
@@ -58,7 +67,7 @@ pub mod pallet {
 		}
 
 		/// Sets the second stored value
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_thing_2())]
 		pub fn set_thing_2(origin: OriginFor<T>, val: u32) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 
@@ -71,7 +80,14 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	/// The sum of the two values. Sums too large for a `u32` are capped at `u32::MAX`, use
+	/// `checked_sum` to tell them apart.
 	pub fn get_sum() -> u32 {
-		Thing1::<T>::get() + Thing2::<T>::get()
+		Thing1::<T>::get().saturating_add(Thing2::<T>::get())
+	}
+
+	/// The sum of the two values, or `None` if it does not fit in a `u32`.
+	pub fn checked_sum() -> Option<u32> {
+		Thing1::<T>::get().checked_add(Thing2::<T>::get())
 	}
 }
@@ -53,11 +53,12 @@ impl frame_system::Config for TestRuntime {
 
 impl Config for TestRuntime {
 	type Event = Event;
+	type WeightInfo = ();
 }
 
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
-fn new_test_ext() -> sp_io::TestExternalities {
+pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::default()
 		.build_storage::<TestRuntime>()
 		.unwrap()
@@ -95,3 +96,23 @@ fn sums_both_values() {
 		assert_eq!(SumStorage::get_sum(), 85);
 	});
 }
+
+#[test]
+fn checked_sum_matches_sum() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SumStorage::set_thing_1(Origin::signed(1), 42));
+		assert_ok!(SumStorage::set_thing_2(Origin::signed(1), 43));
+		assert_eq!(SumStorage::checked_sum(), Some(85));
+	});
+}
+
+#[test]
+fn overflowing_sum_is_detected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SumStorage::set_thing_1(Origin::signed(1), u32::MAX));
+		assert_ok!(SumStorage::set_thing_2(Origin::signed(1), 1));
+		assert_eq!(SumStorage::checked_sum(), None);
+		// The plain sum saturates instead of wrapping around
+		assert_eq!(SumStorage::get_sum(), u32::MAX);
+	});
+}
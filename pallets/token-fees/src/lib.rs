@@ -0,0 +1,238 @@
+//! Transaction fees paid in basic-token balances.
+//!
+//! The transaction payment pallet calculates fees in the native currency, and hands them to the
+//! runtime's `OnChargeTransaction` to collect. `TokenFeeAdapter` collects them either in the native
+//! currency, exactly like `CurrencyAdapter`, or in the basic-token pallet's tokens at the
+//! `NativePerToken` conversion rate. Whatever the transaction did not use is refunded in the same
+//! asset after it is dispatched.
+//!
+//! Users pick the asset with the `ChargeFeeAsset` signed extension, so an account that only holds
+//! tokens can still transact. The extension must come before `ChargeTransactionPayment` in the
+//! runtime's `SignedExtra`, because it leaves the choice in storage for the adapter to pick up.
+//! Token fees, including tips, are taken out of the token's total supply.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+pub use pallet::*;
+
+use frame_support::traits::{Currency, Get, Imbalance, OnUnbalanced};
+use pallet_transaction_payment::{CurrencyAdapter, OnChargeTransaction};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension, UniqueSaturatedInto},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod tests;
+
+type NegativeImbalanceOf<C, T> =
+	<C as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// The assets a transaction's fee can be paid in
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum FeeAsset {
+	/// The native currency, as collected by the transaction payment pallet's `CurrencyAdapter`
+	Native,
+	/// The basic-token pallet's tokens, converted at the `NativePerToken` rate
+	BasicToken,
+}
+
+impl Default for FeeAsset {
+	fn default() -> Self {
+		FeeAsset::Native
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: pallet_transaction_payment::Config + basic_token::Config {
+		/// How much of a fee in the native currency one token pays for. Fees paid in tokens are
+		/// rounded up to a whole token.
+		type NativePerToken: Get<u128>;
+	}
+
+	/// The asset the transaction being applied pays its fee in. `ChargeFeeAsset` sets it, and
+	/// `TokenFeeAdapter` takes it again when it withdraws the fee.
+	#[pallet::storage]
+	pub(super) type PendingFeeAsset<T> = StorageValue<_, FeeAsset, ValueQuery>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The number of tokens that pays for a fee in the native currency.
+	pub fn token_fee<Balance: UniqueSaturatedInto<u128>>(fee: Balance) -> u64 {
+		let fee: u128 = fee.unique_saturated_into();
+		let rate = T::NativePerToken::get().max(1);
+		let mut tokens = fee / rate;
+		if fee % rate != 0 {
+			tokens = tokens.saturating_add(1);
+		}
+		tokens.unique_saturated_into()
+	}
+}
+
+/// Picks the asset a transaction's fee is paid in.
+///
+/// Must be placed before `ChargeTransactionPayment` in the runtime's `SignedExtra`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeFeeAsset<T: Config + Send + Sync>(pub FeeAsset, PhantomData<T>);
+
+impl<T: Config + Send + Sync> ChargeFeeAsset<T> {
+	/// Pay the transaction's fee in the given asset.
+	pub fn from(asset: FeeAsset) -> Self {
+		Self(asset, PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for ChargeFeeAsset<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeFeeAsset<{:?}>", self.0)
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ChargeFeeAsset<T> {
+	const IDENTIFIER: &'static str = "ChargeFeeAsset";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	// The transaction payment extension withdraws the fee while validating too, so the choice is
+	// recorded here as well. Storage changes made during validation are thrown away afterwards.
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		<PendingFeeAsset<T>>::put(self.0);
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		<PendingFeeAsset<T>>::put(self.0);
+		Ok(())
+	}
+}
+
+/// The fee withdrawn before dispatch, kept to correct it afterwards
+pub enum FeeLiquidity<NegativeImbalance> {
+	/// The fee was withdrawn from the native currency
+	Native(Option<NegativeImbalance>),
+	/// This many tokens were withdrawn
+	BasicToken(u64),
+}
+
+impl<NegativeImbalance> Default for FeeLiquidity<NegativeImbalance> {
+	fn default() -> Self {
+		FeeLiquidity::Native(None)
+	}
+}
+
+/// Collects fees in the asset chosen with `ChargeFeeAsset`. Native fees are handled by
+/// `CurrencyAdapter<C, OU>`.
+pub struct TokenFeeAdapter<C, OU>(PhantomData<(C, OU)>);
+
+impl<T, C, OU> OnChargeTransaction<T> for TokenFeeAdapter<C, OU>
+where
+	T: Config,
+	T::TransactionByteFee: Get<<C as Currency<<T as frame_system::Config>::AccountId>>::Balance>,
+	C: Currency<<T as frame_system::Config>::AccountId>,
+	C::PositiveImbalance: Imbalance<
+		<C as Currency<<T as frame_system::Config>::AccountId>>::Balance,
+		Opposite = C::NegativeImbalance,
+	>,
+	C::NegativeImbalance: Imbalance<
+		<C as Currency<<T as frame_system::Config>::AccountId>>::Balance,
+		Opposite = C::PositiveImbalance,
+	>,
+	OU: OnUnbalanced<NegativeImbalanceOf<C, T>>,
+{
+	type LiquidityInfo = FeeLiquidity<NegativeImbalanceOf<C, T>>;
+	type Balance = <C as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &T::Call,
+		info: &DispatchInfoOf<T::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		match <PendingFeeAsset<T>>::take() {
+			FeeAsset::Native => <CurrencyAdapter<C, OU> as OnChargeTransaction<T>>::withdraw_fee(
+				who, call, info, fee, tip,
+			)
+			.map(FeeLiquidity::Native),
+			FeeAsset::BasicToken => {
+				let tokens = Pallet::<T>::token_fee(fee);
+				basic_token::Pallet::<T>::withdraw(who, tokens)
+					.map_err(|_| InvalidTransaction::Payment)?;
+				Ok(FeeLiquidity::BasicToken(tokens))
+			}
+		}
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		info: &DispatchInfoOf<T::Call>,
+		post_info: &PostDispatchInfoOf<T::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		match already_withdrawn {
+			FeeLiquidity::Native(imbalance) => {
+				<CurrencyAdapter<C, OU> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+					who,
+					info,
+					post_info,
+					corrected_fee,
+					tip,
+					imbalance,
+				)
+			}
+			FeeLiquidity::BasicToken(paid) => {
+				let refund = paid.saturating_sub(Pallet::<T>::token_fee(corrected_fee));
+				basic_token::Pallet::<T>::deposit(who, refund);
+				Ok(())
+			}
+		}
+	}
+}
@@ -0,0 +1,277 @@
+use crate::{self as token_fees, ChargeFeeAsset, Config, FeeAsset, TokenFeeAdapter};
+use frame_support::{
+	assert_ok, construct_runtime, parameter_types,
+	weights::{DispatchClass, DispatchInfo, IdentityFee, Pays, PostDispatchInfo, Weight},
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_core::H256;
+use sp_io;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime!(
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Module, Storage},
+		BasicToken: basic_token::{Module, Call, Storage, Event<T>},
+		TokenFees: token_fees::{Module, Storage},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	// No base weights, so that fees are only the length and weight of the transaction
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::builder()
+			.base_block(0)
+			.for_class(DispatchClass::all(), |weights| {
+				weights.base_extrinsic = 0;
+			})
+			.build_or_panic();
+}
+impl frame_system::Config for TestRuntime {
+	type BaseCallFilter = ();
+	type BlockWeights = BlockWeights;
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type MaxLocks = ();
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TransactionByteFee: u64 = 1;
+}
+impl pallet_transaction_payment::Config for TestRuntime {
+	type OnChargeTransaction = TokenFeeAdapter<Balances, ()>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: u64 = 100;
+	pub const StringLimit: u32 = 32;
+}
+impl basic_token::Config for TestRuntime {
+	type Event = Event;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type InitOrigin = frame_system::EnsureRoot<u64>;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type StringLimit = StringLimit;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const NativePerToken: u128 = 10;
+}
+impl Config for TestRuntime {
+	type NativePerToken = NativePerToken;
+}
+
+// Account 1 holds all the tokens but none of the native currency, and account 2 the opposite.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<TestRuntime>()
+		.unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime> {
+		balances: vec![(2, 10_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BasicToken::init(Origin::root(), 1));
+	});
+	ext
+}
+
+// Every transaction is this long, so its fee is this plus its weight
+const LEN: usize = 10;
+
+type Extra = (
+	ChargeFeeAsset<TestRuntime>,
+	ChargeTransactionPayment<TestRuntime>,
+);
+
+fn extra(asset: FeeAsset) -> Extra {
+	(
+		ChargeFeeAsset::from(asset),
+		ChargeTransactionPayment::from(0),
+	)
+}
+
+fn call() -> Call {
+	Call::BasicToken(basic_token::Call::transfer(3, 5))
+}
+
+fn info(weight: Weight) -> DispatchInfo {
+	DispatchInfo {
+		weight,
+		..Default::default()
+	}
+}
+
+fn post_info(actual_weight: Weight) -> PostDispatchInfo {
+	PostDispatchInfo {
+		actual_weight: Some(actual_weight),
+		pays_fee: Pays::Yes,
+	}
+}
+
+/// Charges the fee for a transaction declaring `weight`, then corrects it for `actual_weight`
+fn charge(
+	who: u64,
+	asset: FeeAsset,
+	weight: Weight,
+	actual_weight: Weight,
+) -> Result<(), TransactionValidityError> {
+	let pre = extra(asset).pre_dispatch(&who, &call(), &info(weight), LEN)?;
+	Extra::post_dispatch(pre, &info(weight), &post_info(actual_weight), LEN, &Ok(()))
+}
+
+#[test]
+fn pays_native_fee_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(charge(2, FeeAsset::default(), 1_000, 1_000));
+
+		assert_eq!(Balances::free_balance(2), 10_000 - 1_010);
+		assert_eq!(BasicToken::total_supply(), 21_000_000);
+	})
+}
+
+#[test]
+fn refunds_native_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(charge(2, FeeAsset::Native, 1_000, 400));
+
+		assert_eq!(Balances::free_balance(2), 10_000 - 410);
+	})
+}
+
+#[test]
+fn pays_fee_in_tokens() {
+	new_test_ext().execute_with(|| {
+		let pre = extra(FeeAsset::BasicToken)
+			.pre_dispatch(&1, &call(), &info(1_000), LEN)
+			.unwrap();
+
+		// 1_010 at 10 per token, rounded up
+		assert_eq!(BasicToken::get_balance(1), 21_000_000 - 101);
+
+		assert_ok!(Extra::post_dispatch(
+			pre,
+			&info(1_000),
+			&post_info(1_000),
+			LEN,
+			&Ok(())
+		));
+		assert_eq!(BasicToken::get_balance(1), 21_000_000 - 101);
+		assert_eq!(BasicToken::total_supply(), 21_000_000 - 101);
+	})
+}
+
+#[test]
+fn refunds_unused_fee_in_tokens() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(charge(1, FeeAsset::BasicToken, 1_000, 400));
+
+		// Only 410 of the 1_010 were used, which is 41 tokens
+		assert_eq!(BasicToken::get_balance(1), 21_000_000 - 41);
+		assert_eq!(BasicToken::total_supply(), 21_000_000 - 41);
+	})
+}
+
+#[test]
+fn rejects_token_fee_without_tokens() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			charge(2, FeeAsset::BasicToken, 1_000, 1_000),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_eq!(Balances::free_balance(2), 10_000);
+	})
+}
+
+#[test]
+fn rejects_native_fee_without_native_balance() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			charge(1, FeeAsset::Native, 1_000, 1_000),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_eq!(BasicToken::get_balance(1), 21_000_000);
+	})
+}
+
+#[test]
+fn fee_asset_only_applies_to_its_transaction() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(charge(1, FeeAsset::BasicToken, 1_000, 1_000));
+
+		// Without `ChargeFeeAsset`, the next transaction pays in the native currency
+		let pre = ChargeTransactionPayment::<TestRuntime>::from(0)
+			.pre_dispatch(&2, &call(), &info(1_000), LEN)
+			.unwrap();
+		assert_eq!(Balances::free_balance(2), 10_000 - 1_010);
+		assert_ok!(ChargeTransactionPayment::<TestRuntime>::post_dispatch(
+			pre,
+			&info(1_000),
+			&post_info(1_000),
+			LEN,
+			&Ok(())
+		));
+		assert_eq!(BasicToken::get_balance(1), 21_000_000 - 101);
+	})
+}
+
+#[test]
+fn token_fee_rounds_up() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TokenFees::token_fee(0u64), 0);
+		assert_eq!(TokenFees::token_fee(10u64), 1);
+		assert_eq!(TokenFees::token_fee(11u64), 2);
+		assert_eq!(TokenFees::token_fee(u128::max_value()), u64::max_value());
+	})
+}
@@ -3,8 +3,19 @@
 
 //! A pallet that implements a storage set on top of a sorted vec and demonstrates performance
 //! tradeoffs when using map sets.
+//!
+//! The set is kept in a `BoundedVec`, so it never grows past the runtime's `MaxMembers`, and the
+//! calls are charged for the number of members they actually search and shift.
+//!
+//! Accounts can't simply add themselves to the set. They request membership, reserving a deposit,
+//! and are admitted either by the runtime's `AddOrigin` or by `VoteThreshold` votes from current
+//! members. The deposit is returned when a request is approved or withdrawn, and slashed when it is
+//! rejected. Members may leave at any time, and the `RemoveOrigin` can remove them. The runtime's
+//! `MembershipChanged` handler is told about every member added and removed.
 
-use account_set::AccountSet;
+use account_set::{AccountSet, OnMembershipChanged};
+pub use account_set::{BoundedVec, MembershipRequest};
+use frame_support::traits::Currency;
 
 pub use pallet::*;
 use sp_std::collections::btree_set::BTreeSet;
@@ -12,24 +23,75 @@ use sp_std::collections::btree_set::BTreeSet;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
+type MembershipRequestOf<T> = MembershipRequest<AccountIdOf<T>, BalanceOf<T>>;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+	use crate::{
+		weights::WeightInfo, BalanceOf, BoundedVec, MembershipRequestOf, NegativeImbalanceOf,
+		OnMembershipChanged,
+	};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{OnUnbalanced, ReservableCurrency},
+	};
 	use frame_system::pallet_prelude::*;
-	use sp_std::prelude::*;
-
-	/// A maximum number of members. When membership reaches this number, no new members may join.
-	pub const MAX_MEMBERS: usize = 16;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// A maximum number of members. When membership reaches this number, no new members may
+		/// join.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// The currency membership deposits are reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved while a membership request is pending
+		#[pallet::constant]
+		type MembershipDeposit: Get<BalanceOf<Self>>;
+
+		/// Handler for the deposits of rejected requests
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The origin that approves or rejects membership requests
+		type AddOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin that removes members against their will
+		type RemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of members' votes that admits an applicant
+		#[pallet::constant]
+		type VoteThreshold: Get<u32>;
+
+		/// Told whenever an account joins or leaves the set
+		type MembershipChanged: OnMembershipChanged<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn members)]
-	pub(super) type Members<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	pub(super) type Members<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxMembers>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn request)]
+	pub(super) type Requests<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, MembershipRequestOf<T>, OptionQuery>;
 
 	#[pallet::event]
 	#[pallet::metadata(T::AccountId = "AccountId")]
@@ -39,6 +101,14 @@ pub mod pallet {
 		MemberAdded(T::AccountId),
 		/// Removed a member
 		MemberRemoved(T::AccountId),
+		/// Requested membership
+		MembershipRequested(T::AccountId),
+		/// Withdrew a membership request
+		RequestWithdrawn(T::AccountId),
+		/// Rejected a membership request and slashed its deposit
+		RequestRejected(T::AccountId),
+		/// A member voted for an applicant (voter, applicant)
+		Voted(T::AccountId, T::AccountId),
 	}
 
 	#[pallet::error]
@@ -49,6 +119,12 @@ pub mod pallet {
 		NotMember,
 		/// Cannot add another member because the limit is already reached
 		MembershipLimitReached,
+		/// Cannot request membership because a request is already pending
+		AlreadyRequested,
+		/// There is no pending membership request for this account
+		NoRequest,
+		/// Cannot vote for the same applicant twice
+		AlreadyVoted,
 	}
 
 	#[pallet::pallet]
@@ -60,56 +136,196 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Adds a member to the membership set unless the max is reached
-		#[pallet::weight(10_000)]
-		pub fn add_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let new_member = ensure_signed(origin)?;
+		/// Asks to join the membership set, reserving the membership deposit
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::request_membership(T::MaxMembers::get()))]
+		pub fn request_membership(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let applicant = ensure_signed(origin)?;
 
-			let mut members = Members::<T>::get();
+			let members = Members::<T>::get();
+			ensure!(
+				members.binary_search(&applicant).is_err(),
+				Error::<T>::AlreadyMember
+			);
 			ensure!(
-				members.len() < MAX_MEMBERS,
+				(members.len() as u32) < T::MaxMembers::get(),
 				Error::<T>::MembershipLimitReached
 			);
+			ensure!(
+				!Requests::<T>::contains_key(&applicant),
+				Error::<T>::AlreadyRequested
+			);
+
+			let request = MembershipRequestOf::<T>::reserve::<T::Currency>(
+				&applicant,
+				T::MembershipDeposit::get(),
+			)?;
+			Requests::<T>::insert(&applicant, request);
+
+			Self::deposit_event(Event::MembershipRequested(applicant));
+			Ok(Some(T::WeightInfo::request_membership(members.len() as u32)).into())
+		}
+
+		/// Withdraws the caller's pending membership request, returning the deposit
+		#[pallet::weight(T::WeightInfo::withdraw_request())]
+		pub fn withdraw_request(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let applicant = ensure_signed(origin)?;
+
+			let request = Requests::<T>::take(&applicant).ok_or(Error::<T>::NoRequest)?;
+			request.refund::<T::Currency>(&applicant);
+
+			Self::deposit_event(Event::RequestWithdrawn(applicant));
+			Ok(().into())
+		}
+
+		/// Admits an applicant, returning their deposit
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::approve_request(T::MaxMembers::get()))]
+		pub fn approve_request(
+			origin: OriginFor<T>,
+			applicant: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+
+			let request = Requests::<T>::get(&applicant).ok_or(Error::<T>::NoRequest)?;
+			let members = Members::<T>::get();
+			let member_count = members.len() as u32;
+
+			Self::admit(members, applicant, request)?;
+			Ok(Some(T::WeightInfo::approve_request(member_count)).into())
+		}
+
+		/// Turns an applicant down, slashing their deposit
+		#[pallet::weight(T::WeightInfo::reject_request())]
+		pub fn reject_request(
+			origin: OriginFor<T>,
+			applicant: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+
+			let request = Requests::<T>::take(&applicant).ok_or(Error::<T>::NoRequest)?;
+			request.slash::<T::Currency, T::Slash>(&applicant);
+
+			Self::deposit_event(Event::RequestRejected(applicant));
+			Ok(().into())
+		}
 
+		/// Votes, as a member, to admit an applicant. The applicant is admitted with the vote that
+		/// reaches the `VoteThreshold`. Only votes from current members count, so the votes of
+		/// members who have left since are dropped.
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::vote(T::MaxMembers::get()))]
+		pub fn vote(origin: OriginFor<T>, applicant: T::AccountId) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+
+			let members = Members::<T>::get();
+			let member_count = members.len() as u32;
+			ensure!(members.binary_search(&voter).is_ok(), Error::<T>::NotMember);
+
+			let mut request = Requests::<T>::get(&applicant).ok_or(Error::<T>::NoRequest)?;
+			let admitted = request
+				.vote(voter.clone(), T::VoteThreshold::get(), |v| {
+					members.binary_search(v).is_ok()
+				})
+				.map_err(|_| Error::<T>::AlreadyVoted)?;
+
+			if admitted {
+				Self::admit(members, applicant.clone(), request)?;
+			} else {
+				Requests::<T>::insert(&applicant, request);
+			}
+			Self::deposit_event(Event::Voted(voter, applicant));
+			Ok(Some(T::WeightInfo::vote(member_count)).into())
+		}
+
+		/// Removes the caller from the membership set
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::remove_member(T::MaxMembers::get()))]
+		pub fn remove_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let old_member = ensure_signed(origin)?;
+
+			let member_count = Self::evict(old_member)?;
+			Ok(Some(T::WeightInfo::remove_member(member_count)).into())
+		}
+
+		/// Removes a member from the membership set against their will
+		///
+		/// Charged for a full set up front, and refunded down to the actual number of members.
+		#[pallet::weight(T::WeightInfo::kick_member(T::MaxMembers::get()))]
+		pub fn kick_member(
+			origin: OriginFor<T>,
+			old_member: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::RemoveOrigin::ensure_origin(origin)?;
+
+			let member_count = Self::evict(old_member)?;
+			Ok(Some(T::WeightInfo::kick_member(member_count)).into())
+		}
+
+		// also see `append_or_insert`, `append_or_put` in pallet-elections/phragmen, democracy
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Adds an applicant to the set, and resolves their request by returning the deposit.
+		fn admit(
+			members: BoundedVec<T::AccountId, T::MaxMembers>,
+			new_member: T::AccountId,
+			request: MembershipRequestOf<T>,
+		) -> DispatchResult {
+			Self::insert_member(members, &new_member)?;
+			Requests::<T>::remove(&new_member);
+			request.refund::<T::Currency>(&new_member);
+			Self::deposit_event(Event::MemberAdded(new_member));
+			Ok(())
+		}
+
+		/// Inserts a new member into the set, unless they are already in it or it is full.
+		pub(super) fn insert_member(
+			mut members: BoundedVec<T::AccountId, T::MaxMembers>,
+			new_member: &T::AccountId,
+		) -> DispatchResult {
 			// We don't want to add duplicate members, so we check whether the potential new
 			// member is already present in the list. Because the list is always ordered, we can
 			// leverage the binary search which makes this check O(log n).
-			match members.binary_search(&new_member) {
-				// If the search succeeds, the caller is already a member, so just return
+			match members.binary_search(new_member) {
+				// If the search succeeds, the applicant is already a member, so just return
 				Ok(_) => Err(Error::<T>::AlreadyMember.into()),
-				// If the search fails, the caller is not a member and we learned the index where
-				// they should be inserted
+				// If the search fails, the applicant is not a member and we learned the index
+				// where they should be inserted, unless the set is already full
 				Err(index) => {
-					members.insert(index, new_member.clone());
+					members
+						.try_insert(index, new_member.clone())
+						.map_err(|_| Error::<T>::MembershipLimitReached)?;
 					Members::<T>::put(members);
-					Self::deposit_event(Event::MemberAdded(new_member));
-					Ok(().into())
+					T::MembershipChanged::on_member_added(new_member);
+					Ok(())
 				}
 			}
 		}
 
-		/// Removes a member.
-		#[pallet::weight(10_000)]
-		pub fn remove_member(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let old_member = ensure_signed(origin)?;
-
+		/// Removes a member from the set, and returns the number of members before the removal.
+		fn evict(old_member: T::AccountId) -> Result<u32, DispatchError> {
 			let mut members = Members::<T>::get();
+			let member_count = members.len() as u32;
 
 			// We have to find out if the member exists in the sorted vec, and, if so, where.
 			match members.binary_search(&old_member) {
-				// If the search succeeds, the caller is a member, so remove her
+				// If the search succeeds, the account is a member, so remove her
 				Ok(index) => {
 					members.remove(index);
 					Members::<T>::put(members);
+					T::MembershipChanged::on_member_removed(&old_member);
 					Self::deposit_event(Event::MemberRemoved(old_member));
-					Ok(().into())
+					Ok(member_count)
 				}
-				// If the search fails, the caller is not a member, so just return
+				// If the search fails, the account is not a member, so just return
 				Err(_) => Err(Error::<T>::NotMember.into()),
 			}
 		}
-
-		// also see `append_or_insert`, `append_or_put` in pallet-elections/phragmen, democracy
 	}
 }
 
@@ -119,4 +335,23 @@ impl<T: Config> AccountSet for Module<T> {
 	fn accounts() -> BTreeSet<T::AccountId> {
 		Self::members().into_iter().collect::<BTreeSet<_>>()
 	}
+
+	fn contains(who: &T::AccountId) -> bool {
+		Self::members().binary_search(who).is_ok()
+	}
+
+	fn count() -> u32 {
+		Self::members().len() as u32
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &T::AccountId) -> frame_support::dispatch::DispatchResult {
+		Self::insert_member(Self::members(), who)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_count() -> u32 {
+		use frame_support::traits::Get;
+		T::MaxMembers::get()
+	}
 }
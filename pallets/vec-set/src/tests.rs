@@ -1,12 +1,16 @@
-use crate::{self as vec_set, Config, Error};
+use crate::{self as vec_set, weights::WeightInfo, BoundedVec, Config, Error};
+use account_set::{AccountSet, OnMembershipChanged};
 use frame_support::{assert_noop, assert_ok, construct_runtime, parameter_types};
-use frame_system as system;
+use frame_system::{self as system, EnsureRoot};
+use parity_scale_codec::{Decode, Encode};
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
 type Block = frame_system::mocking::MockBlock<TestRuntime>;
@@ -18,6 +22,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		VecSet: vec_set::{Module, Call, Storage, Event<T>},
 	}
 );
@@ -45,51 +50,247 @@ impl frame_system::Config for TestRuntime {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type MaxLocks = ();
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxMembers: u32 = 16;
+	pub const MembershipDeposit: u64 = 10;
+	pub const VoteThreshold: u32 = 2;
+}
 impl Config for TestRuntime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	type AddOrigin = EnsureRoot<u64>;
+	type RemoveOrigin = EnsureRoot<u64>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = RecordMembershipChanges;
+	type WeightInfo = ();
+}
+
+/// A membership change reported to the runtime
+#[derive(Clone, Debug, PartialEq)]
+pub enum MembershipChange {
+	Added(u64),
+	Removed(u64),
+}
+
+thread_local! {
+	static MEMBERSHIP_CHANGES: RefCell<Vec<MembershipChange>> = RefCell::new(Vec::new());
+}
+
+/// Records the membership changes the pallet reports, so the tests can check them
+pub struct RecordMembershipChanges;
+
+impl OnMembershipChanged<u64> for RecordMembershipChanges {
+	fn on_member_added(who: &u64) {
+		MEMBERSHIP_CHANGES.with(|c| c.borrow_mut().push(MembershipChange::Added(*who)));
+	}
+
+	fn on_member_removed(who: &u64) {
+		MEMBERSHIP_CHANGES.with(|c| c.borrow_mut().push(MembershipChange::Removed(*who)));
+	}
 }
 
-struct ExternalityBuilder;
+fn membership_changes() -> Vec<MembershipChange> {
+	MEMBERSHIP_CHANGES.with(|c| c.borrow().clone())
+}
+
+pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
 	pub fn build() -> TestExternalities {
-		let storage = system::GenesisConfig::default()
+		let mut storage = system::GenesisConfig::default()
 			.build_storage::<TestRuntime>()
 			.unwrap();
+		// Accounts 0 to 19 can afford the membership deposit, account 20 can't
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: (0..20).map(|i| (i, 100)).chain(Some((20, 5))).collect(),
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
 		let mut ext = TestExternalities::from(storage);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
 }
 
+/// Request membership for `who`, and approve it
+fn add_member(who: u64) {
+	assert_ok!(VecSet::request_membership(Origin::signed(who)));
+	assert_ok!(VecSet::approve_request(Origin::root(), who));
+}
+
 #[test]
-fn add_member_works() {
+fn request_membership_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(VecSet::add_member(Origin::signed(1)));
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
 
-		let expected_event = Event::vec_set(vec_set::Event::MemberAdded(1));
+		let expected_event = Event::vec_set(vec_set::Event::MembershipRequested(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(VecSet::request(1).map(|r| r.deposit), Some(10));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(VecSet::members(), Vec::<u64>::new());
+	})
+}
+
+#[test]
+fn request_membership_handles_errors() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
+		assert_noop!(
+			VecSet::request_membership(Origin::signed(1)),
+			Error::<TestRuntime>::AlreadyRequested
+		);
+
+		assert_ok!(VecSet::approve_request(Origin::root(), 1));
+		assert_noop!(
+			VecSet::request_membership(Origin::signed(1)),
+			Error::<TestRuntime>::AlreadyMember
+		);
+
+		// 20 can't afford the deposit
+		assert_noop!(
+			VecSet::request_membership(Origin::signed(20)),
+			pallet_balances::Error::<TestRuntime, _>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn withdraw_request_returns_the_deposit() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
+		assert_ok!(VecSet::withdraw_request(Origin::signed(1)));
+
+		let expected_event = Event::vec_set(vec_set::Event::RequestWithdrawn(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(VecSet::request(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		assert_noop!(
+			VecSet::withdraw_request(Origin::signed(1)),
+			Error::<TestRuntime>::NoRequest
+		);
+	})
+}
+
+#[test]
+fn approve_request_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
+		assert_ok!(VecSet::approve_request(Origin::root(), 1));
 
-		assert_eq!(System::events()[0].event, expected_event,);
+		let expected_event = Event::vec_set(vec_set::Event::MemberAdded(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
 
 		assert_eq!(VecSet::members(), vec![1]);
+		assert_eq!(VecSet::request(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
 	})
 }
 
 #[test]
-fn cant_add_duplicate_members() {
+fn approve_request_handles_errors() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(VecSet::add_member(Origin::signed(1)));
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
+		assert_noop!(
+			VecSet::approve_request(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
 
 		assert_noop!(
-			VecSet::add_member(Origin::signed(1)),
-			Error::<TestRuntime>::AlreadyMember
+			VecSet::approve_request(Origin::root(), 2),
+			Error::<TestRuntime>::NoRequest
+		);
+	})
+}
+
+#[test]
+fn reject_request_slashes_the_deposit() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(VecSet::request_membership(Origin::signed(1)));
+		assert_noop!(
+			VecSet::reject_request(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(VecSet::reject_request(Origin::root(), 1));
+
+		let expected_event = Event::vec_set(vec_set::Event::RequestRejected(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(VecSet::request(1), None);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(VecSet::members(), Vec::<u64>::new());
+	})
+}
+
+#[test]
+fn votes_admit_applicants_at_the_threshold() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		add_member(2);
+		assert_ok!(VecSet::request_membership(Origin::signed(3)));
+
+		assert_ok!(VecSet::vote(Origin::signed(1), 3));
+		assert_eq!(VecSet::request(3).map(|r| r.votes), Some(vec![1]));
+		assert_eq!(VecSet::members(), vec![1, 2]);
+
+		// The second vote reaches the threshold
+		assert_ok!(VecSet::vote(Origin::signed(2), 3));
+		let expected_event = Event::vec_set(vec_set::Event::Voted(2, 3));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(VecSet::members(), vec![1, 2, 3]);
+		assert_eq!(VecSet::request(3), None);
+		assert_eq!(Balances::free_balance(3), 100);
+	})
+}
+
+#[test]
+fn vote_handles_errors() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		assert_ok!(VecSet::request_membership(Origin::signed(3)));
+
+		// 2 is NOT a member
+		assert_noop!(
+			VecSet::vote(Origin::signed(2), 3),
+			Error::<TestRuntime>::NotMember
+		);
+
+		assert_noop!(
+			VecSet::vote(Origin::signed(1), 4),
+			Error::<TestRuntime>::NoRequest
+		);
+
+		assert_ok!(VecSet::vote(Origin::signed(1), 3));
+		assert_noop!(
+			VecSet::vote(Origin::signed(1), 3),
+			Error::<TestRuntime>::AlreadyVoted
 		);
 	})
 }
@@ -97,14 +298,21 @@ fn cant_add_duplicate_members() {
 #[test]
 fn cant_exceed_max_members() {
 	ExternalityBuilder::build().execute_with(|| {
+		// Request for the 17th member while there's still room
+		assert_ok!(VecSet::request_membership(Origin::signed(16)));
+
 		// Add 16 members, reaching the max
 		for i in 0..16 {
-			assert_ok!(VecSet::add_member(Origin::signed(i)));
+			add_member(i);
 		}
 
 		// Try to add the 17th member exceeding the max
 		assert_noop!(
-			VecSet::add_member(Origin::signed(16)),
+			VecSet::approve_request(Origin::root(), 16),
+			Error::<TestRuntime>::MembershipLimitReached
+		);
+		assert_noop!(
+			VecSet::request_membership(Origin::signed(17)),
 			Error::<TestRuntime>::MembershipLimitReached
 		);
 	})
@@ -113,7 +321,7 @@ fn cant_exceed_max_members() {
 #[test]
 fn remove_member_works() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert_ok!(VecSet::add_member(Origin::signed(1)));
+		add_member(1);
 		assert_ok!(VecSet::remove_member(Origin::signed(1)));
 
 		// check correct event emission
@@ -135,3 +343,127 @@ fn remove_member_handles_errors() {
 		);
 	})
 }
+
+#[test]
+fn kick_member_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		assert_noop!(
+			VecSet::kick_member(Origin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(VecSet::kick_member(Origin::root(), 1));
+
+		let expected_event = Event::vec_set(vec_set::Event::MemberRemoved(1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_eq!(VecSet::members(), Vec::<u64>::new());
+		assert_noop!(
+			VecSet::kick_member(Origin::root(), 1),
+			Error::<TestRuntime>::NotMember
+		);
+	})
+}
+
+#[test]
+fn calls_are_charged_for_the_current_members() {
+	ExternalityBuilder::build().execute_with(|| {
+		for i in 0..3 {
+			add_member(i);
+		}
+
+		assert_ok!(VecSet::request_membership(Origin::signed(3)));
+		let post_info = VecSet::approve_request(Origin::root(), 3).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::approve_request(3))
+		);
+
+		let post_info = VecSet::remove_member(Origin::signed(3)).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::remove_member(4))
+		);
+	})
+}
+
+#[test]
+fn members_beyond_the_bound_cant_be_decoded() {
+	let full = vec![0u64; 16].encode();
+	assert!(BoundedVec::<u64, MaxMembers>::decode(&mut &full[..]).is_ok());
+
+	let too_many = vec![0u64; 17].encode();
+	assert!(BoundedVec::<u64, MaxMembers>::decode(&mut &too_many[..]).is_err());
+}
+
+#[test]
+fn membership_changes_are_reported() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		add_member(2);
+		assert_ok!(VecSet::request_membership(Origin::signed(3)));
+		assert_ok!(VecSet::vote(Origin::signed(1), 3));
+		assert_ok!(VecSet::vote(Origin::signed(2), 3));
+		assert_ok!(VecSet::kick_member(Origin::root(), 2));
+		assert_ok!(VecSet::remove_member(Origin::signed(3)));
+
+		// Failed calls don't report anything
+		assert_noop!(
+			VecSet::kick_member(Origin::root(), 2),
+			Error::<TestRuntime>::NotMember
+		);
+
+		assert_eq!(
+			membership_changes(),
+			vec![
+				MembershipChange::Added(1),
+				MembershipChange::Added(2),
+				MembershipChange::Added(3),
+				MembershipChange::Removed(2),
+				MembershipChange::Removed(3),
+			]
+		);
+	})
+}
+
+#[test]
+fn account_set_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert!(!<VecSet as AccountSet>::contains(&1));
+		assert_eq!(<VecSet as AccountSet>::count(), 0);
+
+		add_member(3);
+		add_member(1);
+
+		assert!(<VecSet as AccountSet>::contains(&1));
+		assert!(<VecSet as AccountSet>::contains(&3));
+		assert!(!<VecSet as AccountSet>::contains(&2));
+		assert_eq!(<VecSet as AccountSet>::count(), 2);
+		assert_eq!(
+			<VecSet as AccountSet>::accounts()
+				.into_iter()
+				.collect::<Vec<_>>(),
+			vec![1, 3]
+		);
+	})
+}
+
+#[test]
+fn votes_of_departed_members_dont_count() {
+	ExternalityBuilder::build().execute_with(|| {
+		add_member(1);
+		add_member(2);
+		add_member(3);
+		assert_ok!(VecSet::request_membership(Origin::signed(4)));
+		assert_ok!(VecSet::vote(Origin::signed(1), 4));
+
+		// 1 leaves, so their vote is dropped and 2's vote doesn't reach the threshold
+		assert_ok!(VecSet::kick_member(Origin::root(), 1));
+		assert_ok!(VecSet::vote(Origin::signed(2), 4));
+		assert_eq!(VecSet::request(4).map(|r| r.votes), Some(vec![2]));
+
+		assert_ok!(VecSet::vote(Origin::signed(3), 4));
+		assert_eq!(VecSet::request(4), None);
+		assert!(<VecSet as AccountSet>::contains(&4));
+	})
+}
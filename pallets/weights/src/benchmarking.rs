@@ -0,0 +1,57 @@
+//! Benchmarks for the weights pallet
+//!
+//! The calls are weighed by the hand written scales in this pallet rather than by a generated
+//! `WeightInfo`, so the results aren't written to a weights file. Compare them with the scales
+//! instead, to check that each scale grows with its call's arguments the way the call does.
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::storage::StorageValue;
+use frame_system::RawOrigin;
+
+/// The largest loop count the benchmarks pass to the linear calls
+const MAX_LOOPS: u32 = 1_000;
+
+/// The largest `x` passed to `complex_calculations`, which loops `x * x` times
+const MAX_SQRT_LOOPS: u32 = 100;
+
+benchmarks! {
+	store_value {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(StoredValue::get(), 42);
+	}
+
+	add_n {
+		let n in 0 .. MAX_LOOPS;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), n)
+	verify {
+		assert_eq!(StoredValue::get(), n);
+	}
+
+	double {
+		let v in 0 .. MAX_LOOPS;
+		StoredValue::put(v);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), v)
+	verify {
+		assert_eq!(StoredValue::get(), 2 * v);
+	}
+
+	complex_calculations {
+		let x in 0 .. MAX_SQRT_LOOPS;
+		let y in 0 .. MAX_LOOPS;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), x, y)
+	verify {
+		assert_eq!(StoredValue::get(), 2 * y);
+	}
+
+	// The flag that makes the cost grow with `val`
+	add_or_set {
+		let v in 0 .. MAX_LOOPS;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), true, v)
+}
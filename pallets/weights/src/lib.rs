@@ -3,9 +3,12 @@
 #![allow(clippy::unnecessary_wraps)]
 //! Transaction Weight Examples
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 use frame_support::{
 	decl_module, decl_storage,
-	dispatch::{DispatchResult, PaysFee, WeighData},
+	dispatch::{DispatchResult, DispatchResultWithPostInfo, PaysFee, WeighData},
 	ensure,
 	weights::{ClassifyDispatch, DispatchClass, Pays, Weight},
 };
@@ -127,34 +130,45 @@ decl_module! {
 		// add_n sets the storage value n times, so it should cost n times as much as
 		// store_value. Because it performs both a read and a write, the multiplier is set to 200
 		// instead of 100 as before.
+		//
+		// The weight in the annotation is charged up front. Once the value saturates, the
+		// remaining additions would not change it, so they are skipped. Returning the weight that
+		// was actually used refunds the caller for them.
 		#[weight = Linear(200)]
-		fn add_n(_origin, n: u32) -> DispatchResult {
+		fn add_n(_origin, n: u32) -> DispatchResultWithPostInfo {
 
-			let mut old : u32;
+			let mut performed = 0;
 			for _i in 1..=n {
-				old = StoredValue::get();
+				performed += 1;
+				let old = StoredValue::get();
+				if old == u32::max_value() {
+					break;
+				}
 				StoredValue::put(old + 1);
 			}
-			Ok(())
+
+			Ok(Some(Linear(200).weigh_data((&performed,))).into())
 		}
 
 		// The actual expense of `double` is proportional to a storage value. Dispatch
 		// weightings can't use storage values directly, because the weight should be computable
-		// ahead of time. Instead we have the caller pass in the expected storage value and we
-		// ensure it is correct.
+		// ahead of time. Instead the caller passes in an upper bound on the storage value, and
+		// pays for that much work up front. Whatever the bound overstates is refunded.
 		#[weight = Linear(200)]
-		fn double(_origin, initial_value: u32) -> DispatchResult {
+		fn double(_origin, max_initial_value: u32) -> DispatchResultWithPostInfo {
 
-			// Ensure the value passed by the caller actually matches storage If this condition
-			// were not true, the caller would be able to avoid paying appropriate fees.
+			// Ensure the bound passed by the caller actually covers the storage value. If this
+			// condition were not true, the caller would be able to avoid paying appropriate fees.
 			let initial = StoredValue::get();
-			ensure!(initial == initial_value, "Storage value did not match parameter");
+			ensure!(initial <= max_initial_value, "Storage value is larger than the declared bound");
 
 			for _i in 1..=initial {
 				let old = StoredValue::get();
-				StoredValue::put(old + 1);
+				StoredValue::put(old.saturating_add(1));
 			}
-			Ok(())
+
+			// Only charge for the iterations that were needed
+			Ok(Some(Linear(200).weigh_data((&initial,))).into())
 		}
 
 		// This one is quadratic in the first argument plus linear in the second plus a constant.
@@ -162,7 +176,7 @@ decl_module! {
 		// demonstrate that weights should grow by the same order as the compute required by the
 		// transaction.
 		#[weight = Quadratic(200, 30, 100)]
-		fn complex_calculations(_origin, x: u32, y: u32) -> DispatchResult {
+		fn complex_calculations(_origin, x: u32, y: u32) -> DispatchResultWithPostInfo {
 			// This first part performs a relatively cheap (hence 30)
 			// in-memory calculations.
 			let mut part1 = 0;
@@ -170,17 +184,27 @@ decl_module! {
 				part1 += 2
 			}
 
-			// The second part performs x^2 storage read-writes (hence 200)
-			for _j in 1..=x {
+			// The second part performs up to x^2 storage read-writes (hence 200). As in `add_n`,
+			// the read-writes stop once the value saturates.
+			let mut performed: u32 = 0;
+			'outer: for _j in 1..=x {
 				for _k in 1..=x {
-					StoredValue::put(StoredValue::get() + 1);
+					performed += 1;
+					let old = StoredValue::get();
+					if old == u32::max_value() {
+						break 'outer;
+					}
+					StoredValue::put(old + 1);
 				}
 			}
 
 			// One final storage write (hence 100)
 			StoredValue::put(part1);
 
-			Ok(())
+			// Refund the read-writes that were skipped, at the same scale as the annotation
+			let skipped = x.saturating_mul(x).saturating_sub(performed);
+			let declared = Quadratic(200, 30, 100).weigh_data((&x, &y));
+			Ok(Some(declared.saturating_sub(Linear(200).weigh_data((&skipped,)))).into())
 		}
 
 		// Here the first parameter, a boolean has a significant effect on the computational
@@ -209,6 +209,7 @@ impl pallet_transaction_payment::Config for Runtime {
 // ---------------------- Recipe Pallet Configurations ----------------------
 impl sum_storage::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = sum_storage::weights::SubstrateWeight<Runtime>;
 }
 
 construct_runtime!(
@@ -330,6 +331,18 @@ impl_runtime_apis! {
 			// amalgamator file
 			SumStorage::get_sum()
 		}
+
+		fn get_sum_checked() -> Result<u32, sum_storage_runtime_api::SumOverflow> {
+			SumStorage::checked_sum().ok_or(sum_storage_runtime_api::SumOverflow)
+		}
+
+		fn get_sum_with_operands() -> sum_storage_runtime_api::SumWithOperands {
+			sum_storage_runtime_api::SumWithOperands {
+				thing1: SumStorage::thing1(),
+				thing2: SumStorage::thing2(),
+				sum: SumStorage::checked_sum(),
+			}
+		}
 	}
 
 	impl sp_session::SessionKeys<Block> for Runtime {
@@ -343,4 +356,35 @@ impl_runtime_apis! {
 			None
 		}
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{add_benchmark, BenchmarkBatch, Benchmarking, TrackedStorageKey};
+
+			// Keys that are read or written in every block, so the benchmarks don't count them
+			let whitelist: Vec<TrackedStorageKey> = vec![
+				// Block Number
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
+				// Total Issuance
+				hex_literal::hex!("c2261276cc9d1f8598ea4b6a74b15c2f57c875e4cff74148e4628f264b974c80").to_vec().into(),
+				// Execution Phase
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7ff553b5a9862a516939d82b3d3d8661a").to_vec().into(),
+				// Event Count
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef70a98fdbe9ce6c55837576c60c7af3850").to_vec().into(),
+				// System Events
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7").to_vec().into(),
+			];
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+
+			add_benchmark!(params, batches, sum_storage, SumStorage);
+
+			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+			Ok(batches)
+		}
+	}
 }
@@ -1,4 +1,4 @@
-//! Helper module to build a genesis configuration for the weight-fee-runtime
+//! Helper module to build a genesis configuration for the minimal-grandpa-runtime
 
 use super::{
 	AccountId, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig, SystemConfig,
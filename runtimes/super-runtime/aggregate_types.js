@@ -8,11 +8,13 @@ const fs = require('fs');
 // Redundant with construct_runtime!
 const pallets = [
   "basic-token",
+  "block-rewards",
   "charity",
   "check-membership",
   "compounding-interest",
   "constant-config",
   "default-instance",
+  "difficulty-adjustment",
   "double-map",
   "fixed-point",
   "generic-event",
@@ -14,11 +14,15 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 #[cfg(feature = "std")]
 pub mod genesis;
 
+#[cfg(test)]
+mod tests;
+
+use account_set::EnsureMemberOrRoot;
 use check_membership::{loose as check_membership_loose, tight as check_membership_tight};
 use frame_system as system;
 use pallet_transaction_payment::CurrencyAdapter;
 use sp_api::impl_runtime_apis;
-use sp_core::{OpaqueMetadata, H256};
+use sp_core::{OpaqueMetadata, H256, U256};
 use sp_runtime::traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, IdentityLookup, Verify};
 use sp_runtime::{
 	create_runtime_str, generic,
@@ -175,7 +179,8 @@ parameter_types! {
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
-	type OnTimestampSet = ();
+	/// Retarget the proof of work difficulty each time a block's timestamp is set.
+	type OnTimestampSet = DifficultyAdjustment;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
 }
@@ -217,17 +222,44 @@ impl pallet_sudo::Config for Runtime {
 
 // ---------------------- Recipe Pallet Configurations ----------------------
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 100;
+	pub const StringLimit: u32 = 32;
+}
+
 impl basic_token::Config for Runtime {
 	type Event = Event;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type InitOrigin = frame_system::EnsureRoot<AccountId>;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type StringLimit = StringLimit;
+	type WeightInfo = basic_token::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const BaseReward: Balance = 1 << 50;
+	/// Roughly one week of six second blocks
+	pub const HalvingInterval: BlockNumber = 100_800;
+}
+
+impl block_rewards::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type FindAuthor = block_rewards::PowAuthor<AccountId>;
+	type BaseReward = BaseReward;
+	type HalvingInterval = HalvingInterval;
 }
 
 impl charity::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type WeightInfo = charity::weights::SubstrateWeight<Runtime>;
 }
 
 impl compounding_interest::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = compounding_interest::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -239,6 +271,7 @@ impl constant_config::Config for Runtime {
 	type Event = Event;
 	type MaxAddend = MaxAddend;
 	type ClearFrequency = ClearFrequency;
+	type WeightInfo = constant_config::weights::SubstrateWeight<Runtime>;
 }
 
 // The following two configuration traits are for the loosely and tightly coupled variants
@@ -248,60 +281,123 @@ impl check_membership_loose::Config for Runtime {
 	// You can choose either the `vec-set` or `map-set` implementation of the `AccountSet` trait
 	type MembershipSource = VecSet;
 	// type MembershipSource = MapSet;
+	type WeightInfo = check_membership_loose::weights::SubstrateWeight<Runtime>;
 }
 impl check_membership_tight::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = check_membership_tight::weights::SubstrateWeight<Runtime>;
 }
 
 // The following two configuration traits are for two different instances of the deafult-instance
 // pallet. Notice that only the second instance has to explicitly specify an instance.
 impl default_instance::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = default_instance::weights::SubstrateWeight<Runtime>;
 }
 
 /* impl default_instance::Config<default_instance::Instance2> for Runtime {
 	type Event = Event;
 }*/
 
+parameter_types! {
+	pub const TargetBlockTime: u64 = 6_000;
+	pub const AdjustmentWindow: u32 = 60;
+	pub const DampFactor: u128 = 3;
+	pub const ClampFactor: u128 = 2;
+	pub MinDifficulty: U256 = U256::from(10_000);
+}
+
+impl difficulty_adjustment::Config for Runtime {
+	type TargetBlockTime = TargetBlockTime;
+	type AdjustmentWindow = AdjustmentWindow;
+	type DampFactor = DampFactor;
+	type ClampFactor = ClampFactor;
+	type MinDifficulty = MinDifficulty;
+}
+
+parameter_types! {
+	/// Shared by the double map, the map set and the vec set
+	pub const MembershipDeposit: Balance = 1_000;
+	pub const VoteThreshold: u32 = 3;
+}
+
 impl double_map::Config for Runtime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	// Members of the vec set, as well as root, may approve requests to join the double map
+	type AddOrigin = EnsureMemberOrRoot<VecSet>;
+	type RemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = ();
+	type WeightInfo = double_map::weights::SubstrateWeight<Runtime>;
 }
 
 impl fixed_point::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = fixed_point::weights::SubstrateWeight<Runtime>;
 }
 
 impl generic_event::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = generic_event::weights::SubstrateWeight<Runtime>;
 }
 
-impl hello_substrate::Config for Runtime {}
+impl hello_substrate::Config for Runtime {
+	type WeightInfo = hello_substrate::weights::SubstrateWeight<Runtime>;
+}
 
 // The following two configuration traits are for two different instances of the last-caller pallet
 impl last_caller::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = last_caller::weights::SubstrateWeight<Runtime>;
+}
+
+impl lockable_currency::Config for Runtime {
+	type Currency = Balances;
+	type Event = Event;
+	type WeightInfo = lockable_currency::weights::SubstrateWeight<Runtime>;
 }
 
 /* impl last_caller::Config<last_caller::Instance2> for Runtime {
 	type Event = Event;
 }*/
 
+parameter_types! {
+	/// Shared by the map set and the vec set, so their benchmarks cover the same range of sizes.
+	pub const MaxMembers: u32 = 10_000;
+}
+
 impl map_set::Config for Runtime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	type AddOrigin = frame_system::EnsureRoot<AccountId>;
+	type RemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = ();
+	type WeightInfo = map_set::weights::SubstrateWeight<Runtime>;
 }
 
 impl ringbuffer_queue::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = ringbuffer_queue::weights::SubstrateWeight<Runtime>;
 }
 
 impl randomness::Config for Runtime {
 	type Event = Event;
 	type RandomnessSource = RandomnessCollectiveFlip;
+	type WeightInfo = randomness::weights::SubstrateWeight<Runtime>;
 }
 
 impl reservable_currency::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type WeightInfo = reservable_currency::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -316,26 +412,40 @@ impl simple_crowdfund::Config for Runtime {
 	type SubmissionDeposit = SubmissionDeposit;
 	type MinContribution = MinContribution;
 	type RetirementPeriod = RetirementPeriod;
+	type WeightInfo = simple_crowdfund::weights::SubstrateWeight<Runtime>;
 }
 
 impl simple_event::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = simple_event::weights::SubstrateWeight<Runtime>;
 }
 
 impl simple_map::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = simple_map::weights::SubstrateWeight<Runtime>;
 }
 
 impl storage_cache::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = storage_cache::weights::SubstrateWeight<Runtime>;
 }
 
 impl struct_storage::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = struct_storage::weights::SubstrateWeight<Runtime>;
 }
 
 impl vec_set::Config for Runtime {
 	type Event = Event;
+	type MaxMembers = MaxMembers;
+	type Currency = Balances;
+	type MembershipDeposit = MembershipDeposit;
+	type Slash = ();
+	type AddOrigin = frame_system::EnsureRoot<AccountId>;
+	type RemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type VoteThreshold = VoteThreshold;
+	type MembershipChanged = ();
+	type WeightInfo = vec_set::weights::SubstrateWeight<Runtime>;
 }
 
 // ---------------------- End of Recipe Pallet Configurations ----------------------
@@ -353,19 +463,22 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		// The Recipe Pallets
-		BasicToken: basic_token::{Module, Call, Storage, Event<T>},
-		Charity: charity::{Module, Call, Storage, Config, Event<T>},
+		BasicToken: basic_token::{Module, Call, Storage, Config<T>, Event<T>},
+		BlockRewards: block_rewards::{Module, Event<T>},
+		Charity: charity::{Module, Call, Storage, Config<T>, Event<T>},
 		CheckMembershipLoose: check_membership_loose::{Module, Call, Event<T>},
 		CheckMembershipTight: check_membership_tight::{Module, Call, Event<T>},
 		CompoundingInterest: compounding_interest::{Module, Call, Storage, Event},
 		ConstantConfig: constant_config::{Module, Call, Storage, Event},
 		DefaultInstance1: default_instance::{Module, Call, Storage, Event<T>},
 		//DefaultInstance2: default_instance::<Instance2>::{Module, Call, Storage, Event<T>},
+		DifficultyAdjustment: difficulty_adjustment::{Module, Storage, Config},
 		DoubleMap: double_map::{Module, Call, Storage, Event<T>},
 		FixedPoint: fixed_point::{Module, Call, Storage, Event<T>},
 		HelloSubstrate: hello_substrate::{Module, Call},
 		GenericEvent: generic_event::{Module, Call, Event<T>},
 		LastCaller1: last_caller::{Module, Call, Storage, Event<T>},
+		LockableCurrency: lockable_currency::{Module, Call, Event<T>},
 		MapSet: map_set::{Module, Call, Storage, Event<T>},
 		RingbufferQueue: ringbuffer_queue::{Module, Call, Storage, Event<T>},
 		RandomnessDemo: randomness::{Module, Call, Storage, Event<T>},
@@ -468,6 +581,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_consensus_pow::DifficultyApi<Block, U256> for Runtime {
+		fn difficulty() -> U256 {
+			DifficultyAdjustment::difficulty()
+		}
+	}
+
+	impl sha3pow_runtime_api::PowHashingApi<Block> for Runtime {
+		fn pow_hashing() -> sha3pow_runtime_api::PowHashing {
+			DifficultyAdjustment::hashing()
+		}
+	}
+
+	impl basic_token_runtime_api::BasicTokenApi<Block, AccountId, basic_token::AssetId> for Runtime {
+		fn holdings(who: AccountId) -> Vec<(basic_token::AssetId, u64)> {
+			BasicToken::holdings(&who)
+		}
+	}
+
+	impl block_rewards_runtime_api::BlockRewardsApi<Block, BlockNumber, Balance> for Runtime {
+		fn block_reward(number: BlockNumber) -> Balance {
+			BlockRewards::block_reward(number)
+		}
+
+		fn issuance_schedule() -> Vec<(BlockNumber, Balance)> {
+			BlockRewards::issuance_schedule()
+		}
+
+		fn total_rewards(number: BlockNumber) -> Balance {
+			BlockRewards::total_rewards(number)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
 			Vec::new()
@@ -479,4 +624,57 @@ impl_runtime_apis! {
 			None
 		}
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{add_benchmark, BenchmarkBatch, Benchmarking, TrackedStorageKey};
+
+			// Keys that are read or written in every block, so the benchmarks don't count them
+			let whitelist: Vec<TrackedStorageKey> = vec![
+				// Block Number
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
+				// Total Issuance
+				hex_literal::hex!("c2261276cc9d1f8598ea4b6a74b15c2f57c875e4cff74148e4628f264b974c80").to_vec().into(),
+				// Execution Phase
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7ff553b5a9862a516939d82b3d3d8661a").to_vec().into(),
+				// Event Count
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef70a98fdbe9ce6c55837576c60c7af3850").to_vec().into(),
+				// System Events
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7").to_vec().into(),
+			];
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+
+			add_benchmark!(params, batches, basic_token, BasicToken);
+			add_benchmark!(params, batches, charity, Charity);
+			add_benchmark!(params, batches, check_membership_loose, CheckMembershipLoose);
+			add_benchmark!(params, batches, check_membership_tight, CheckMembershipTight);
+			add_benchmark!(params, batches, compounding_interest, CompoundingInterest);
+			add_benchmark!(params, batches, constant_config, ConstantConfig);
+			add_benchmark!(params, batches, default_instance, DefaultInstance1);
+			add_benchmark!(params, batches, double_map, DoubleMap);
+			add_benchmark!(params, batches, fixed_point, FixedPoint);
+			add_benchmark!(params, batches, generic_event, GenericEvent);
+			add_benchmark!(params, batches, hello_substrate, HelloSubstrate);
+			add_benchmark!(params, batches, last_caller, LastCaller1);
+			add_benchmark!(params, batches, lockable_currency, LockableCurrency);
+			add_benchmark!(params, batches, map_set, MapSet);
+			add_benchmark!(params, batches, randomness, RandomnessDemo);
+			add_benchmark!(params, batches, reservable_currency, ReservableCurrency);
+			add_benchmark!(params, batches, ringbuffer_queue, RingbufferQueue);
+			add_benchmark!(params, batches, simple_crowdfund, SimpleCrowdfund);
+			add_benchmark!(params, batches, simple_event, SimpleEvent);
+			add_benchmark!(params, batches, simple_map, SimpleMap);
+			add_benchmark!(params, batches, storage_cache, StorageCache);
+			add_benchmark!(params, batches, struct_storage, StructStorage);
+			add_benchmark!(params, batches, vec_set, VecSet);
+
+			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+			Ok(batches)
+		}
+	}
 }
@@ -0,0 +1,115 @@
+use crate::*;
+use account_set::{EnsureMember, EnsureMemberOrRoot};
+use frame_support::{assert_noop, assert_ok, traits::EnsureOrigin};
+use sp_runtime::DispatchError;
+
+fn account(id: u8) -> AccountId {
+	AccountId::from([id; 32])
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: (1..=4).map(|i| (account(i), 1 << 60)).collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Request membership of the vec set for `who`, and approve it
+fn join_vec_set(who: AccountId) {
+	assert_ok!(VecSet::request_membership(Origin::signed(who.clone())));
+	assert_ok!(VecSet::approve_request(Origin::root(), who));
+}
+
+/// Request membership of the map set for `who`, and approve it
+fn join_map_set(who: AccountId) {
+	assert_ok!(MapSet::request_membership(Origin::signed(who.clone())));
+	assert_ok!(MapSet::approve_request(Origin::root(), who));
+}
+
+#[test]
+fn ensure_member_follows_the_vec_set() {
+	new_test_ext().execute_with(|| {
+		assert!(EnsureMember::<VecSet>::try_origin(Origin::signed(account(1))).is_err());
+
+		join_vec_set(account(1));
+		assert_eq!(
+			EnsureMember::<VecSet>::try_origin(Origin::signed(account(1))).ok(),
+			Some(account(1))
+		);
+		assert!(EnsureMember::<VecSet>::try_origin(Origin::signed(account(2))).is_err());
+		assert!(EnsureMember::<VecSet>::try_origin(Origin::root()).is_err());
+		assert!(EnsureMember::<VecSet>::try_origin(Origin::none()).is_err());
+
+		assert_ok!(VecSet::remove_member(Origin::signed(account(1))));
+		assert!(EnsureMember::<VecSet>::try_origin(Origin::signed(account(1))).is_err());
+	})
+}
+
+#[test]
+fn ensure_member_follows_the_map_set() {
+	new_test_ext().execute_with(|| {
+		join_map_set(account(1));
+		assert_eq!(
+			EnsureMember::<MapSet>::try_origin(Origin::signed(account(1))).ok(),
+			Some(account(1))
+		);
+		assert!(EnsureMember::<MapSet>::try_origin(Origin::signed(account(2))).is_err());
+
+		// Membership of one set says nothing about the other
+		assert!(EnsureMember::<VecSet>::try_origin(Origin::signed(account(1))).is_err());
+	})
+}
+
+#[test]
+fn ensure_member_or_root_accepts_root() {
+	new_test_ext().execute_with(|| {
+		join_vec_set(account(1));
+		assert_eq!(
+			EnsureMemberOrRoot::<VecSet>::try_origin(Origin::root()).ok(),
+			Some(None)
+		);
+		assert_eq!(
+			EnsureMemberOrRoot::<VecSet>::try_origin(Origin::signed(account(1))).ok(),
+			Some(Some(account(1)))
+		);
+		assert!(EnsureMemberOrRoot::<VecSet>::try_origin(Origin::signed(account(2))).is_err());
+		assert!(EnsureMemberOrRoot::<VecSet>::try_origin(Origin::none()).is_err());
+	})
+}
+
+#[test]
+fn vec_set_members_approve_double_map_requests() {
+	new_test_ext().execute_with(|| {
+		join_vec_set(account(1));
+		assert_ok!(DoubleMap::request_membership(Origin::signed(account(3))));
+		assert_ok!(DoubleMap::request_membership(Origin::signed(account(4))));
+
+		// 2 is not in the vec set
+		assert_noop!(
+			DoubleMap::approve_request(Origin::signed(account(2)), account(3)),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(DoubleMap::approve_request(
+			Origin::signed(account(1)),
+			account(3)
+		));
+		assert_eq!(DoubleMap::all_members(), vec![account(3)]);
+
+		// Once 1 leaves the vec set, only root can approve
+		assert_ok!(VecSet::kick_member(Origin::root(), account(1)));
+		assert_noop!(
+			DoubleMap::approve_request(Origin::signed(account(1)), account(4)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(DoubleMap::approve_request(Origin::root(), account(4)));
+		assert_eq!(DoubleMap::all_members(), vec![account(3), account(4)]);
+	})
+}
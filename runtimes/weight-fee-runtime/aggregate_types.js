@@ -7,6 +7,8 @@ const fs = require('fs');
 // Does not include system pallets because Apps already supports them.
 // Redundant with construct_runtime!
 const pallets = [
+  "basic-token",
+  "token-fees",
   "weights",
 ]
 
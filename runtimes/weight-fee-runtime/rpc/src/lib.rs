@@ -0,0 +1,139 @@
+//! RPC interface for the fee breakdown runtime API.
+
+use codec::{Codec, Decode};
+use fee_breakdown_runtime_api::{
+	FeeBreakdown as RuntimeFeeBreakdown, FeeBreakdownApi as FeeBreakdownRuntimeApi, FeePart,
+};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay},
+	FixedPointNumber, FixedU128,
+};
+use std::sync::Arc;
+
+#[rpc]
+pub trait FeeBreakdownApi<BlockHash> {
+	/// How the fee of a SCALE encoded extrinsic would be calculated at the given block, or the
+	/// best block if none is given.
+	#[rpc(name = "feeBreakdown_queryFeeBreakdown")]
+	fn query_fee_breakdown(&self, encoded_xt: Bytes, at: Option<BlockHash>) -> Result<FeeDetails>;
+}
+
+/// One part of a fee, and the calculation that produced it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePartDetails {
+	/// The amount as a decimal string, because balances may not fit in a JSON number
+	pub amount: String,
+	/// The name of the calculation that produced the amount
+	pub source: String,
+}
+
+/// The answer of `feeBreakdown_queryFeeBreakdown`.
+///
+/// `total = baseFee + lengthFee + multiplier * weightFee + tip`, unless `paysFee` is false, in
+/// which case only the tip is charged.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeDetails {
+	pub pays_fee: bool,
+	pub base_fee: FeePartDetails,
+	pub length_fee: FeePartDetails,
+	pub weight_fee: FeePartDetails,
+	/// The multiplier as a decimal string with 18 decimal places
+	pub multiplier: FeePartDetails,
+	pub tip: FeePartDetails,
+	pub total: String,
+}
+
+impl<Balance: MaybeDisplay> From<RuntimeFeeBreakdown<Balance>> for FeeDetails {
+	fn from(breakdown: RuntimeFeeBreakdown<Balance>) -> Self {
+		FeeDetails {
+			pays_fee: breakdown.pays_fee,
+			base_fee: detail(breakdown.base_fee, |amount| amount.to_string()),
+			length_fee: detail(breakdown.length_fee, |amount| amount.to_string()),
+			weight_fee: detail(breakdown.weight_fee, |amount| amount.to_string()),
+			multiplier: detail(breakdown.multiplier, format_multiplier),
+			tip: detail(breakdown.tip, |amount| amount.to_string()),
+			total: breakdown.total.to_string(),
+		}
+	}
+}
+
+fn detail<Amount>(part: FeePart<Amount>, format: impl Fn(&Amount) -> String) -> FeePartDetails {
+	FeePartDetails {
+		amount: format(&part.amount),
+		source: String::from_utf8_lossy(&part.source).into_owned(),
+	}
+}
+
+fn format_multiplier(multiplier: &FixedU128) -> String {
+	let inner = multiplier.into_inner();
+	let div = FixedU128::DIV;
+	format!("{}.{:018}", inner / div, inner % div)
+}
+
+/// A struct that implements the `FeeBreakdownApi`.
+pub struct FeeBreakdown<C, Block, Balance> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Balance)>,
+}
+
+impl<C, Block, Balance> FeeBreakdown<C, Block, Balance> {
+	/// Create new `FeeBreakdown` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error code of `feeBreakdown_queryFeeBreakdown` when the extrinsic can't be decoded.
+pub const DECODE_ERROR: i64 = 1;
+
+/// Error code of `feeBreakdown_queryFeeBreakdown` when the runtime call fails.
+pub const RUNTIME_ERROR: i64 = 2;
+
+impl<C, Block, Balance> FeeBreakdownApi<<Block as BlockT>::Hash> for FeeBreakdown<C, Block, Balance>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: FeeBreakdownRuntimeApi<Block, Balance>,
+	Balance: Codec + MaybeDisplay + Send + Sync + 'static,
+{
+	fn query_fee_breakdown(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<FeeDetails> {
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let len = encoded_xt.len() as u32;
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(DECODE_ERROR),
+			message: "Unable to decode the extrinsic".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		self.client
+			.runtime_api()
+			.query_fee_breakdown(&at, uxt, len)
+			.map(Into::into)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(RUNTIME_ERROR),
+				message: "Unable to query the fee breakdown".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
@@ -0,0 +1,49 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use sp_runtime::FixedU128;
+use sp_std::prelude::*;
+
+/// One part of a transaction's fee
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FeePart<Balance> {
+	pub amount: Balance,
+	/// The name of the calculation that produced the amount, as UTF-8
+	pub source: Vec<u8>,
+}
+
+/// How a transaction's fee is calculated, part by part.
+///
+/// `total = base_fee + length_fee + multiplier * weight_fee + tip`, unless the transaction does
+/// not pay fees, in which case only the tip is charged.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FeeBreakdown<Balance> {
+	/// Whether the transaction pays any fee besides its tip
+	pub pays_fee: bool,
+	/// The fee every transaction of the dispatch class pays, for its base weight
+	pub base_fee: FeePart<Balance>,
+	/// The fee for the transaction's encoded length
+	pub length_fee: FeePart<Balance>,
+	/// The fee for the transaction's weight, before the multiplier is applied
+	pub weight_fee: FeePart<Balance>,
+	/// The multiplier applied to the weight fee
+	pub multiplier: FeePart<FixedU128>,
+	/// The tip the sender chose to add
+	pub tip: FeePart<Balance>,
+	/// What the transaction is charged before dispatch
+	pub total: Balance,
+}
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime amalgamator file (the `runtime/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+	pub trait FeeBreakdownApi<Balance> where Balance: Codec {
+		/// How the fee of `uxt`, whose encoding is `len` bytes long, would be calculated in this
+		/// block.
+		fn query_fee_breakdown(uxt: Block::Extrinsic, len: u32) -> FeeBreakdown<Balance>;
+	}
+}
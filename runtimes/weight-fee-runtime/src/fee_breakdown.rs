@@ -0,0 +1,94 @@
+//! Explains how the transaction payment pallet calculates a transaction's fee, for the
+//! `FeeBreakdownApi` runtime API.
+
+use super::{
+	Balance, BlockWeights, LinearWeightToFee, QuadraticWeightToFee, Runtime, TransactionByteFee,
+	TransactionPayment, UncheckedExtrinsic,
+};
+use fee_breakdown_runtime_api::{FeeBreakdown, FeePart};
+use frame_support::{
+	traits::Get,
+	weights::{GetDispatchInfo, IdentityFee, Pays, WeightToFeePolynomial},
+};
+use pallet_transaction_payment::TargetedFeeAdjustment;
+use sp_runtime::FixedPointNumber;
+
+/// Names the calculation behind one part of a transaction's fee. Implemented for every option the
+/// runtime offers for `WeightToFee` and `FeeMultiplierUpdate`, so the breakdown stays accurate
+/// whichever of them is enabled.
+pub trait DescribeFee {
+	const DESCRIPTION: &'static str;
+}
+
+impl<C> DescribeFee for LinearWeightToFee<C> {
+	const DESCRIPTION: &'static str = "LinearWeightToFee: FeeWeightRatio * weight";
+}
+
+impl DescribeFee for QuadraticWeightToFee {
+	const DESCRIPTION: &'static str = "QuadraticWeightToFee: 3 * weight^2 - 2.4 * weight";
+}
+
+impl DescribeFee for IdentityFee<Balance> {
+	const DESCRIPTION: &'static str = "IdentityFee: weight";
+}
+
+impl<T, S, V, M> DescribeFee for TargetedFeeAdjustment<T, S, V, M> {
+	const DESCRIPTION: &'static str =
+		"TargetedFeeAdjustment: rises above TargetBlockFullness, falls below it";
+}
+
+impl DescribeFee for () {
+	const DESCRIPTION: &'static str = "Constant: never adjusted";
+}
+
+const LENGTH_FEE_DESCRIPTION: &str = "TransactionByteFee * length";
+const TIP_DESCRIPTION: &str = "Tip: chosen by the sender";
+
+type WeightToFee = <Runtime as pallet_transaction_payment::Config>::WeightToFee;
+type FeeMultiplierUpdate = <Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate;
+
+fn part<Amount>(amount: Amount, source: &str) -> FeePart<Amount> {
+	FeePart {
+		amount,
+		source: source.as_bytes().to_vec(),
+	}
+}
+
+/// How the fee of `uxt`, whose encoding is `len` bytes long, would be calculated in this block.
+/// The parts add up to `TransactionPayment::compute_fee`.
+pub fn fee_breakdown(uxt: &UncheckedExtrinsic, len: u32) -> FeeBreakdown<Balance> {
+	let info = uxt.get_dispatch_info();
+	let tip = uxt
+		.signature
+		.as_ref()
+		.map_or(0, |(_, _, extra)| super::tip(extra));
+	let pays_fee = info.pays_fee == Pays::Yes;
+
+	let base_fee = WeightToFee::calc(&BlockWeights::get().get(info.class).base_extrinsic);
+	let length_fee = TransactionByteFee::get().saturating_mul(len.into());
+	// The transaction payment pallet never charges for more than a full block's weight
+	let weight_fee = WeightToFee::calc(&info.weight.min(BlockWeights::get().max_block));
+	let multiplier = TransactionPayment::next_fee_multiplier();
+
+	let total = if pays_fee {
+		base_fee
+			.saturating_add(length_fee)
+			.saturating_add(multiplier.saturating_mul_int(weight_fee))
+			.saturating_add(tip)
+	} else {
+		tip
+	};
+
+	FeeBreakdown {
+		pays_fee,
+		base_fee: part(base_fee, <WeightToFee as DescribeFee>::DESCRIPTION),
+		length_fee: part(length_fee, LENGTH_FEE_DESCRIPTION),
+		weight_fee: part(weight_fee, <WeightToFee as DescribeFee>::DESCRIPTION),
+		multiplier: part(
+			multiplier,
+			<FeeMultiplierUpdate as DescribeFee>::DESCRIPTION,
+		),
+		tip: part(tip, TIP_DESCRIPTION),
+		total,
+	}
+}
@@ -1,6 +1,8 @@
 //! Helper module to build a genesis configuration for the weight-fee-runtime
 
-use super::{AccountId, BalancesConfig, GenesisConfig, Signature, SudoConfig, SystemConfig};
+use super::{
+	AccountId, BalancesConfig, BasicTokenConfig, GenesisConfig, Signature, SudoConfig, SystemConfig,
+};
 use sp_core::{sr25519, Pair};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
@@ -36,6 +38,9 @@ pub fn dev_genesis(wasm_binary: &[u8]) -> GenesisConfig {
 	)
 }
 
+/// The basic token's supply, all of which the root key starts with
+pub const BASIC_TOKEN_SUPPLY: u64 = basic_token::INITIAL_SUPPLY;
+
 /// Helper function to build a genesis configuration
 pub fn testnet_genesis(
 	wasm_binary: &[u8],
@@ -54,6 +59,10 @@ pub fn testnet_genesis(
 				.map(|k| (k, 1 << 60))
 				.collect(),
 		}),
+		basic_token: Some(BasicTokenConfig {
+			balances: vec![(root_key.clone(), BASIC_TOKEN_SUPPLY)],
+			total_supply: BASIC_TOKEN_SUPPLY,
+		}),
 		pallet_sudo: Some(SudoConfig { key: root_key }),
 	}
 }
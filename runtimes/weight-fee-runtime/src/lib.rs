@@ -14,6 +14,11 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 #[cfg(feature = "std")]
 pub mod genesis;
 
+pub mod fee_breakdown;
+
+#[cfg(test)]
+mod tests;
+
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{Get, Randomness},
@@ -22,7 +27,8 @@ use frame_support::{
 		WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
 	},
 };
-use pallet_transaction_payment::CurrencyAdapter;
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use parity_scale_codec::{Compact, Decode, Encode};
 use smallvec::smallvec;
 use sp_api::impl_runtime_apis;
 use sp_core::{OpaqueMetadata, H256};
@@ -30,7 +36,7 @@ use sp_runtime::traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, Identity
 use sp_runtime::{
 	create_runtime_str, generic,
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult, FixedPointNumber, MultiSignature, Perquintill,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -209,6 +215,21 @@ impl pallet_sudo::Config for Runtime {
 
 impl weights::Config for Runtime {}
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 100;
+	pub const StringLimit: u32 = 32;
+}
+
+impl basic_token::Config for Runtime {
+	type Event = Event;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type InitOrigin = frame_system::EnsureRoot<AccountId>;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type StringLimit = StringLimit;
+	type WeightInfo = basic_token::weights::SubstrateWeight<Runtime>;
+}
+
 // --------------------- Multiple Options for WeightToFee -----------------------
 
 /// Convert from weight to fee via a simple coefficient multiplication. The associated type C
@@ -273,13 +294,27 @@ parameter_types! {
 
 	// Establish the byte-fee. It is used in all configurations.
 	pub const TransactionByteFee: u128 = 1;
+
+	// The fee multiplier rises while blocks are fuller than this, and falls while they are emptier.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+
+	// How quickly the fee multiplier responds to blocks that miss the target fullness.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+
+	// The fee multiplier never falls below this, so that fees can recover after long quiet periods.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 }
 
+/// Updates the fee multiplier after each block according to how full the block was, so fees rise
+/// under sustained congestion and decay once it passes.
+pub type SlowAdjustingFeeUpdate<R> =
+	TargetedFeeAdjustment<R, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+
 impl pallet_transaction_payment::Config for Runtime {
 	// The asset in which fees will be collected, and what to do with the imbalance.
 	// Enable exactly one of the following options.
-	//TODO there used to be examples here for using currencies from pallet generic_asset.
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>; // The balances pallet (The most common choice)
+	//type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>; // The balances pallet (The most common choice)
+	type OnChargeTransaction = token_fees::TokenFeeAdapter<Balances, ()>; // The balances pallet, or basic-token tokens if the user chooses them
 
 	// Byte fee is multiplied by the length of the
 	// serialized transaction in bytes
@@ -291,8 +326,19 @@ impl pallet_transaction_payment::Config for Runtime {
 	type WeightToFee = LinearWeightToFee<FeeWeightRatio>;
 	// type WeightToFee = QuadraticWeightToFee<WeightFeeConstant, WeightFeeLinear, WeightFeeQuadratic>;
 
-	//TODO Explore how to change FeeMultiplierUpdate
-	type FeeMultiplierUpdate = ();
+	// Adjust the fee multiplier after every block based on how full it was.
+	// Enable exactly one of the following options.
+	//type FeeMultiplierUpdate = ();
+	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
+}
+
+parameter_types! {
+	// Users who pay their fees in basic-token tokens pay one token per this much of the fee.
+	pub const NativePerToken: u128 = 1_000_000_000;
+}
+
+impl token_fees::Config for Runtime {
+	type NativePerToken = NativePerToken;
 }
 
 // --------------------------------------------
@@ -311,6 +357,8 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		// The Recipe Pallets
 		Weights: weights::{Module, Call, Storage},
+		BasicToken: basic_token::{Module, Call, Storage, Config<T>, Event<T>},
+		TokenFees: token_fees::{Module, Storage},
 	}
 );
 
@@ -332,8 +380,19 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
+	// Must come before `ChargeTransactionPayment`, which charges the fee in the asset chosen here
+	token_fees::ChargeFeeAsset<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
+
+/// The tip offered in a signed extrinsic's `extra`. `ChargeTransactionPayment` keeps it private,
+/// but it is the extension's only encoded field.
+pub fn tip(extra: &SignedExtra) -> Balance {
+	// Naming the element's type stops this from compiling if the extension moves in `SignedExtra`
+	let payment: &pallet_transaction_payment::ChargeTransactionPayment<Runtime> = &extra.7;
+	Compact::<Balance>::decode(&mut &payment.encode()[..]).map_or(0, |tip| tip.0)
+}
+
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
@@ -408,6 +467,15 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fee_breakdown_runtime_api::FeeBreakdownApi<Block, Balance> for Runtime {
+		fn query_fee_breakdown(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+		) -> fee_breakdown_runtime_api::FeeBreakdown<Balance> {
+			fee_breakdown::fee_breakdown(&uxt, len)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
 			Vec::new()
@@ -419,4 +487,36 @@ impl_runtime_apis! {
 			None
 		}
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{add_benchmark, BenchmarkBatch, Benchmarking, TrackedStorageKey};
+
+			// Keys that are read or written in every block, so the benchmarks don't count them
+			let whitelist: Vec<TrackedStorageKey> = vec![
+				// Block Number
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
+				// Total Issuance
+				hex_literal::hex!("c2261276cc9d1f8598ea4b6a74b15c2f57c875e4cff74148e4628f264b974c80").to_vec().into(),
+				// Execution Phase
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7ff553b5a9862a516939d82b3d3d8661a").to_vec().into(),
+				// Event Count
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef70a98fdbe9ce6c55837576c60c7af3850").to_vec().into(),
+				// System Events
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7").to_vec().into(),
+			];
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+
+			// Basic token is benchmarked in the super runtime, whose weights this runtime uses
+			add_benchmark!(params, batches, weights, Weights);
+
+			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+			Ok(batches)
+		}
+	}
 }
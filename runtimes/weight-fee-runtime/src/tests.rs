@@ -0,0 +1,358 @@
+use crate::*;
+use frame_support::{
+	assert_ok,
+	dispatch::DispatchResultWithPostInfo,
+	storage::StorageValue,
+	traits::OnFinalize,
+	weights::{DispatchClass, GetDispatchInfo, PostDispatchInfo, Weight},
+};
+use pallet_transaction_payment::{ChargeTransactionPayment, NextFeeMultiplier};
+use parity_scale_codec::Encode;
+use sp_runtime::{
+	generic::Era,
+	traits::{Dispatchable, SignedExtension},
+};
+use token_fees::{ChargeFeeAsset, FeeAsset};
+
+// The encoded length used for every transaction. Only the weight part of the fee matters here.
+const LEN: usize = 10;
+
+fn alice() -> AccountId {
+	AccountId::from([1u8; 32])
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(alice(), 1 << 60)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// The part of the fee that pays for `weight`, given the runtime's `LinearWeightToFee`
+fn weight_fee(weight: Weight) -> Balance {
+	weight as Balance * FeeWeightRatio::get()
+}
+
+/// Dispatches a call from Alice the way the executive would: the fee for the declared weight is
+/// withdrawn up front, and whatever the call reports it did not use is refunded afterwards.
+/// Returns the dispatch result, the fee for the declared weight, and the fee actually paid.
+fn dispatch_with_fee(call: Call) -> (DispatchResultWithPostInfo, Balance, Balance) {
+	let who = alice();
+	let info = call.get_dispatch_info();
+	let declared_fee = TransactionPayment::compute_fee(LEN as u32, &info, 0);
+	let before = Balances::free_balance(&who);
+
+	let pre = ChargeTransactionPayment::<Runtime>::from(0)
+		.pre_dispatch(&who, &call, &info, LEN)
+		.expect("Alice can pay the fee");
+	let result = call.dispatch(Origin::signed(who.clone()));
+	let post_info: PostDispatchInfo = match &result {
+		Ok(post_info) => *post_info,
+		Err(err) => err.post_info,
+	};
+	assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+		pre,
+		&info,
+		&post_info,
+		LEN,
+		&result.map(|_| ()).map_err(|e| e.error),
+	));
+
+	let paid = before - Balances::free_balance(&who);
+	assert_eq!(
+		paid,
+		TransactionPayment::compute_actual_fee(LEN as u32, &info, &post_info, 0)
+	);
+	(result, declared_fee, paid)
+}
+
+fn store(value: u32) {
+	assert_ok!(Call::Weights(weights::Call::store_value(value)).dispatch(Origin::signed(alice())));
+}
+
+#[test]
+fn double_refunds_overstated_bound() {
+	new_test_ext().execute_with(|| {
+		store(3);
+
+		let (result, declared_fee, paid) =
+			dispatch_with_fee(Call::Weights(weights::Call::double(100)));
+
+		assert_ok!(result);
+		assert_eq!(Weights::stored_value(), 6);
+		// Only 3 of the 100 declared iterations were needed
+		assert_eq!(declared_fee - paid, weight_fee(200 * 97));
+	})
+}
+
+#[test]
+fn double_with_exact_bound_is_not_refunded() {
+	new_test_ext().execute_with(|| {
+		store(3);
+
+		let (result, declared_fee, paid) =
+			dispatch_with_fee(Call::Weights(weights::Call::double(3)));
+
+		assert_ok!(result);
+		assert_eq!(Weights::stored_value(), 6);
+		assert_eq!(paid, declared_fee);
+	})
+}
+
+#[test]
+fn double_rejects_understated_bound() {
+	new_test_ext().execute_with(|| {
+		store(10);
+
+		let (result, declared_fee, paid) =
+			dispatch_with_fee(Call::Weights(weights::Call::double(5)));
+
+		assert_eq!(
+			result.map_err(|e| e.error),
+			Err("Storage value is larger than the declared bound".into())
+		);
+		assert_eq!(Weights::stored_value(), 10);
+		// The rejected call still pays for the work it declared
+		assert_eq!(paid, declared_fee);
+	})
+}
+
+#[test]
+fn add_n_refunds_additions_after_saturation() {
+	new_test_ext().execute_with(|| {
+		store(u32::max_value() - 2);
+
+		let (result, declared_fee, paid) =
+			dispatch_with_fee(Call::Weights(weights::Call::add_n(10)));
+
+		assert_ok!(result);
+		assert_eq!(Weights::stored_value(), u32::max_value());
+		// Two additions and the read that found the value saturated were performed
+		assert_eq!(declared_fee - paid, weight_fee(200 * 7));
+	})
+}
+
+#[test]
+fn complex_calculations_charges_full_weight_without_saturation() {
+	new_test_ext().execute_with(|| {
+		let (result, declared_fee, paid) =
+			dispatch_with_fee(Call::Weights(weights::Call::complex_calculations(4, 3)));
+
+		assert_ok!(result);
+		assert_eq!(Weights::stored_value(), 6);
+		assert_eq!(paid, declared_fee);
+	})
+}
+
+#[test]
+fn complex_calculations_refunds_read_writes_after_saturation() {
+	new_test_ext().execute_with(|| {
+		store(u32::max_value() - 5);
+
+		let (result, declared_fee, paid) =
+			dispatch_with_fee(Call::Weights(weights::Call::complex_calculations(4, 3)));
+
+		assert_ok!(result);
+		assert_eq!(Weights::stored_value(), 6);
+		// Five increments and the read that found the value saturated, out of 16 declared
+		assert_eq!(declared_fee - paid, weight_fee(200 * 10));
+	})
+}
+
+/// Finalizes `blocks` blocks whose normal dispatches used `fullness` of the weight allowed to them,
+/// and returns the fee multiplier after each one.
+fn run_blocks(blocks: u32, fullness: Perbill) -> Vec<Multiplier> {
+	let max_normal = BlockWeights::get()
+		.get(DispatchClass::Normal)
+		.max_total
+		.unwrap();
+	(0..blocks)
+		.map(|_| {
+			let n = System::block_number();
+			System::set_block_consumed_resources(fullness * max_normal, 0);
+			TransactionPayment::on_finalize(n);
+			System::set_block_number(n + 1);
+			TransactionPayment::next_fee_multiplier()
+		})
+		.collect()
+}
+
+fn store_value_fee() -> Balance {
+	let info = Call::Weights(weights::Call::store_value(0)).get_dispatch_info();
+	TransactionPayment::compute_fee(LEN as u32, &info, 0)
+}
+
+#[test]
+fn fees_rise_under_sustained_full_blocks() {
+	new_test_ext().execute_with(|| {
+		let initial_fee = store_value_fee();
+
+		let multipliers = run_blocks(100, Perbill::one());
+
+		assert!(multipliers[0] > Multiplier::one());
+		assert!(multipliers.windows(2).all(|w| w[1] > w[0]));
+		assert!(store_value_fee() > initial_fee);
+	})
+}
+
+#[test]
+fn fees_decay_after_congestion() {
+	new_test_ext().execute_with(|| {
+		run_blocks(100, Perbill::one());
+		let peak = TransactionPayment::next_fee_multiplier();
+		let peak_fee = store_value_fee();
+
+		let multipliers = run_blocks(100, Perbill::zero());
+
+		assert!(multipliers[0] < peak);
+		assert!(multipliers.windows(2).all(|w| w[1] < w[0]));
+		assert!(store_value_fee() < peak_fee);
+	})
+}
+
+#[test]
+fn fees_track_target_fullness() {
+	new_test_ext().execute_with(|| {
+		// Blocks just under the target lower the multiplier, blocks just over it raise it
+		let below = run_blocks(1, Perbill::from_percent(20))[0];
+		assert!(below < Multiplier::one());
+
+		NextFeeMultiplier::put(Multiplier::one());
+		let above = run_blocks(1, Perbill::from_percent(30))[0];
+		assert!(above > Multiplier::one());
+	})
+}
+
+#[test]
+fn multiplier_never_falls_below_minimum() {
+	new_test_ext().execute_with(|| {
+		NextFeeMultiplier::put(MinimumMultiplier::get());
+
+		let multipliers = run_blocks(10, Perbill::zero());
+
+		assert!(multipliers.iter().all(|m| *m == MinimumMultiplier::get()));
+	})
+}
+
+fn signed_extra(nonce: Index, asset: FeeAsset) -> SignedExtra {
+	(
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		ChargeFeeAsset::from(asset),
+		ChargeTransactionPayment::from(0),
+	)
+}
+
+#[test]
+fn fees_can_be_paid_in_basic_token() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BasicToken::init(Origin::root(), alice()));
+		let native_before = Balances::free_balance(&alice());
+		let tokens_before = BasicToken::get_balance(&alice());
+
+		let call = Call::Weights(weights::Call::double(100));
+		let info = call.get_dispatch_info();
+		let pre = signed_extra(0, FeeAsset::BasicToken)
+			.pre_dispatch(&alice(), &call, &info, LEN)
+			.expect("Alice can pay the fee in tokens");
+		let result = call.dispatch(Origin::signed(alice()));
+		let post_info = result.unwrap();
+		assert_ok!(SignedExtra::post_dispatch(
+			pre,
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		let fee = TransactionPayment::compute_actual_fee(LEN as u32, &info, &post_info, 0);
+		assert_eq!(Balances::free_balance(&alice()), native_before);
+		assert_eq!(
+			BasicToken::get_balance(&alice()),
+			tokens_before - TokenFees::token_fee(fee)
+		);
+	})
+}
+
+fn signed_xt(call: Call, tip: Balance) -> UncheckedExtrinsic {
+	let mut extra = signed_extra(0, FeeAsset::Native);
+	extra.7 = ChargeTransactionPayment::from(tip);
+	// Nothing checks the signature here
+	let signature = sp_core::sr25519::Signature::from_raw([0u8; 64]).into();
+	UncheckedExtrinsic::new_signed(call, alice(), signature, extra)
+}
+
+#[test]
+fn fee_breakdown_adds_up_to_the_charged_fee() {
+	new_test_ext().execute_with(|| {
+		// Raise the multiplier above one, so that it shows in the weight fee
+		run_blocks(100, Perbill::one());
+
+		let call = Call::Weights(weights::Call::double(100));
+		let info = call.get_dispatch_info();
+		let uxt = signed_xt(call, 7);
+		let len = uxt.encode().len() as u32;
+
+		let breakdown = fee_breakdown::fee_breakdown(&uxt, len);
+		assert!(breakdown.pays_fee);
+		assert_eq!(
+			breakdown.length_fee.amount,
+			TransactionByteFee::get() * len as Balance
+		);
+		assert_eq!(breakdown.weight_fee.amount, weight_fee(info.weight));
+		assert_eq!(
+			breakdown.multiplier.amount,
+			TransactionPayment::next_fee_multiplier()
+		);
+		assert!(breakdown.multiplier.amount > Multiplier::one());
+		assert_eq!(breakdown.tip.amount, 7);
+		assert_eq!(
+			breakdown.total,
+			TransactionPayment::compute_fee(len, &info, 7)
+		);
+	})
+}
+
+#[test]
+fn fee_breakdown_names_the_runtime_polynomials() {
+	new_test_ext().execute_with(|| {
+		let uxt = signed_xt(Call::Weights(weights::Call::store_value(0)), 0);
+		let breakdown = fee_breakdown::fee_breakdown(&uxt, LEN as u32);
+
+		assert_eq!(
+			breakdown.weight_fee.source,
+			b"LinearWeightToFee: FeeWeightRatio * weight".to_vec()
+		);
+		assert_eq!(breakdown.base_fee.source, breakdown.weight_fee.source);
+		assert_eq!(
+			breakdown.length_fee.source,
+			b"TransactionByteFee * length".to_vec()
+		);
+		assert!(breakdown
+			.multiplier
+			.source
+			.starts_with(b"TargetedFeeAdjustment"));
+	})
+}
+
+#[test]
+fn unsigned_extrinsics_have_no_tip() {
+	new_test_ext().execute_with(|| {
+		let uxt = UncheckedExtrinsic::new_unsigned(Call::Weights(weights::Call::store_value(0)));
+		let breakdown = fee_breakdown::fee_breakdown(&uxt, LEN as u32);
+
+		assert_eq!(breakdown.tip.amount, 0);
+		assert_eq!(breakdown.total, store_value_fee());
+	})
+}
@@ -0,0 +1,143 @@
+//! A `Vec` that can never hold more than `S::get()` items.
+//!
+//! frame-support 3.0 does not ship a `BoundedVec` yet, so this is a minimal version with the same
+//! interface, covering what the membership sets need. Decoding fails for vectors over the bound, so a storage
+//! item of this type can't be read back longer than the runtime allows.
+
+use frame_support::traits::Get;
+use parity_scale_codec::{Decode, Encode, EncodeLike, Error, Input, Output};
+use sp_std::{convert::TryFrom, fmt, marker::PhantomData, ops::Deref, prelude::*};
+
+/// A `Vec` with at most `S::get()` items. It can be read like a `Vec`, but only changed through
+/// methods that respect the bound.
+pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S: Get<u32>> BoundedVec<T, S> {
+	/// The largest number of items this vector can hold.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Inserts `element` at `index`, shifting everything after it to the right. Returns the
+	/// element back if the vector is already full.
+	///
+	/// Panics if `index > len`, as `Vec::insert` does.
+	pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), T> {
+		if self.0.len() < Self::bound() {
+			self.0.insert(index, element);
+			Ok(())
+		} else {
+			Err(element)
+		}
+	}
+
+	/// Appends `element`, unless the vector is already full.
+	pub fn try_push(&mut self, element: T) -> Result<(), T> {
+		if self.0.len() < Self::bound() {
+			self.0.push(element);
+			Ok(())
+		} else {
+			Err(element)
+		}
+	}
+}
+
+impl<T, S> BoundedVec<T, S> {
+	/// Removes and returns the item at `index`, shifting everything after it to the left.
+	///
+	/// Panics if `index` is out of bounds, as `Vec::remove` does.
+	pub fn remove(&mut self, index: usize) -> T {
+		self.0.remove(index)
+	}
+
+	/// The items, as a plain `Vec`.
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
+	type Error = ();
+
+	fn try_from(items: Vec<T>) -> Result<Self, ()> {
+		if items.len() <= Self::bound() {
+			Ok(Self(items, PhantomData))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T, S> Default for BoundedVec<T, S> {
+	fn default() -> Self {
+		Self(Vec::new(), PhantomData)
+	}
+}
+
+impl<T: Clone, S> Clone for BoundedVec<T, S> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone(), PhantomData)
+	}
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedVec<T, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<T: Eq, S> Eq for BoundedVec<T, S> {}
+
+impl<T: PartialEq, S> PartialEq<Vec<T>> for BoundedVec<T, S> {
+	fn eq(&self, other: &Vec<T>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedVec<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_tuple("BoundedVec").field(&self.0).finish()
+	}
+}
+
+impl<T, S> Deref for BoundedVec<T, S> {
+	type Target = Vec<T>;
+
+	fn deref(&self) -> &Vec<T> {
+		&self.0
+	}
+}
+
+impl<T, S> IntoIterator for BoundedVec<T, S> {
+	type Item = T;
+	type IntoIter = sp_std::vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+// Encoded exactly like the inner `Vec`, so the storage layout is unchanged.
+impl<T: Encode, S> Encode for BoundedVec<T, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<T: Encode, S> EncodeLike for BoundedVec<T, S> {}
+impl<T: Encode, S> EncodeLike<Vec<T>> for BoundedVec<T, S> {}
+
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let items = Vec::<T>::decode(input)?;
+		Self::try_from(items).map_err(|_| "BoundedVec exceeds its limit".into())
+	}
+}
@@ -1,11 +1,182 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_std::collections::btree_set::BTreeSet;
+mod bounded_vec;
+
+pub use bounded_vec::BoundedVec;
+#[cfg(feature = "runtime-benchmarks")]
+use frame_support::dispatch::DispatchResult;
+use frame_support::{
+	dispatch::DispatchError,
+	traits::{EnsureOrigin, OnUnbalanced, ReservableCurrency},
+	RuntimeDebug,
+};
+use frame_system::RawOrigin;
+use parity_scale_codec::{Decode, Encode};
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, vec::Vec};
 
 /// Types that implement the AccountSet trait are able to supply a set of accounts
 /// The trait is generic over the notion of Account used.
 pub trait AccountSet {
-	type AccountId;
+	type AccountId: Ord;
 
 	fn accounts() -> BTreeSet<Self::AccountId>;
+
+	/// Whether `who` is in the set. The default collects the whole set, so implementors should
+	/// override it with a check against their own storage.
+	fn contains(who: &Self::AccountId) -> bool {
+		Self::accounts().contains(who)
+	}
+
+	/// The number of accounts in the set. The default collects the whole set, so implementors
+	/// should override it when they track the size.
+	fn count() -> u32 {
+		Self::accounts().len() as u32
+	}
+
+	/// Add `who` to the set, so benchmarks of pallets that rely on the set can set up members.
+	/// Implementors insert the account directly, without deposits or approvals, but still respect
+	/// their bound and report the change to their `OnMembershipChanged` handler.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &Self::AccountId) -> DispatchResult;
+
+	/// The most accounts the set can hold, so benchmarks can fill it with `add`.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_count() -> u32;
+}
+
+/// Types that implement the OnMembershipChanged trait are told when an account joins or leaves a
+/// set, so they can keep their own state in sync without scanning the whole set.
+pub trait OnMembershipChanged<AccountId> {
+	/// `who` was added to the set
+	fn on_member_added(who: &AccountId);
+
+	/// `who` was removed from the set
+	fn on_member_removed(who: &AccountId);
+}
+
+/// Ignores membership changes, for runtimes that have nothing to keep in sync
+impl<AccountId> OnMembershipChanged<AccountId> for () {
+	fn on_member_added(_: &AccountId) {}
+
+	fn on_member_removed(_: &AccountId) {}
+}
+
+/// An origin check that passes for accounts in `Set`, and returns the account
+pub struct EnsureMember<Set>(PhantomData<Set>);
+
+impl<O, Set> EnsureOrigin<O> for EnsureMember<Set>
+where
+	O: Into<Result<RawOrigin<Set::AccountId>, O>> + From<RawOrigin<Set::AccountId>>,
+	Set: AccountSet,
+{
+	type Success = Set::AccountId;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if Set::contains(&who) => Ok(who),
+			o => Err(O::from(o)),
+		})
+	}
+
+	/// Signs with the first account in `Set`, so the set must not be empty
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		let who = Set::accounts()
+			.into_iter()
+			.next()
+			.expect("EnsureMember needs at least one member to succeed");
+		O::from(RawOrigin::Signed(who))
+	}
+}
+
+/// An origin check that passes for accounts in `Set`, returning `Some` account, and for root,
+/// returning `None`
+pub struct EnsureMemberOrRoot<Set>(PhantomData<Set>);
+
+impl<O, Set> EnsureOrigin<O> for EnsureMemberOrRoot<Set>
+where
+	O: Into<Result<RawOrigin<Set::AccountId>, O>> + From<RawOrigin<Set::AccountId>>,
+	Set: AccountSet,
+{
+	type Success = Option<Set::AccountId>;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Root => Ok(None),
+			RawOrigin::Signed(who) if Set::contains(&who) => Ok(Some(who)),
+			o => Err(O::from(o)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::Root)
+	}
+}
+
+/// A pending request to join a set, backed by a deposit and admitted by enough members' votes
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct MembershipRequest<AccountId, Balance> {
+	/// The deposit reserved from the applicant until the request is resolved
+	pub deposit: Balance,
+	/// The accounts that voted to admit the applicant
+	pub votes: Vec<AccountId>,
+}
+
+/// The voter has already voted for the request
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AlreadyVoted;
+
+impl<AccountId: PartialEq, Balance> MembershipRequest<AccountId, Balance> {
+	/// A request without any votes
+	pub fn new(deposit: Balance) -> Self {
+		Self {
+			deposit,
+			votes: Vec::new(),
+		}
+	}
+
+	/// Reserves `deposit` from `applicant` in `Currency`, and returns a request backed by it
+	pub fn reserve<Currency>(applicant: &AccountId, deposit: Balance) -> Result<Self, DispatchError>
+	where
+		Currency: ReservableCurrency<AccountId, Balance = Balance>,
+	{
+		Currency::reserve(applicant, deposit)?;
+		Ok(Self::new(deposit))
+	}
+
+	/// Resolves an admitted or withdrawn request by returning the deposit to `applicant`
+	pub fn refund<Currency>(self, applicant: &AccountId)
+	where
+		Currency: ReservableCurrency<AccountId, Balance = Balance>,
+	{
+		Currency::unreserve(applicant, self.deposit);
+	}
+
+	/// Resolves a rejected request by slashing the deposit from `applicant` and handing it to
+	/// `Slash`
+	pub fn slash<Currency, Slash>(self, applicant: &AccountId)
+	where
+		Currency: ReservableCurrency<AccountId, Balance = Balance>,
+		Slash: OnUnbalanced<Currency::NegativeImbalance>,
+	{
+		let imbalance = Currency::slash_reserved(applicant, self.deposit).0;
+		Slash::on_unbalanced(imbalance);
+	}
+
+	/// Records `voter`'s vote, and returns whether the votes that count reach `threshold`. Votes
+	/// from accounts that have left the set since are dropped, so they never count toward it.
+	pub fn vote(
+		&mut self,
+		voter: AccountId,
+		threshold: u32,
+		is_member: impl FnMut(&AccountId) -> bool,
+	) -> Result<bool, AlreadyVoted> {
+		if self.votes.contains(&voter) {
+			return Err(AlreadyVoted);
+		}
+		self.votes.retain(is_member);
+		self.votes.push(voter);
+		Ok(self.votes.len() as u32 >= threshold)
+	}
 }